@@ -15,6 +15,7 @@ from .alkahest_py import (
     PyERC721PaymentObligationData as ERC721PaymentObligationData,
     PyERC1155EscrowObligationData as ERC1155EscrowObligationData,
     PyERC1155PaymentObligationData as ERC1155PaymentObligationData,
+    PyTokenBundleEscrowObligationData as TokenBundleEscrowObligationData,
     StringObligationClient,
     PyStringObligationData as StringObligationData,
     OracleClient,
@@ -24,6 +25,11 @@ from .alkahest_py import (
     PyArbitrateOptions as ArbitrateOptions,
     PyTrustedOracleArbiterDemandData as TrustedOracleArbiterDemandData,
     PyErc20Data as Erc20Data,
+    PyTransactionBatch as TransactionBatch,
+    PySimulationReport as SimulationReport,
+    PySimulationStep as SimulationStep,
+    PyPendingTx as PendingTx,
+    PyEscrowTerms as EscrowTerms,
     # Address Configuration Classes
     PyErc20Addresses as Erc20Addresses,
     PyErc721Addresses as Erc721Addresses,
@@ -35,12 +41,33 @@ from .alkahest_py import (
     # IEAS Types
     PyAttestation as Attestation,
     PyAttestationRequest as AttestationRequest,
+    PyAttestationBuilder as AttestationBuilder,
     PyAttestationRequestData as AttestationRequestData,
     PyAttested as Attested,
     PyRevocationRequest as RevocationRequest,
     PyRevocationRequestData as RevocationRequestData,
     PyRevoked as Revoked,
     PyTimestamped as Timestamped,
+    expiration_in,
+    TransactionRevertError,
+    InsufficientBalanceError,
+    ReadOnlyClientError,
+    encode_uid_arbiter_demand,
+    encode_recipient_arbiter_demand,
+    encode_intrinsics_arbiter_demand,
+    encode_intrinsics_arbiter_2_demand,
+    demand_erc20_payment,
+    demand_hash,
+    normalize_schema_uid,
+    compute_schema_uid,
+    decode_attested_log,
+    decode_revoked_log,
+    decode_escrow_claimed_log,
+    decode_raw_log,
+    import_offer,
+    register_demand_decoder,
+    unregister_demand_decoder,
+    registered_demand_decoders,
 )
 
 __all__ = [
@@ -56,6 +83,7 @@ __all__ = [
     "ERC721PaymentObligationData",
     "ERC1155EscrowObligationData",
     "ERC1155PaymentObligationData",
+    "TokenBundleEscrowObligationData",
     "StringObligationClient",
     "StringObligationData",
     "DecodedAttestation",
@@ -66,6 +94,11 @@ __all__ = [
     "ArbitrateOptions",
     "TrustedOracleArbiterDemandData",
     "Erc20Data",
+    "TransactionBatch",
+    "SimulationReport",
+    "SimulationStep",
+    "PendingTx",
+    "EscrowTerms",
     # Address Configuration Classes
     "Erc20Addresses",
     "Erc721Addresses", 
@@ -77,10 +110,31 @@ __all__ = [
     # IEAS Types
     "Attestation",
     "AttestationRequest",
+    "AttestationBuilder",
     "AttestationRequestData",
     "Attested",
     "RevocationRequest",
     "RevocationRequestData",
     "Revoked",
     "Timestamped",
+    "expiration_in",
+    "TransactionRevertError",
+    "InsufficientBalanceError",
+    "ReadOnlyClientError",
+    "encode_uid_arbiter_demand",
+    "encode_recipient_arbiter_demand",
+    "encode_intrinsics_arbiter_demand",
+    "encode_intrinsics_arbiter_2_demand",
+    "demand_erc20_payment",
+    "demand_hash",
+    "normalize_schema_uid",
+    "compute_schema_uid",
+    "decode_attested_log",
+    "decode_revoked_log",
+    "decode_escrow_claimed_log",
+    "decode_raw_log",
+    "import_offer",
+    "register_demand_decoder",
+    "unregister_demand_decoder",
+    "registered_demand_decoders",
 ]
\ No newline at end of file
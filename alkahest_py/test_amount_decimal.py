@@ -0,0 +1,30 @@
+from decimal import Decimal
+
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    ERC1155EscrowObligationData,
+    ERC1155PaymentObligationData,
+)
+
+TOKEN = "0x" + "11" * 20
+PARTY = "0x" + "22" * 20
+
+
+def test_erc20_amount_decimal():
+    escrow = ERC20EscrowObligationData(TOKEN, 1_500_000, PARTY, b"")
+    assert escrow.amount_decimal(6) == "1.500000"
+    assert Decimal(escrow.amount_decimal(6)) == Decimal("1.5")
+    assert escrow.amount_decimal(0) == "1500000"
+
+    payment = ERC20PaymentObligationData(TOKEN, 5, PARTY)
+    assert payment.amount_decimal(18) == "0.000000000000000005"
+
+
+def test_erc1155_amount_decimal():
+    amount = str(10**30 + 1)
+    escrow = ERC1155EscrowObligationData(TOKEN, "1", amount, PARTY, b"")
+    assert escrow.amount_decimal(18) == "1000000000000.000000000000000001"
+
+    payment = ERC1155PaymentObligationData(TOKEN, "1", "42", PARTY)
+    assert payment.amount_decimal(2) == "0.42"
@@ -0,0 +1,33 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, MockERC721
+
+
+@pytest.mark.asyncio
+async def test_erc20_approval_status():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    MockERC20(token, env.god_wallet_provider).transfer(env.alice, 100)
+
+    status = await env.alice_client.erc20.approval_status(token)
+    assert status == {"escrow": "0", "payment": "0"}
+
+    await env.alice_client.erc20.approve({"address": token, "value": 100}, "escrow")
+    await env.alice_client.erc20.approve({"address": token, "value": 25}, "payment")
+
+    status = await env.alice_client.erc20.approval_status(token)
+    assert status == {"escrow": "100", "payment": "25"}
+
+
+@pytest.mark.asyncio
+async def test_erc721_approval_status():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc721_a
+    MockERC721(token, env.god_wallet_provider).mint(env.alice)
+
+    status = await env.alice_client.erc721.approval_status(token)
+    assert status == {"escrow": False, "payment": False}
+
+    await env.alice_client.erc721.approve_all(token, "payment")
+
+    status = await env.alice_client.erc721.approval_status(token)
+    assert status == {"escrow": False, "payment": True}
@@ -0,0 +1,38 @@
+from alkahest_py import (
+    EnvTestManager,
+    encode_intrinsics_arbiter_2_demand,
+    encode_intrinsics_arbiter_demand,
+    encode_recipient_arbiter_demand,
+    encode_uid_arbiter_demand,
+)
+
+
+def test_encode_uid_arbiter_demand():
+    env = EnvTestManager()
+    uid = "0x" + "ab" * 32
+
+    encoded = encode_uid_arbiter_demand(uid, env.addresses)
+    assert encoded["arbiter"].lower() == env.addresses.arbiters_addresses.uid_arbiter.lower()
+    assert encoded["demand"] == bytes.fromhex("ab" * 32)
+
+
+def test_encode_recipient_arbiter_demand():
+    env = EnvTestManager()
+
+    encoded = encode_recipient_arbiter_demand(env.bob, env.addresses)
+    assert encoded["arbiter"].lower() == env.addresses.arbiters_addresses.recipient_arbiter.lower()
+    assert encoded["demand"] == bytes(12) + bytes.fromhex(env.bob[2:])
+
+
+def test_encode_intrinsics_arbiter_demands():
+    env = EnvTestManager()
+    arbiters = env.addresses.arbiters_addresses
+
+    encoded = encode_intrinsics_arbiter_demand(env.addresses)
+    assert encoded["arbiter"].lower() == arbiters.intrinsics_arbiter.lower()
+    assert encoded["demand"] == b""
+
+    schema = "0x" + "cd" * 32
+    encoded = encode_intrinsics_arbiter_2_demand(schema, env.addresses)
+    assert encoded["arbiter"].lower() == arbiters.intrinsics_arbiter_2.lower()
+    assert encoded["demand"] == bytes.fromhex("cd" * 32)
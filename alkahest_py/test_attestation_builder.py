@@ -0,0 +1,49 @@
+import time
+
+import pytest
+from alkahest_py import AttestationBuilder, AttestationRequest
+
+SCHEMA = "0x1234567890123456789012345678901234567890123456789012345678901234"
+RECIPIENT = "0x1234567890123456789012345678901234567890"
+
+
+def test_attestation_builder_defaults():
+    request = AttestationBuilder().schema(SCHEMA).build()
+    assert isinstance(request, AttestationRequest)
+    assert request.schema == SCHEMA
+    assert request.data.recipient == "0x0000000000000000000000000000000000000000"
+    assert request.data.expiration_time == 0
+    assert request.data.revocable is True
+    assert int(request.data.ref_uid, 16) == 0
+    assert request.data.data == b""
+    assert request.data.value == 0
+
+
+def test_attestation_builder_fluent():
+    ref_uid = "0x" + "ab" * 32
+    request = (
+        AttestationBuilder()
+        .schema(SCHEMA)
+        .recipient(RECIPIENT)
+        .data(b"payload")
+        .expiration_in(3600)
+        .revocable(False)
+        .ref_uid(ref_uid)
+        .value(5)
+        .build()
+    )
+    assert request.data.recipient.lower() == RECIPIENT.lower()
+    assert request.data.data == b"payload"
+    assert abs(request.data.expiration_time - (int(time.time()) + 3600)) < 5
+    assert request.data.revocable is False
+    assert request.data.ref_uid == ref_uid
+    assert request.data.value == 5
+
+
+def test_attestation_builder_validation():
+    with pytest.raises(ValueError, match="schema is required"):
+        AttestationBuilder().recipient(RECIPIENT).build()
+    with pytest.raises(ValueError):
+        AttestationBuilder().schema(SCHEMA).recipient("not an address").build()
+    with pytest.raises(ValueError):
+        AttestationBuilder().schema("0x1234").build()
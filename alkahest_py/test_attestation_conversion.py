@@ -0,0 +1,30 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+FIELDS = [
+    "uid", "schema", "ref_uid", "time", "expiration_time",
+    "revocation_time", "recipient", "attester", "revocable",
+]
+
+
+@pytest.mark.asyncio
+async def test_attestation_oracle_attestation_round_trip():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    uid = (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0))["log"]["uid"]
+
+    attestation = await env.alice_client.attestation.get_attestation(uid)
+    oracle_attestation = attestation.to_oracle_attestation()
+
+    for field in FIELDS:
+        assert getattr(oracle_attestation, field) == getattr(attestation, field)
+    assert oracle_attestation.data == "0x" + attestation.data.hex()
+
+    back = oracle_attestation.to_attestation()
+    for field in FIELDS:
+        assert getattr(back, field) == getattr(attestation, field)
+    assert back.data == attestation.data
@@ -0,0 +1,27 @@
+import pytest
+from alkahest_py import EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_attestation_count():
+    env = EnvTestManager()
+    string_obligation = env.addresses.string_obligation_addresses.obligation
+    assert await env.alice_client.attestation_count(string_obligation) == 0
+
+    string_client = env.bob_client.string_obligation
+    first = await string_client.do_obligation("first", None)
+    await string_client.do_obligation("second", None)
+    schema = (await env.bob_client.attestation.get_attestation(first)).schema
+
+    assert await env.alice_client.attestation_count(string_obligation) == 2
+    assert await env.alice_client.attestation_count(string_obligation.upper().replace("0X", "0x")) == 2
+    assert await env.alice_client.attestation_count(string_obligation, schema) == 2
+    assert await env.alice_client.attestation_count(string_obligation, "0x" + "00" * 32) == 0
+    assert await env.alice_client.attestation_count(env.alice) == 0
+
+    # Only attestations from from_block on are counted
+    assert await env.alice_client.attestation_count(string_obligation, from_block=0) == 2
+    assert await env.alice_client.attestation_count(string_obligation, from_block=10**9) == 0
+
+    with pytest.raises(ValueError):
+        await env.alice_client.attestation_count("not an address")
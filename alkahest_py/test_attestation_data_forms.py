@@ -0,0 +1,45 @@
+import pytest
+from alkahest_py import (
+    Attestation,
+    AttestationBuilder,
+    AttestationRequestData,
+    OracleAttestation,
+)
+
+RECIPIENT = "0x" + "12" * 20
+UID = "0x" + "ab" * 32
+SCHEMA = "0x" + "cd" * 32
+REF_UID = "0x" + "00" * 32
+
+
+def test_request_data_accepts_bytes_or_hex():
+    from_bytes = AttestationRequestData(RECIPIENT, 0, True, REF_UID, b"\x01\xff", 0)
+    from_hex = AttestationRequestData(RECIPIENT, 0, True, REF_UID, "0x01FF", 0)
+    unprefixed = AttestationRequestData(RECIPIENT, 0, True, REF_UID, "01ff", 0)
+
+    for request_data in [from_bytes, from_hex, unprefixed]:
+        assert request_data.data == b"\x01\xff"
+        assert request_data.data_hex == "0x01ff"
+
+    with pytest.raises(ValueError):
+        AttestationRequestData(RECIPIENT, 0, True, REF_UID, "0xnothex", 0)
+
+
+def test_attestation_exposes_both_forms():
+    attestation = Attestation(UID, SCHEMA, 1, 0, 0, REF_UID, RECIPIENT, RECIPIENT, True, "0xbeef")
+    assert attestation.data == b"\xbe\xef"
+    assert attestation.data_bytes == b"\xbe\xef"
+    assert attestation.data_hex == "0xbeef"
+
+    oracle_attestation = OracleAttestation(
+        UID, SCHEMA, REF_UID, 1, 0, 0, RECIPIENT, RECIPIENT, True, "0xBEEF"
+    )
+    assert oracle_attestation.data_bytes == b"\xbe\xef"
+    assert oracle_attestation.data_hex == "0xbeef"
+
+
+def test_fetched_data_round_trips_into_request():
+    attestation = Attestation(UID, SCHEMA, 1, 0, 0, REF_UID, RECIPIENT, RECIPIENT, True, b"\x02")
+    builder = AttestationBuilder().schema(SCHEMA).recipient(RECIPIENT)
+    assert builder.data(attestation.data_hex).build().data.data == b"\x02"
+    assert builder.data(attestation.data).build().data.data == b"\x02"
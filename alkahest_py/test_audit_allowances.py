@@ -0,0 +1,24 @@
+import pytest
+from alkahest_py import EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_audit_allowances():
+    env = EnvTestManager()
+    token_a = env.mock_addresses.erc20_a
+    token_b = env.mock_addresses.erc20_b
+
+    await env.alice_client.erc20.approve({"address": token_a, "value": 100}, "escrow")
+    await env.alice_client.erc20.approve({"address": token_b, "value": 25}, "payment")
+
+    audit = await env.alice_client.erc20.audit_allowances([token_a, token_b])
+    by_token = {token.lower(): status for token, status in audit.items()}
+
+    assert by_token[token_a.lower()] == {"escrow": "100", "payment": "0"}
+    assert by_token[token_b.lower()] == {"escrow": "0", "payment": "25"}
+
+
+@pytest.mark.asyncio
+async def test_audit_allowances_empty():
+    env = EnvTestManager()
+    assert await env.alice_client.erc20.audit_allowances([]) == {}
@@ -0,0 +1,49 @@
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    EnvTestManager,
+    MockERC20,
+)
+
+
+@pytest.mark.asyncio
+async def test_barter_result():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    payment = await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid)
+    claim = await env.alice_client.wait_for_fulfillment(
+        env.addresses.erc20_addresses.escrow_obligation, buy_uid, 0
+    )
+
+    result = await env.alice_client.barter_result(buy_uid, claim)
+
+    assert result["buy_attestation"] == buy_uid
+    assert result["fulfillment"] == payment["log"]["uid"]
+    assert result["buyer"].lower() == env.alice.lower()
+    assert result["counterparty"].lower() == env.bob.lower()
+    assert result["gave_type"] == "erc20"
+    assert isinstance(result["gave"], ERC20EscrowObligationData)
+    assert result["gave"].amount == 100
+    assert result["received_type"] == "ERC20 payment"
+    assert isinstance(result["received"], ERC20PaymentObligationData)
+    assert result["received"].token.lower() == env.mock_addresses.erc20_b.lower()
+    assert result["received"].amount == 200
+    assert result["transaction_hash"] == payment["transaction_hash"]
+
+    other_claim = {**claim, "payment": "0x" + "00" * 31 + "01"}
+    with pytest.raises(ValueError, match="Claim is for escrow"):
+        await env.alice_client.barter_result(buy_uid, other_claim)
+
+    missing_claim = {**claim, "fulfillment": "0x" + "00" * 31 + "01"}
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.barter_result(buy_uid, missing_claim)
@@ -0,0 +1,54 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    MockERC20,
+    MockERC721,
+    MockERC1155,
+    TokenBundleEscrowObligationData,
+    demand_erc20_payment,
+)
+
+
+@pytest.mark.asyncio
+async def test_bundle_contents():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 50)
+    token_id = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider).mint(env.bob)
+    MockERC1155(env.mock_addresses.erc1155_a, env.god_wallet_provider).mint(env.bob, 1, 20)
+
+    bundle_data = {
+        "erc20s": [{"address": env.mock_addresses.erc20_b, "value": 25}],
+        "erc721s": [{"address": env.mock_addresses.erc721_a, "id": token_id}],
+        "erc1155s": [{"address": env.mock_addresses.erc1155_a, "id": 1, "value": 10}],
+    }
+    item = demand_erc20_payment(env.mock_addresses.erc20_a, 50, env.bob, env.addresses)
+
+    await env.bob_client.token_bundle.approve(bundle_data, "escrow")
+    result = await env.bob_client.token_bundle.buy_with_bundle(bundle_data, item, 0)
+    uid = result["log"]["uid"]
+
+    contents = await env.bob_client.token_bundle.bundle_contents(uid)
+    assert [e["value"] for e in contents["erc20s"]] == [25]
+    assert contents["erc20s"][0]["address"].lower() == env.mock_addresses.erc20_b.lower()
+    assert [e["id"] for e in contents["erc721s"]] == [token_id]
+    assert [(e["id"], e["value"]) for e in contents["erc1155s"]] == [(1, 10)]
+
+    escrow = await env.bob_client.attestation.get_attestation(uid)
+    decoded = TokenBundleEscrowObligationData.decode(escrow.data)
+    assert decoded.arbiter.lower() == item["arbiter"].lower()
+    assert bytes(decoded.demand) == item["demand"]
+    assert decoded.contents() == contents
+
+
+@pytest.mark.asyncio
+async def test_bundle_contents_rejects_other_escrows():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    await env.alice_client.erc20.approve(price, "escrow")
+    result = await env.alice_client.erc20.buy_erc20_for_erc20(
+        price, {"address": env.mock_addresses.erc20_b, "value": 1}, 0
+    )
+
+    with pytest.raises(ValueError, match="not a token bundle escrow"):
+        await env.alice_client.token_bundle.bundle_contents(result["log"]["uid"])
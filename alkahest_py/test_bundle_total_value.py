@@ -0,0 +1,52 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    MockERC20,
+    MockERC721,
+    MockERC1155,
+    TokenBundleEscrowObligationData,
+    demand_erc20_payment,
+)
+
+
+@pytest.mark.asyncio
+async def test_bundle_total_value_of():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 50)
+    erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+    first_id = erc721.mint(env.bob)
+    second_id = erc721.mint(env.bob)
+    erc1155 = MockERC1155(env.mock_addresses.erc1155_a, env.god_wallet_provider)
+    erc1155.mint(env.bob, 1, 20)
+    erc1155.mint(env.bob, 2, 20)
+
+    bundle_data = {
+        "erc20s": [{"address": env.mock_addresses.erc20_b, "value": 25}],
+        "erc721s": [
+            {"address": env.mock_addresses.erc721_a, "id": first_id},
+            {"address": env.mock_addresses.erc721_a, "id": second_id},
+        ],
+        "erc1155s": [
+            {"address": env.mock_addresses.erc1155_a, "id": 1, "value": 10},
+            {"address": env.mock_addresses.erc1155_a, "id": 2, "value": 5},
+        ],
+    }
+    item = demand_erc20_payment(env.mock_addresses.erc20_a, 50, env.bob, env.addresses)
+
+    await env.bob_client.token_bundle.approve(bundle_data, "escrow")
+    result = await env.bob_client.token_bundle.buy_with_bundle(bundle_data, item, 0)
+    escrow = await env.bob_client.attestation.get_attestation(result["log"]["uid"])
+    bundle = TokenBundleEscrowObligationData.decode(escrow.data)
+
+    assert [erc20["value"] for erc20 in bundle.erc20s] == [25]
+    assert len(bundle.erc721s) == 2
+    assert len(bundle.erc1155s) == 2
+
+    assert bundle.total_value_of(env.mock_addresses.erc20_b) == 25
+    assert bundle.total_value_of(env.mock_addresses.erc20_b.upper().replace("0X", "0x")) == 25
+    assert bundle.total_value_of(env.mock_addresses.erc721_a) == 2
+    assert bundle.total_value_of(env.mock_addresses.erc1155_a) == 15
+    assert bundle.total_value_of(env.mock_addresses.erc20_a) == 0
+
+    with pytest.raises(ValueError):
+        bundle.total_value_of("not an address")
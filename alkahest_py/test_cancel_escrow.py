@@ -0,0 +1,25 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_cancel_escrow_unsupported():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+
+    await env.alice_client.erc20.approve(price_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    with pytest.raises(NotImplementedError, match="reclaim_expired"):
+        await env.alice_client.erc20.cancel_escrow(buy_uid)
+
+    # The escrow is untouched
+    assert mock_erc20.balance_of(env.addresses.erc20_addresses.escrow_obligation) == 100
@@ -0,0 +1,34 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, MockERC20
+
+# Anvil's second account
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+ADDRESS = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+
+
+@pytest.mark.asyncio
+async def test_compare_permit_cost():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    MockERC20(token, env.god_wallet_provider).transfer(ADDRESS, 100)
+    client = AlkahestClient(PRIVATE_KEY, rpc_url=env.rpc_url, address_config=env.addresses)
+
+    comparison = await client.erc20.compare_permit_cost({"address": token, "value": 100})
+
+    assert comparison["approve_path"] > 0
+    # The mock token supports EIP-2612, and a permit saves a whole transaction
+    assert comparison["permit_path"] is not None
+    assert 0 < comparison["permit_path"] < comparison["approve_path"]
+    assert comparison["cheaper"] == "permit"
+
+    # Only simulated: no allowance was left behind
+    status = await client.erc20.approval_status(token)
+    assert status["escrow"] == "0"
+
+
+@pytest.mark.asyncio
+async def test_compare_permit_cost_needs_private_key():
+    env = EnvTestManager()
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 50}
+    with pytest.raises(ValueError, match="private key"):
+        await env.alice_client.erc20.compare_permit_cost(price_data)
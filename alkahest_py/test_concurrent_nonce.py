@@ -0,0 +1,64 @@
+import asyncio
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, MockERC721
+
+
+@pytest.mark.asyncio
+async def test_concurrent_writes_share_nonces():
+    """
+    Writes issued concurrently from different extension clients of the same
+    parent must not collide on nonces.
+    """
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+
+    mock_erc20.transfer(env.alice, 100)
+    token_id = mock_erc721.mint(env.alice)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    nft_data = {"address": env.mock_addresses.erc721_a, "id": token_id}
+
+    await asyncio.gather(
+        env.alice_client.erc20.approve(price_data, "escrow"),
+        env.alice_client.erc721.approve(nft_data, "payment"),
+    )
+
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+
+    escrow, payment = await asyncio.gather(
+        env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0),
+        env.alice_client.erc721.pay_with_erc721(nft_data, env.bob),
+    )
+
+    assert escrow["log"]["uid"] != payment["log"]["uid"]
+    assert mock_erc20.balance_of(env.addresses.erc20_addresses.escrow_obligation) == 100
+    assert mock_erc721.owner_of(token_id).lower() == env.bob.lower()
+
+
+@pytest.mark.asyncio
+async def test_same_key_on_two_nodes():
+    """
+    The same key connected to two nodes gets a manager per node, so writes on one
+    don't wait for the other's receipts.
+    """
+    env_a = EnvTestManager()
+    env_b = EnvTestManager()
+    for env in (env_a, env_b):
+        MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    await asyncio.gather(
+        *(
+            env.alice_client.erc20.pay_with_erc20(
+                {"address": env.mock_addresses.erc20_a, "value": 100}, env.bob
+            )
+            for env in (env_a, env_b)
+        )
+    )
+
+    for env in (env_a, env_b):
+        assert MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).balance_of(env.bob) == 100
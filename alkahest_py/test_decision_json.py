@@ -0,0 +1,35 @@
+import json
+
+from alkahest_py import Decision, OracleAttestation
+from alkahest_py.alkahest_py import PyListenResult as ListenResult
+
+
+def make_decision(uid: str, decision: bool, tx_hash: str) -> Decision:
+    attestation = OracleAttestation(
+        uid, "0x" + "11" * 32, "0x" + "00" * 32, 1, 0, 0,
+        "0x" + "22" * 20, "0x" + "33" * 20, True, "0x",
+    )
+    return Decision(attestation, decision, tx_hash)
+
+
+def test_decision_to_json():
+    decision = make_decision("0x" + "aa" * 32, True, "0x" + "bb" * 32)
+    assert json.loads(decision.to_json()) == {
+        "attestation_uid": "0x" + "aa" * 32,
+        "decision": True,
+        "transaction_hash": "0x" + "bb" * 32,
+    }
+
+
+def test_listen_result_to_jsonl():
+    decisions = [
+        make_decision("0x" + "aa" * 32, True, "0x" + "01" * 32),
+        make_decision("0x" + "cc" * 32, False, "0x" + "02" * 32),
+    ]
+    result = ListenResult(decisions, "0x1")
+
+    lines = result.to_jsonl().splitlines()
+    assert [json.loads(line)["decision"] for line in lines] == [True, False]
+    assert json.loads(lines[1])["attestation_uid"] == "0x" + "cc" * 32
+    assert result.to_jsonl().endswith("\n")
+    assert ListenResult([], "0x1").to_jsonl() == ""
@@ -0,0 +1,27 @@
+import pytest
+from alkahest_py import ERC20EscrowObligationData, StringObligationData
+
+TOKEN = "0x1111111111111111111111111111111111111111"
+ARBITER = "0x2222222222222222222222222222222222222222"
+
+
+def test_decode_hex_matches_decode():
+    obligation = ERC20EscrowObligationData(TOKEN, 100, ARBITER, b"\x01\x02\x03")
+    encoded = bytes(ERC20EscrowObligationData.encode(obligation))
+
+    for hex_str in ["0x" + encoded.hex(), encoded.hex(), "0x" + encoded.hex().upper()]:
+        decoded = ERC20EscrowObligationData.decode_hex(hex_str)
+        assert decoded.amount == 100
+        assert decoded.token.lower() == TOKEN
+        assert decoded.arbiter.lower() == ARBITER
+        assert bytes(decoded.demand) == b"\x01\x02\x03"
+
+
+def test_decode_hex_string_obligation():
+    encoded = bytes(StringObligationData.encode(StringObligationData("hello")))
+    assert StringObligationData.decode_hex("0x" + encoded.hex()).item == "hello"
+
+
+def test_decode_hex_rejects_invalid_hex():
+    with pytest.raises(ValueError):
+        ERC20EscrowObligationData.decode_hex("0xnothex")
@@ -0,0 +1,30 @@
+import pytest
+from alkahest_py import ERC20EscrowObligationData, StringObligationData
+
+TOKEN = "0x1111111111111111111111111111111111111111"
+ARBITER = "0x2222222222222222222222222222222222222222"
+
+
+def encoded_escrows(count):
+    return [
+        bytes(ERC20EscrowObligationData.encode(ERC20EscrowObligationData(TOKEN, i, ARBITER, b"")))
+        for i in range(count)
+    ]
+
+
+def test_decode_many_keeps_order():
+    # Large enough to be split across threads
+    decoded = ERC20EscrowObligationData.decode_many(encoded_escrows(1000))
+    assert [obligation.amount for obligation in decoded] == list(range(1000))
+
+
+def test_decode_many_empty():
+    assert StringObligationData.decode_many([]) == []
+
+
+def test_decode_many_names_first_failing_index():
+    items = encoded_escrows(1000)
+    items[700] = b"\x01"
+    items[300] = b"\x02"
+    with pytest.raises(ValueError, match="item 300"):
+        ERC20EscrowObligationData.decode_many(items)
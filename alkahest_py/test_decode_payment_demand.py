@@ -0,0 +1,40 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    MockERC721,
+    ERC721EscrowObligationData,
+    ERC20PaymentObligationData,
+)
+
+
+@pytest.mark.asyncio
+async def test_decode_payment_demand():
+    env = EnvTestManager()
+    mock_erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+    token_id = mock_erc721.mint(env.alice)
+
+    bid_data = {"address": env.mock_addresses.erc721_a, "id": token_id}
+    ask_data = {"address": env.mock_addresses.erc20_a, "value": 250}
+
+    await env.alice_client.erc721.approve(bid_data, "escrow")
+    result = await env.alice_client.erc721.buy_erc20_with_erc721(bid_data, ask_data, 0)
+
+    escrow = await env.alice_client.attestation.get_attestation(result["log"]["uid"])
+    escrow_data = ERC721EscrowObligationData.decode(escrow.data)
+
+    demand = escrow_data.decode_payment_demand(env.addresses)
+    assert isinstance(demand, ERC20PaymentObligationData)
+    assert demand.token.lower() == env.mock_addresses.erc20_a.lower()
+    assert demand.amount == 250
+    assert demand.payee.lower() == env.alice.lower()
+
+
+def test_decode_payment_demand_unknown_arbiter():
+    escrow_data = ERC721EscrowObligationData(
+        token="0x0000000000000000000000000000000000000001",
+        token_id="1",
+        arbiter="0x0000000000000000000000000000000000000002",
+        demand=b"",
+    )
+    with pytest.raises(ValueError):
+        escrow_data.decode_payment_demand()
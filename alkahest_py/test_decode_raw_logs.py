@@ -0,0 +1,42 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    MockERC20,
+    decode_attested_log,
+    decode_escrow_claimed_log,
+    decode_raw_log,
+    decode_revoked_log,
+)
+
+
+@pytest.mark.asyncio
+async def test_decode_raw_logs_from_receipt():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    arbiter_data = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b"demand"}
+    await env.alice_client.erc20.approve(price_data, "escrow")
+    result = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0, include_logs=True)
+
+    raw = next(log for log in result["logs"] if log["event"] == "Attested")
+    attested = decode_attested_log(raw["topics"], bytes(raw["data"]))
+    assert attested == result["log"]
+
+    transfer = next(log for log in result["logs"] if log["event"] == "Transfer")
+    decoded = decode_raw_log(transfer["address"], transfer["topics"], bytes(transfer["data"]))
+    assert decoded["event"] == "Transfer"
+    assert decoded["args"] == transfer["args"]
+
+    # A log of another event is rejected rather than misdecoded
+    with pytest.raises(ValueError, match="Revoked"):
+        decode_revoked_log(raw["topics"], bytes(raw["data"]))
+    with pytest.raises(ValueError, match="EscrowClaimed"):
+        decode_escrow_claimed_log(transfer["topics"], bytes(transfer["data"]))
+
+
+def test_decode_raw_log_unknown_event():
+    topic = "0x" + "ab" * 32
+    decoded = decode_raw_log("0x" + "11" * 20, [topic], b"\x01")
+    assert decoded["event"] is None
+    assert decoded["topics"] == [topic]
@@ -0,0 +1,48 @@
+import json
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_escrow_uses_default_arbiter():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 200)
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+
+    arbiter = env.addresses.erc20_addresses.payment_obligation
+    env.alice_client.set_default_arbiter(arbiter, b"default demand")
+
+    escrow = await env.alice_client.erc20.buy_with_erc20(price, None, 0)
+    offer = json.loads(await env.alice_client.export_offer(escrow["log"]["uid"]))
+    assert offer["arbiter"].lower() == arbiter.lower()
+    assert offer["demand"] == "0x" + b"default demand".hex()
+
+    # An explicit item still wins over the default
+    escrow = await env.alice_client.erc20.buy_with_erc20(
+        price, {"arbiter": arbiter, "demand": b"explicit"}, 0
+    )
+    offer = json.loads(await env.alice_client.export_offer(escrow["log"]["uid"]))
+    assert offer["demand"] == "0x" + b"explicit".hex()
+
+
+@pytest.mark.asyncio
+async def test_escrow_without_item_or_default():
+    env = EnvTestManager()
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+
+    with pytest.raises(ValueError, match="default arbiter"):
+        await env.alice_client.erc20.buy_with_erc20(price, None, 0)
+
+    env.alice_client.set_default_arbiter(env.addresses.erc20_addresses.payment_obligation, b"")
+    env.alice_client.clear_default_arbiter()
+    with pytest.raises(ValueError, match="default arbiter"):
+        await env.alice_client.erc20.buy_with_erc20(price, None, 0)
+
+
+def test_set_default_arbiter_rejects_bad_address():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        env.alice_client.set_default_arbiter("not an address", b"")
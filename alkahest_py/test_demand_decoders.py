@@ -0,0 +1,98 @@
+import time
+
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    EnvTestManager,
+    MockERC20,
+    import_offer,
+    register_demand_decoder,
+    registered_demand_decoders,
+    unregister_demand_decoder,
+)
+
+TOKEN = "0x" + "11" * 20
+CUSTOM_ARBITER = "0x" + "ab" * 20
+
+
+def test_custom_demand_decoder():
+    escrow = ERC20EscrowObligationData(TOKEN, 100, CUSTOM_ARBITER, bytes.fromhex("00ff"))
+    assert escrow.decode_demand() == bytes.fromhex("00ff")
+
+    register_demand_decoder(CUSTOM_ARBITER, lambda demand: {"flag": demand[-1]})
+    try:
+        assert CUSTOM_ARBITER in registered_demand_decoders()
+        assert escrow.decode_demand() == {"flag": 255}
+    finally:
+        assert unregister_demand_decoder(CUSTOM_ARBITER)
+
+    assert escrow.decode_demand() == bytes.fromhex("00ff")
+    assert not unregister_demand_decoder(CUSTOM_ARBITER)
+
+
+def test_raising_decoder_falls_back_to_raw_demand():
+    escrow = ERC20EscrowObligationData(TOKEN, 100, CUSTOM_ARBITER, bytes.fromhex("00ff"))
+
+    def decoder(demand):
+        raise ValueError("can't decode")
+
+    register_demand_decoder(CUSTOM_ARBITER, decoder)
+    try:
+        assert escrow.decode_demand() == bytes.fromhex("00ff")
+    finally:
+        unregister_demand_decoder(CUSTOM_ARBITER)
+
+
+def test_payment_demands_decode_without_registry():
+    env = EnvTestManager()
+    payment = ERC20PaymentObligationData(TOKEN, 5, env.alice)
+    escrow = ERC20EscrowObligationData(
+        TOKEN, 100, env.addresses.erc20_addresses.payment_obligation, payment.encode_self()
+    )
+
+    demand = escrow.decode_demand(env.addresses)
+    assert isinstance(demand, ERC20PaymentObligationData)
+    assert demand.amount == 5
+
+
+def test_decoder_must_be_callable():
+    with pytest.raises(TypeError):
+        register_demand_decoder(CUSTOM_ARBITER, "not a function")
+
+
+@pytest.mark.asyncio
+async def test_registered_decoders_describe_escrows():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    item = {"arbiter": CUSTOM_ARBITER, "demand": bytes.fromhex("00ff")}
+    escrow = await env.alice_client.erc20.permit_and_buy_with_erc20(
+        price, item, int(time.time()) + 3600
+    )
+    uid = escrow["log"]["uid"]
+
+    register_demand_decoder(CUSTOM_ARBITER, lambda demand: {"flag": demand[-1]})
+    try:
+        offer = import_offer(await env.alice_client.export_offer(uid))
+        assert offer["demand_summary"] == {"flag": 255}
+        summary = await env.alice_client.explain_attestation(uid, env.addresses)
+        assert "demand:      {'flag': 255}" in summary
+        with pytest.raises(ValueError, match="other than a payment"):
+            await env.bob_client.fulfillment_economics(uid)
+    finally:
+        unregister_demand_decoder(CUSTOM_ARBITER)
+
+    register_demand_decoder(CUSTOM_ARBITER, lambda demand: demand[5])
+    try:
+        offer = import_offer(await env.alice_client.export_offer(uid))
+        assert offer["demand_summary"] is None
+        summary = await env.alice_client.explain_attestation(uid, env.addresses)
+        assert "demand:      2 bytes" in summary
+    finally:
+        unregister_demand_decoder(CUSTOM_ARBITER)
+
+    offer = import_offer(await env.alice_client.export_offer(uid))
+    assert offer["demand_summary"] is None
+    summary = await env.alice_client.explain_attestation(uid, env.addresses)
+    assert "demand:      2 bytes" in summary
@@ -0,0 +1,44 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    MockERC721,
+    ERC20PaymentObligationData,
+    ERC721EscrowObligationData,
+    demand_erc20_payment,
+)
+
+
+def test_demand_erc20_payment_round_trip():
+    env = EnvTestManager()
+
+    encoded = demand_erc20_payment(env.mock_addresses.erc20_a, 250, env.alice, env.addresses)
+    assert encoded["arbiter"].lower() == env.addresses.erc20_addresses.payment_obligation.lower()
+
+    demand = ERC20PaymentObligationData.decode(encoded["demand"])
+    assert demand.token.lower() == env.mock_addresses.erc20_a.lower()
+    assert demand.amount == 250
+    assert demand.payee.lower() == env.alice.lower()
+
+
+@pytest.mark.asyncio
+async def test_demand_erc20_payment_matches_barter_helper():
+    env = EnvTestManager()
+    mock_erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+    token_id = mock_erc721.mint(env.alice)
+
+    bid_data = {"address": env.mock_addresses.erc721_a, "id": token_id}
+    await env.alice_client.erc721.approve(bid_data, "escrow")
+
+    item = demand_erc20_payment(env.mock_addresses.erc20_a, 250, env.alice, env.addresses)
+    result = await env.alice_client.erc721.buy_with_erc721(bid_data, item, 0)
+
+    escrow = await env.alice_client.attestation.get_attestation(result["log"]["uid"])
+    escrow_data = ERC721EscrowObligationData.decode(escrow.data)
+    assert escrow_data.arbiter.lower() == item["arbiter"].lower()
+    assert bytes(escrow_data.demand) == item["demand"]
+
+
+def test_demand_erc20_payment_invalid_address():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        demand_erc20_payment("not an address", 1, env.alice, env.addresses)
@@ -0,0 +1,38 @@
+import pytest
+from eth_utils import keccak
+from alkahest_py import EnvTestManager, demand_erc20_payment, demand_hash
+
+ARBITER = "0x00000000000000000000000000000000000000aa"
+
+
+def abi_encode_arbiter_demand(arbiter: str, demand: bytes) -> bytes:
+    padded = demand + bytes(-len(demand) % 32)
+    return (
+        bytes(12)
+        + bytes.fromhex(arbiter[2:])
+        + (64).to_bytes(32, "big")
+        + len(demand).to_bytes(32, "big")
+        + padded
+    )
+
+
+def test_demand_hash_matches_abi_encoding():
+    demand = b"custom demand data"
+    expected = "0x" + keccak(abi_encode_arbiter_demand(ARBITER, demand)).hex()
+    assert demand_hash({"arbiter": ARBITER, "demand": demand}) == expected
+
+
+def test_demand_hash_distinguishes_terms():
+    env = EnvTestManager()
+    item = demand_erc20_payment(env.mock_addresses.erc20_a, 100, env.bob, env.addresses)
+    same = demand_erc20_payment(env.mock_addresses.erc20_a, 100, env.bob, env.addresses)
+    other = demand_erc20_payment(env.mock_addresses.erc20_a, 101, env.bob, env.addresses)
+
+    assert demand_hash(item) == demand_hash(same)
+    assert demand_hash(item) != demand_hash(other)
+    assert demand_hash(item) != demand_hash({"arbiter": ARBITER, "demand": item["demand"]})
+
+
+def test_demand_hash_invalid_arbiter():
+    with pytest.raises(ValueError):
+        demand_hash({"arbiter": "nope", "demand": b""})
@@ -0,0 +1,14 @@
+import re
+
+import pytest
+from alkahest_py import EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_eas_info():
+    env = EnvTestManager()
+
+    assert env.alice_client.eas_address().lower() == env.addresses.attestation_addresses.eas.lower()
+
+    version = await env.alice_client.eas_version()
+    assert re.fullmatch(r"\d+\.\d+\.\d+", version), f"Unexpected EAS version {version!r}"
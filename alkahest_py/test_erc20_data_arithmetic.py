@@ -0,0 +1,50 @@
+import pytest
+from alkahest_py import Erc20Data
+
+TOKEN = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+OTHER = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"
+
+
+def test_erc20_data_arithmetic():
+    a = Erc20Data(TOKEN, 100)
+    b = Erc20Data(TOKEN.lower(), 40)
+
+    total = a + b
+    assert total.address == TOKEN
+    assert total.value == 140
+    assert (a - b).value == 60
+    assert sum([b, b], Erc20Data(TOKEN, 0)).value == 80
+
+    with pytest.raises(ValueError):
+        b - a
+    with pytest.raises(ValueError, match="Token mismatch"):
+        a + Erc20Data(OTHER, 1)
+
+
+def test_erc20_data_comparison():
+    a = Erc20Data(TOKEN, 100)
+    b = Erc20Data(TOKEN.lower(), 40)
+
+    assert b < a
+    assert not a < b
+    assert a == Erc20Data(TOKEN.lower(), 100)
+    assert a != b
+    assert a != Erc20Data(OTHER, 100)
+
+    with pytest.raises(ValueError, match="Token mismatch"):
+        a < Erc20Data(OTHER, 1)
+
+
+def test_erc20_data_repr_with_metadata():
+    raw = Erc20Data(TOKEN, 100_000_000)
+    assert "value=100000000" in repr(raw)
+
+    usdc = raw.with_metadata(6, "USDC")
+    assert usdc.decimals == 6
+    assert usdc.symbol == "USDC"
+    assert "value=100.0 USDC" in repr(usdc)
+    assert "value=0.000001" in repr(Erc20Data(TOKEN, 1).with_metadata(6))
+
+    # Metadata is display-only and survives arithmetic
+    assert usdc == raw
+    assert "value=150.5 USDC" in repr(usdc + Erc20Data(TOKEN, 50_500_000))
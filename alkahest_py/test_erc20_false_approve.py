@@ -0,0 +1,31 @@
+import json
+from pathlib import Path
+
+import pytest
+from alkahest_py import EnvTestManager, TransactionRevertError
+
+# Built from contracts/MockFalseApprove.sol by contracts/build.sh
+MOCK_FALSE_APPROVE_ARTIFACT = (
+    Path(__file__).parent.parent / "contracts" / "out" / "MockFalseApprove.json"
+)
+
+
+async def deploy_false_approve_token(env):
+    with open(MOCK_FALSE_APPROVE_ARTIFACT) as f:
+        contracts = json.load(f)["contracts"]
+    code = next(c["bin"] for name, c in contracts.items() if name.endswith(":MockFalseApprove"))
+    return await env.god_wallet_provider.deploy(bytes.fromhex(code))
+
+
+@pytest.mark.asyncio
+async def test_approve_raises_when_token_returns_false():
+    env = EnvTestManager()
+    token = await deploy_false_approve_token(env)
+    token_data = {"address": token, "value": 100}
+
+    # The token returns false instead of reverting, so the simulation catches it
+    # before a transaction that would mine without approving anything is sent
+    with pytest.raises(TransactionRevertError, match="returned false"):
+        await env.alice_client.erc20.approve(token_data, "escrow")
+    with pytest.raises(TransactionRevertError, match="returned false"):
+        await env.alice_client.erc20.approve_if_less(token_data, "payment")
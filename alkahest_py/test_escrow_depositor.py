@@ -0,0 +1,27 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_escrow_depositor():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+
+    await env.alice_client.erc20.approve(price_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    # Either party sees alice as the depositor
+    depositor = await env.alice_client.escrow_depositor(buy_uid)
+    assert depositor.lower() == env.alice.lower()
+    assert (await env.bob_client.escrow_depositor(buy_uid)).lower() == env.alice.lower()
+
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.escrow_depositor("0x" + "00" * 31 + "01")
@@ -0,0 +1,43 @@
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    EnvTestManager,
+    EscrowTerms,
+    MockERC20,
+)
+
+
+@pytest.mark.asyncio
+async def test_get_escrow_terms():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    uid = escrow["log"]["uid"]
+
+    terms = await env.bob_client.get_escrow_terms(uid)
+    assert isinstance(terms, EscrowTerms)
+    assert terms.uid == uid
+    assert terms.token_type == "erc20"
+    assert isinstance(terms.offer, ERC20EscrowObligationData)
+    assert terms.offer.amount == 100
+    assert terms.arbiter == env.addresses.erc20_addresses.payment_obligation.lower()
+    assert terms.arbiter_name == "erc20_payment_obligation"
+    assert isinstance(terms.demand, ERC20PaymentObligationData)
+    assert terms.demand.amount == 200
+    assert terms.expiration_time is None
+    assert terms.depositor == env.alice.lower()
+    assert terms.status == "open"
+
+
+@pytest.mark.asyncio
+async def test_get_escrow_terms_unknown_escrow():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        await env.alice_client.get_escrow_terms("0x" + "00" * 31 + "01")
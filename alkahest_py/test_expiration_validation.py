@@ -0,0 +1,29 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, expiration_in
+
+
+@pytest.mark.asyncio
+async def test_expiration_in_the_past_is_rejected():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 200)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+
+    # A duration passed where a timestamp is expected
+    with pytest.raises(ValueError, match=r"expiration_in\(3600\)"):
+        await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 3600)
+    assert mock_erc20.balance_of(env.alice) == 200
+
+    # force skips the check
+    result = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 3600, force=True)
+    assert result["log"]["uid"]
+
+    # Future timestamps and "never expires" are accepted
+    result = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, expiration_in(3600))
+    assert result["log"]["uid"]
@@ -0,0 +1,32 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_explain_erc20_escrow():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    uid = escrow["log"]["uid"]
+
+    summary = await env.alice_client.explain_attestation(uid, env.addresses)
+    assert summary.startswith(f"Attestation {uid}")
+    assert "ERC20 escrow" in summary
+    assert "amount:      100" in summary
+    assert "erc20_payment_obligation" in summary
+    assert "demand:      ERC20 payment of 200" in summary
+    assert "expiration:  never" in summary
+    assert "not revoked" in summary
+
+
+@pytest.mark.asyncio
+async def test_explain_unknown_attestation():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        await env.alice_client.explain_attestation("0x" + "00" * 31 + "01")
@@ -0,0 +1,54 @@
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    EnvTestManager,
+    MockERC20,
+)
+
+
+async def make_escrow(env):
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    return escrow["log"]["uid"]
+
+
+@pytest.mark.asyncio
+async def test_fulfillment_economics():
+    env = EnvTestManager()
+    uid = await make_escrow(env)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+
+    economics = await env.bob_client.fulfillment_economics(uid)
+    assert economics["token_type"] == "erc20"
+    assert isinstance(economics["offer"], ERC20EscrowObligationData)
+    assert economics["offer"].amount == 100
+    assert isinstance(economics["payment"], ERC20PaymentObligationData)
+    assert economics["payment"].amount == 200
+    assert economics["revert_reason"] is None
+    assert economics["gas_estimate"] > 0
+    assert int(economics["gas_cost_wei"]) == economics["gas_estimate"] * economics["gas_price"]
+
+    # Nothing was sent
+    assert MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).balance_of(env.bob) == 200
+
+
+@pytest.mark.asyncio
+async def test_fulfillment_economics_unaffordable():
+    env = EnvTestManager()
+    uid = await make_escrow(env)
+
+    economics = await env.bob_client.fulfillment_economics(uid)
+    assert economics["gas_estimate"] is None
+    assert economics["gas_cost_wei"] is None
+    assert economics["revert_reason"]
+
+
+@pytest.mark.asyncio
+async def test_fulfillment_economics_unknown_escrow():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        await env.bob_client.fulfillment_economics("0x" + "00" * 31 + "01")
@@ -0,0 +1,105 @@
+import json
+import urllib.request
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+def encode_transfer(to: str, amount: int) -> bytes:
+    return (
+        bytes.fromhex("a9059cbb")
+        + bytes(12)
+        + bytes.fromhex(to[2:])
+        + amount.to_bytes(32, "big")
+    )
+
+
+def rpc(env, method, params):
+    # Anvil answers plain HTTP on its websocket port
+    url = env.rpc_url.replace("ws://", "http://", 1)
+    body = json.dumps({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})
+    request = urllib.request.Request(url, body.encode(), {"Content-Type": "application/json"})
+    with urllib.request.urlopen(request) as response:
+        return json.load(response)["result"]
+
+
+def gas_limit_and_used(env, tx_hash):
+    tx = rpc(env, "eth_getTransactionByHash", [tx_hash])
+    receipt = rpc(env, "eth_getTransactionReceipt", [tx_hash])
+    return int(tx["gas"], 16), int(receipt["gasUsed"], 16)
+
+
+@pytest.mark.asyncio
+async def test_send_transaction_with_gas_multiplier():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    client = env.alice_client
+    assert client.gas_multiplier == 1.0
+    client.gas_multiplier = 1.2
+    assert client.gas_multiplier == 1.2
+
+    tx_hash = await client.send_transaction(token, encode_transfer(env.bob, 30))
+    gas_limit, gas_used = gas_limit_and_used(env, tx_hash)
+    assert gas_limit >= 1.2 * gas_used
+    tx_hash = await client.send_transaction(token, encode_transfer(env.bob, 20), gas_multiplier=2.0)
+    gas_limit, gas_used = gas_limit_and_used(env, tx_hash)
+    assert gas_limit >= 2.0 * gas_used
+    assert mock_erc20.balance_of(env.bob) == 50
+
+    with pytest.raises(ValueError, match="at least 1.0"):
+        client.gas_multiplier = 0.5
+    with pytest.raises(ValueError, match="at least 1.0"):
+        await client.send_transaction(token, encode_transfer(env.bob, 1), gas_multiplier=0.9)
+
+
+@pytest.mark.asyncio
+async def test_extension_writes_with_gas_multiplier():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    # Extension clients fetched before the change still pick it up
+    erc20 = env.alice_client.erc20
+    env.alice_client.gas_multiplier = 1.5
+
+    await erc20.approve({"address": token, "value": 40}, "escrow")
+    await erc20.approve({"address": token, "value": 60}, "payment", gas_multiplier=2.0)
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.escrow_obligation) == 40
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.payment_obligation) == 60
+
+    with pytest.raises(ValueError, match="at least 1.0"):
+        await erc20.approve({"address": token, "value": 1}, "escrow", gas_multiplier=0.9)
+
+
+@pytest.mark.asyncio
+async def test_extension_writes_with_gas_multiplier():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    erc20 = env.alice_client.erc20
+
+    # Without a multiplier the limit is the node's estimate, close to the gas used
+    tx_hash = await erc20.approve({"address": token, "value": 10}, "escrow")
+    gas_limit, gas_used = gas_limit_and_used(env, tx_hash)
+    assert gas_limit < 1.5 * gas_used
+
+    # Extension clients fetched before the change still pick it up
+    env.alice_client.gas_multiplier = 1.5
+    tx_hash = await erc20.approve({"address": token, "value": 40}, "escrow")
+    gas_limit, gas_used = gas_limit_and_used(env, tx_hash)
+    assert gas_limit >= 1.5 * gas_used
+
+    tx_hash = await erc20.approve({"address": token, "value": 60}, "payment", gas_multiplier=2.0)
+    gas_limit, gas_used = gas_limit_and_used(env, tx_hash)
+    assert gas_limit >= 2.0 * gas_used
+
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.escrow_obligation) == 40
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.payment_obligation) == 60
+
+    with pytest.raises(ValueError, match="at least 1.0"):
+        await erc20.approve({"address": token, "value": 1}, "escrow", gas_multiplier=0.9)
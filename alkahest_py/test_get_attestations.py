@@ -0,0 +1,46 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_get_attestations_preserves_duplicates():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+    first = (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0))["log"]["uid"]
+    second = (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0))["log"]["uid"]
+
+    uids = [first, second, first, first, second]
+    attestations = await env.alice_client.attestation.get_attestations(uids)
+
+    assert [a.uid for a in attestations] == uids
+    assert attestations[0].data == attestations[2].data
+
+
+@pytest.mark.asyncio
+async def test_get_attestations_empty():
+    env = EnvTestManager()
+    assert await env.alice_client.attestation.get_attestations([]) == []
+
+
+@pytest.mark.asyncio
+async def test_get_attestations_keeps_order_past_concurrency_limit():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 20)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 1}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 1}
+
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 20}, "escrow")
+    uids = [
+        (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0))["log"]["uid"]
+        for _ in range(20)
+    ]
+
+    attestations = await env.alice_client.attestation.get_attestations(uids[::-1])
+
+    assert [a.uid for a in attestations] == uids[::-1]
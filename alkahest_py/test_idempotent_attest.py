@@ -0,0 +1,40 @@
+import pytest
+from alkahest_py import AttestationBuilder, EnvTestManager, compute_schema_uid
+
+ZERO_ADDRESS = "0x0000000000000000000000000000000000000000"
+
+
+@pytest.mark.asyncio
+async def test_idempotent_attest_returns_prior_attestation():
+    env = EnvTestManager()
+    # register_schema takes the schema as a 32-byte hex string
+    schema = "0x" + b"idempotent attest test".ljust(32, b"\0").hex()
+    await env.alice_client.attestation.register_schema(schema, ZERO_ADDRESS, True)
+
+    request = (
+        AttestationBuilder()
+        .schema(compute_schema_uid(schema, ZERO_ADDRESS, True))
+        .recipient(env.bob)
+        .data(b"hello")
+        .build()
+    )
+
+    first = await env.alice_client.attestation.attest(request, idempotent=True)
+    retry = await env.alice_client.attestation.attest(request, idempotent=True)
+    assert retry["log"]["uid"] == first["log"]["uid"]
+    assert retry["transaction_hash"] == first["transaction_hash"]
+
+    # Without idempotency the same request makes a new attestation
+    duplicate = await env.alice_client.attestation.attest(request)
+    assert duplicate["log"]["uid"] != first["log"]["uid"]
+
+    # A different payload is not a retry
+    other = (
+        AttestationBuilder()
+        .schema(compute_schema_uid(schema, ZERO_ADDRESS, True))
+        .recipient(env.bob)
+        .data(b"goodbye")
+        .build()
+    )
+    fresh = await env.alice_client.attestation.attest(other, idempotent=True)
+    assert fresh["log"]["uid"] not in (first["log"]["uid"], duplicate["log"]["uid"])
@@ -0,0 +1,39 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_include_logs():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 200)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+
+    # Logs are only attached when requested
+    result = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0)
+    assert result["logs"] is None, "Logs should not be included by default"
+
+    result = await env.alice_client.erc20.buy_with_erc20(
+        price_data, arbiter_data, 0, include_logs=True
+    )
+    logs = result["logs"]
+    assert logs, "Expected receipt logs to be included"
+
+    events = [log["event"] for log in logs]
+    assert "Attested" in events, f"Attested event missing from {events}"
+    assert "Transfer" in events, f"Transfer event missing from {events}"
+
+    attested = next(log for log in logs if log["event"] == "Attested")
+    assert attested["args"]["uid"] == result["log"]["uid"]
+
+    transfer = next(log for log in logs if log["event"] == "Transfer")
+    assert transfer["address"].lower() == env.mock_addresses.erc20_a.lower()
+    assert transfer["args"]["to"].lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+    assert transfer["args"]["value"] == "100"
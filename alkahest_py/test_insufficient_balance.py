@@ -0,0 +1,39 @@
+import pytest
+from alkahest_py import EnvTestManager, InsufficientBalanceError, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_balance_of():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    MockERC20(token, env.god_wallet_provider).transfer(env.alice, 75)
+
+    assert await env.alice_client.erc20.balance_of(token) == "75"
+    assert await env.bob_client.erc20.balance_of(token, env.alice) == "75"
+    assert await env.bob_client.erc20.balance_of(token) == "0"
+
+
+@pytest.mark.asyncio
+async def test_strict_escrow_checks_balance():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 50)
+
+    price_data = {"address": token, "value": 100}
+    arbiter_data = {
+        "arbiter": env.addresses.erc20_addresses.payment_obligation,
+        "demand": b"custom demand data",
+    }
+    await env.alice_client.erc20.approve(price_data, "escrow")
+
+    with pytest.raises(InsufficientBalanceError, match="required 100, available 50"):
+        await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0, strict=True)
+
+    # Without strict the transaction is sent and reverts on-chain
+    with pytest.raises(RuntimeError):
+        await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0)
+
+    mock_erc20.transfer(env.alice, 50)
+    result = await env.alice_client.erc20.buy_with_erc20(price_data, arbiter_data, 0, strict=True)
+    assert result["log"]["uid"]
@@ -0,0 +1,29 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_is_arbiter_trusted():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    uid = escrow["log"]["uid"]
+
+    payment_obligation = env.addresses.erc20_addresses.payment_obligation
+    trivial = env.addresses.arbiters_addresses.trivial_arbiter
+
+    assert await env.bob_client.is_arbiter_trusted(uid, [trivial, payment_obligation.upper().replace("0X", "0x")])
+    assert not await env.bob_client.is_arbiter_trusted(uid, [trivial])
+    assert not await env.bob_client.is_arbiter_trusted(uid, [])
+
+
+@pytest.mark.asyncio
+async def test_is_arbiter_trusted_rejects_non_escrow():
+    env = EnvTestManager()
+    uid = await env.alice_client.string_obligation.do_obligation("not an escrow", None)
+    with pytest.raises(ValueError, match="not a known escrow"):
+        await env.bob_client.is_arbiter_trusted(uid, [env.addresses.arbiters_addresses.trivial_arbiter])
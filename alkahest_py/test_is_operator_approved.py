@@ -0,0 +1,38 @@
+import pytest
+from alkahest_py import EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_erc721_is_operator_approved():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc721_a
+    escrow = env.addresses.erc721_addresses.escrow_obligation
+
+    assert not await env.alice_client.erc721.is_operator_approved(token, escrow)
+
+    await env.alice_client.erc721.approve_all(token, "escrow")
+    assert await env.alice_client.erc721.is_operator_approved(token, escrow)
+    # Another owner's approvals are separate
+    assert not await env.bob_client.erc721.is_operator_approved(token, escrow)
+    assert await env.bob_client.erc721.is_operator_approved(token, escrow, env.alice)
+
+    await env.alice_client.erc721.revoke_all(token, "escrow")
+    assert not await env.alice_client.erc721.is_operator_approved(token, escrow)
+
+
+@pytest.mark.asyncio
+async def test_erc1155_is_operator_approved():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc1155_a
+    payment = env.addresses.erc1155_addresses.payment_obligation
+
+    assert not await env.alice_client.erc1155.is_operator_approved(token, payment)
+    await env.alice_client.erc1155.approve_all(token, "payment")
+    assert await env.alice_client.erc1155.is_operator_approved(token, payment)
+
+
+@pytest.mark.asyncio
+async def test_is_operator_approved_rejects_bad_address():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        await env.alice_client.erc721.is_operator_approved(env.mock_addresses.erc721_a, "nope")
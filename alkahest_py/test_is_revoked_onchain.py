@@ -0,0 +1,30 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_is_revoked_onchain():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    cached = await env.alice_client.attestation.get_attestation(buy_uid)
+    assert not cached.is_revoked()
+    assert await env.alice_client.attestation.is_revoked_onchain(buy_uid) is False
+
+    # Claiming the escrow revokes its attestation; the cached copy is now stale
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid)
+
+    assert not cached.is_revoked()
+    assert await env.alice_client.attestation.is_revoked_onchain(buy_uid) is True
+
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.attestation.is_revoked_onchain("0x" + "00" * 31 + "01")
@@ -0,0 +1,55 @@
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    ERC721PaymentObligationData,
+    ERC1155EscrowObligationData,
+    StringObligationData,
+)
+
+TOKEN = "0x" + "11" * 20
+ALICE = "0x" + "22" * 20
+BOB = "0x" + "33" * 20
+
+
+def test_change_payee_and_reencode():
+    payment = ERC20PaymentObligationData(TOKEN, 100, ALICE)
+    decoded = ERC20PaymentObligationData.decode(payment.encode_self())
+
+    decoded.payee = BOB
+    decoded.amount = 250
+    reencoded = ERC20PaymentObligationData.decode(decoded.encode_self())
+    assert reencoded.payee == BOB
+    assert reencoded.amount == 250
+    assert reencoded.token == TOKEN
+
+
+def test_change_escrow_fields_and_reencode():
+    escrow = ERC20EscrowObligationData(TOKEN, 100, ALICE, b"\x01")
+    escrow.arbiter = BOB
+    escrow.demand = b"\x02\x03"
+    reencoded = ERC20EscrowObligationData.decode(escrow.encode_self())
+    assert reencoded.arbiter == BOB
+    assert reencoded.demand == b"\x02\x03"
+
+    erc1155 = ERC1155EscrowObligationData(TOKEN, "1", "5", ALICE, b"")
+    erc1155.amount = "7"
+    assert ERC1155EscrowObligationData.decode(erc1155.encode_self()).amount == "7"
+
+    string_data = StringObligationData("before")
+    string_data.item = "after"
+    assert StringObligationData.decode(string_data.encode_self()).item == "after"
+
+
+def test_invalid_values_fail_on_encode():
+    payment = ERC721PaymentObligationData(TOKEN, "1", ALICE)
+    # Setting doesn't validate, encoding does
+    payment.payee = "not an address"
+    assert payment.payee == "not an address"
+    with pytest.raises(ValueError):
+        payment.encode_self()
+
+    escrow = ERC20EscrowObligationData(TOKEN, 100, ALICE, b"")
+    escrow.token = "0x1234"
+    with pytest.raises(ValueError):
+        escrow.encode_self()
@@ -0,0 +1,33 @@
+import pytest
+from alkahest_py import ArbitrateOptions, EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_my_arbitration_decisions():
+    env = EnvTestManager()
+    assert await env.bob_client.oracle.my_arbitration_decisions() == []
+
+    approve_uid = await env.alice_client.string_obligation.do_obligation("approve me", None)
+    reject_uid = await env.alice_client.string_obligation.do_obligation("reject me", None)
+    await env.alice_client.oracle.request_arbitration(approve_uid, env.bob)
+    await env.alice_client.oracle.request_arbitration(reject_uid, env.bob)
+
+    def decide(attestation):
+        return env.bob_client.oracle.extract_obligation_data(attestation) == "approve me"
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        decide, ArbitrateOptions(skip_arbitrated=True)
+    )
+    assert len(result) == 2
+
+    # The decisions are read back from the arbiter's events, not from anything the
+    # run above kept
+    decisions = await env.bob_client.oracle.my_arbitration_decisions()
+    by_uid = {d.attestation.uid: d for d in decisions}
+    assert by_uid[approve_uid].decision is True
+    assert by_uid[reject_uid].decision is False
+    assert {d.transaction_hash for d in decisions} == {d.transaction_hash for d in result}
+
+    # Alice never acted as an oracle
+    assert await env.alice_client.oracle.my_arbitration_decisions() == []
+    assert await env.alice_client.oracle.my_arbitration_decisions(from_block=0) == []
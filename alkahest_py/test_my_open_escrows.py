@@ -0,0 +1,39 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_my_open_escrows():
+    env = EnvTestManager()
+    mock_erc20_a = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20_b = MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider)
+    mock_erc20_a.transfer(env.alice, 300)
+    mock_erc20_b.transfer(env.bob, 200)
+
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 300}, "escrow")
+
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    open_escrow = await env.alice_client.erc20.buy_erc20_for_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, ask_data, 0
+    )
+    claimed_escrow = await env.alice_client.erc20.buy_erc20_for_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 150}, ask_data, 0
+    )
+
+    # Bob fulfills the second escrow
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    await env.bob_client.erc20.pay_erc20_for_erc20(claimed_escrow["log"]["uid"])
+
+    escrows = await env.alice_client.my_open_escrows()
+    uids = [escrow["attestation"].uid for escrow in escrows]
+    assert open_escrow["log"]["uid"] in uids
+    assert claimed_escrow["log"]["uid"] not in uids
+
+    escrow = next(e for e in escrows if e["attestation"].uid == open_escrow["log"]["uid"])
+    assert escrow["token_type"] == "erc20"
+    assert escrow["obligation"].amount == 100
+    assert escrow["obligation"].token.lower() == env.mock_addresses.erc20_a.lower()
+    assert escrow["expiration_time"] == 0
+
+    # Bob has no escrows of his own
+    assert await env.bob_client.my_open_escrows() == []
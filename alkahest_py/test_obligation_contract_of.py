@@ -0,0 +1,34 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_obligation_contract_of_escrow():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+
+    contract = await env.alice_client.obligation_contract_of(escrow["log"]["uid"], env.addresses)
+    assert contract == env.addresses.erc20_addresses.escrow_obligation.lower()
+
+
+@pytest.mark.asyncio
+async def test_obligation_contract_of_string_obligation():
+    env = EnvTestManager()
+    uid = await env.alice_client.string_obligation.do_obligation("hello", None)
+
+    contract = await env.alice_client.obligation_contract_of(uid, env.addresses)
+    assert contract == env.addresses.string_obligation_addresses.obligation.lower()
+
+
+@pytest.mark.asyncio
+async def test_obligation_contract_of_unknown_attestation():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        await env.alice_client.obligation_contract_of("0x" + "00" * 31 + "01")
@@ -0,0 +1,62 @@
+import json
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, import_offer
+
+
+@pytest.mark.asyncio
+async def test_export_and_import_offer():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    exported = await env.alice_client.export_offer(buy_uid)
+    offer = import_offer(exported)
+
+    assert offer["escrow_uid"] == buy_uid
+    assert offer["escrow_contract"].lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+    assert offer["depositor"].lower() == env.alice.lower()
+    assert offer["token_type"] == "erc20"
+    assert offer["offer"]["token"].lower() == env.mock_addresses.erc20_a.lower()
+    assert offer["offer"]["amount"] == "100"
+    assert offer["arbiter"].lower() == env.addresses.erc20_addresses.payment_obligation.lower()
+    assert offer["demand_summary"]["token_type"] == "erc20"
+    assert offer["demand_summary"]["token"].lower() == env.mock_addresses.erc20_b.lower()
+    assert offer["demand_summary"]["amount"] == "200"
+    assert offer["demand_summary"]["payee"].lower() == env.alice.lower()
+    assert offer["expiration_time"] is None
+
+
+def test_import_offer_rejects_malformed_offers():
+    offer = {
+        "version": 1,
+        "chain_id": 31337,
+        "escrow_uid": "0x" + "ab" * 32,
+        "escrow_contract": "0x" + "11" * 20,
+        "depositor": "0x" + "22" * 20,
+        "token_type": "erc20",
+        "offer": {"token": "0x" + "33" * 20, "amount": "1"},
+        "arbiter": "0x" + "44" * 20,
+        "demand": "0x",
+        "demand_summary": None,
+        "expiration_time": None,
+    }
+    assert import_offer(json.dumps(offer)) == offer
+
+    with pytest.raises(ValueError, match="version"):
+        import_offer(json.dumps({**offer, "version": 2}))
+    with pytest.raises(ValueError, match="escrow_uid"):
+        import_offer(json.dumps({**offer, "escrow_uid": "0x1234"}))
+    with pytest.raises(ValueError, match="token_type"):
+        import_offer(json.dumps({**offer, "token_type": "erc4626"}))
+    missing = dict(offer)
+    del missing["arbiter"]
+    with pytest.raises(ValueError, match="missing arbiter"):
+        import_offer(json.dumps(missing))
+    with pytest.raises(ValueError, match="Invalid offer"):
+        import_offer("not json")
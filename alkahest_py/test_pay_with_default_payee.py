@@ -0,0 +1,18 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_pay_with_erc20_defaults_payee_to_signer():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 50}
+    await env.alice_client.erc20.approve(price_data, "payment")
+
+    result = await env.alice_client.erc20.pay_with_erc20(price_data)
+    assert mock_erc20.balance_of(env.alice) == 100
+
+    attestation = await env.alice_client.attestation.get_attestation(result["log"]["uid"])
+    assert attestation.recipient.lower() == env.alice.lower()
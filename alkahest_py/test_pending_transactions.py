@@ -0,0 +1,51 @@
+import asyncio
+
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager
+
+# Anvil's sixth account, kept to this test since a replaced transaction's
+# original send never resolves and holds the signer's nonce lock
+PRIVATE_KEY = "0x8b3a350cf5c34c9194ca85829a2df0ec3153be0318b5e2d3348e872092edffba"
+ADDRESS = "0x9965507D1a55bcC2695C58ba16FB37d819B0A4dc"
+
+
+@pytest.mark.asyncio
+async def test_pending_transactions_and_speed_up():
+    env = EnvTestManager()
+    client = AlkahestClient(PRIVATE_KEY, rpc_url=env.rpc_url, address_config=env.addresses)
+    assert await client.pending_transactions() == []
+
+    await env.god_wallet_provider.anvil_set_auto_mine(False)
+    stuck = asyncio.ensure_future(client.send_transaction(env.bob, b"", value=1))
+    pending = []
+    for _ in range(50):
+        pending = await client.pending_transactions()
+        if pending:
+            break
+        await asyncio.sleep(0.1)
+
+    assert len(pending) == 1
+    tx = pending[0]
+    assert tx.to.lower() == env.bob.lower()
+    assert not tx.queued
+
+    with pytest.raises(ValueError, match="must be higher"):
+        await client.speed_up(tx.nonce, tx.gas_price)
+
+    new_hash = await client.speed_up(tx.nonce, tx.gas_price * 2)
+    assert new_hash != tx.hash
+    [replacement] = await client.pending_transactions()
+    assert replacement.hash == new_hash
+    assert replacement.gas_price == tx.gas_price * 2
+
+    await env.god_wallet_provider.anvil_mine(1)
+    await env.god_wallet_provider.anvil_set_auto_mine(True)
+    assert await client.pending_transactions() == []
+    stuck.cancel()
+
+
+@pytest.mark.asyncio
+async def test_speed_up_unknown_nonce():
+    env = EnvTestManager()
+    with pytest.raises(ValueError, match="No pending transaction"):
+        await env.alice_client.speed_up(1000, 10**9)
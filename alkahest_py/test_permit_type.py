@@ -0,0 +1,128 @@
+import json
+import urllib.request
+from pathlib import Path
+
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, MockERC20
+
+# Anvil's second account
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+ADDRESS = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+
+# Built from contracts/MockDaiPermit.sol by contracts/build.sh
+MOCK_DAI_ARTIFACT = Path(__file__).parent.parent / "contracts" / "out" / "MockDaiPermit.json"
+
+
+async def deploy_mock_dai(env):
+    with open(MOCK_DAI_ARTIFACT) as f:
+        contracts = json.load(f)["contracts"]
+    code = next(c["bin"] for name, c in contracts.items() if name.endswith(":MockDaiPermit"))
+    return await env.god_wallet_provider.deploy(bytes.fromhex(code))
+
+
+def dai_nonce(env, token, holder):
+    # nonces(address), read over anvil's HTTP endpoint on the websocket port
+    data = "0x7ecebe00" + holder[2:].lower().rjust(64, "0")
+    call = {"to": token, "data": data}
+    body = json.dumps({"jsonrpc": "2.0", "id": 1, "method": "eth_call", "params": [call, "latest"]})
+    url = env.rpc_url.replace("ws://", "http://", 1)
+    request = urllib.request.Request(url, body.encode(), {"Content-Type": "application/json"})
+    with urllib.request.urlopen(request) as response:
+        return int(json.load(response)["result"], 16)
+
+
+@pytest.mark.asyncio
+async def test_permit_type_detected_and_explicit_eip2612():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 50}
+    # The mock token is a standard EIP-2612 token, so detection picks that format
+    await env.alice_client.erc20.permit_and_pay_with_erc20(price_data, env.bob)
+    await env.alice_client.erc20.permit_and_pay_with_erc20(price_data, env.bob, permit_type="eip2612")
+
+    assert mock_erc20.balance_of(env.bob) == 100
+
+
+@pytest.mark.asyncio
+async def test_permit_type_rejects_unknown_format():
+    env = EnvTestManager()
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 50}
+    with pytest.raises(ValueError, match="permit_type"):
+        await env.alice_client.erc20.permit_and_pay_with_erc20(price_data, env.bob, permit_type="eip712")
+
+
+@pytest.mark.asyncio
+async def test_dai_permit_needs_private_key():
+    # The test environment's clients are connected without a private key, so they
+    # have no key to sign a DAI-style permit with
+    env = EnvTestManager()
+    price_data = {"address": env.mock_addresses.erc20_a, "value": 50}
+    with pytest.raises(ValueError, match="private key"):
+        await env.alice_client.erc20.permit_and_pay_with_erc20(price_data, env.bob, permit_type="dai")
+
+
+@pytest.mark.asyncio
+async def test_dai_permit_detected_and_sent():
+    env = EnvTestManager()
+    token = await deploy_mock_dai(env)
+    dai = MockERC20(token, env.god_wallet_provider)
+    dai.transfer(ADDRESS, 100)
+    client = AlkahestClient(PRIVATE_KEY, rpc_url=env.rpc_url, address_config=env.addresses)
+
+    price_data = {"address": token, "value": 40}
+    # Detection finds DAI's typehash, so a DAI permit is signed and sent before paying
+    await client.erc20.permit_and_pay_with_erc20(price_data, env.bob, dai_expiry=0)
+    assert dai_nonce(env, token, ADDRESS) == 1
+    assert dai.balance_of(env.bob) == 40
+
+    await client.erc20.permit_and_pay_with_erc20(
+        price_data, env.bob, permit_type="dai", dai_expiry=2**40
+    )
+    assert dai_nonce(env, token, ADDRESS) == 2
+    assert dai.balance_of(env.bob) == 80
+    assert dai.balance_of(ADDRESS) == 20
+
+    # The token has no EIP-2612 permit, so forcing that format fails
+    with pytest.raises(Exception):
+        await client.erc20.permit_and_pay_with_erc20(price_data, env.bob, permit_type="eip2612")
+    assert dai.balance_of(env.bob) == 80
+
+
+@pytest.mark.asyncio
+async def test_dai_permit_needs_explicit_expiry():
+    env = EnvTestManager()
+    token = await deploy_mock_dai(env)
+    dai = MockERC20(token, env.god_wallet_provider)
+    dai.transfer(ADDRESS, 100)
+    client = AlkahestClient(PRIVATE_KEY, rpc_url=env.rpc_url, address_config=env.addresses)
+
+    # A DAI permit grants an unlimited allowance, so it isn't signed unless asked for
+    price_data = {"address": token, "value": 40}
+    with pytest.raises(ValueError, match="dai_expiry"):
+        await client.erc20.permit_and_pay_with_erc20(price_data, env.bob)
+    assert dai_nonce(env, token, ADDRESS) == 0
+    assert dai.balance_of(env.bob) == 0
+
+
+@pytest.mark.asyncio
+async def test_dai_permit_when_paying_for_an_escrow():
+    env = EnvTestManager()
+    token = await deploy_mock_dai(env)
+    dai = MockERC20(token, env.god_wallet_provider)
+    dai.transfer(ADDRESS, 100)
+    mock_erc20_a = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20_a.transfer(env.alice, 100)
+    client = AlkahestClient(PRIVATE_KEY, rpc_url=env.rpc_url, address_config=env.addresses)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": token, "value": 40}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    buy_result = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+
+    # The escrow demands payment in the DAI-style token, so its permit is used
+    await client.erc20.permit_and_pay_erc20_for_erc20(buy_result["log"]["uid"], dai_expiry=0)
+    assert dai_nonce(env, token, ADDRESS) == 1
+    assert dai.balance_of(env.alice) == 40
+    assert mock_erc20_a.balance_of(ADDRESS) == 100
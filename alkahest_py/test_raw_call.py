@@ -0,0 +1,25 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+# keccak256("balanceOf(address)")[:4]
+BALANCE_OF_SELECTOR = bytes.fromhex("70a08231")
+
+
+@pytest.mark.asyncio
+async def test_raw_call():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 1234)
+
+    owner = bytes.fromhex(env.alice[2:]).rjust(32, b"\x00")
+    result = await env.alice_client.call(
+        env.mock_addresses.erc20_a, BALANCE_OF_SELECTOR + owner
+    )
+
+    assert isinstance(result, bytes)
+    assert int.from_bytes(result, "big") == mock_erc20.balance_of(env.alice)
+
+    result = await env.alice_client.call(
+        env.mock_addresses.erc20_a, BALANCE_OF_SELECTOR + owner, from_address=env.bob
+    )
+    assert int.from_bytes(result, "big") == 1234
@@ -0,0 +1,64 @@
+import pytest
+from alkahest_py import (
+    AlkahestClient,
+    EnvTestManager,
+    MockERC20,
+    ReadOnlyClientError,
+)
+
+
+@pytest.mark.asyncio
+async def test_read_only_client_reads():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    uid = escrow["log"]["uid"]
+
+    client = AlkahestClient.read_only(env.rpc_url, address_config=env.addresses)
+    terms = await client.get_escrow_terms(uid)
+    assert terms.depositor == env.alice.lower()
+    assert terms.status == "open"
+
+    attestation = await client.attestation.get_attestation(uid)
+    assert attestation.attester.lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+
+
+@pytest.mark.asyncio
+async def test_read_only_client_refuses_writes():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, address_config=env.addresses)
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    with pytest.raises(ReadOnlyClientError):
+        await client.erc20.approve(bid_data, "escrow")
+    with pytest.raises(ReadOnlyClientError):
+        await client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    with pytest.raises(ReadOnlyClientError):
+        client.batch()
+
+
+@pytest.mark.asyncio
+async def test_read_only_client_has_no_own_address():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, address_config=env.addresses)
+    token = env.mock_addresses.erc721_a
+    operator = env.addresses.erc721_addresses.escrow_obligation
+
+    # Methods that default to the client's own address refuse instead of reading a
+    # throwaway key's empty account
+    with pytest.raises(ReadOnlyClientError):
+        await client.my_open_escrows()
+    with pytest.raises(ReadOnlyClientError):
+        await client.erc721.is_operator_approved(token, operator)
+    with pytest.raises(ReadOnlyClientError):
+        await client.erc20.balance_of(env.mock_addresses.erc20_a)
+    with pytest.raises(ReadOnlyClientError):
+        await client.oracle.my_arbitration_decisions()
+
+    # Passing the address works as usual
+    assert not await client.erc721.is_operator_approved(token, operator, owner=env.alice)
+    assert await client.erc20.balance_of(env.mock_addresses.erc20_a, owner=env.alice) == "0"
@@ -0,0 +1,63 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, MockERC20
+
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+
+
+@pytest.mark.asyncio
+async def test_separate_read_and_write_rpc():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    MockERC20(token, env.god_wallet_provider).transfer(env.alice, 123)
+
+    # Anvil serves HTTP and websockets on the same port
+    read_rpc_url = env.rpc_url.replace("ws://", "http://")
+    client = AlkahestClient(
+        PRIVATE_KEY,
+        address_config=env.addresses,
+        read_rpc_url=read_rpc_url,
+        write_rpc_url=env.rpc_url,
+    )
+
+    # balanceOf(alice) goes through the read RPC
+    data = bytes.fromhex("70a08231") + bytes(12) + bytes.fromhex(env.alice[2:])
+    result = await client.call(token, data)
+    assert int.from_bytes(result, "big") == 123
+
+
+def test_rpc_url_required():
+    with pytest.raises(ValueError, match="rpc_url or write_rpc_url"):
+        AlkahestClient(PRIVATE_KEY, read_rpc_url="http://127.0.0.1:8545")
+
+
+@pytest.mark.asyncio
+async def test_extension_reads_use_read_rpc():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    MockERC20(token, env.god_wallet_provider).transfer(env.alice, 123)
+
+    client = AlkahestClient(
+        PRIVATE_KEY,
+        address_config=env.addresses,
+        read_rpc_url=env.rpc_url.replace("ws://", "http://"),
+        write_rpc_url=env.rpc_url,
+    )
+    assert await client.erc20.balance_of(token, env.alice) == "123"
+
+    # Nothing listens on this port, so reads through it fail while the write RPC works
+    unreachable = AlkahestClient(
+        PRIVATE_KEY,
+        address_config=env.addresses,
+        read_rpc_url="http://127.0.0.1:1",
+        write_rpc_url=env.rpc_url,
+    )
+    with pytest.raises(Exception):
+        await unreachable.erc20.balance_of(token, env.alice)
+    with pytest.raises(Exception):
+        await unreachable.erc20.approval_status(token)
+    with pytest.raises(Exception):
+        await unreachable.erc721.approval_status(env.mock_addresses.erc721_a)
+    with pytest.raises(Exception):
+        await unreachable.erc20.buy_erc20_for_erc20(
+            {"address": token, "value": 1}, {"address": token, "value": 1}, 2**40
+        )
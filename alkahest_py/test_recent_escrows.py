@@ -0,0 +1,41 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_recent_escrows():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 300)
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.bob, 100)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 300}, "escrow")
+    await env.bob_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 100}, "escrow")
+
+    first = await env.alice_client.erc20.buy_erc20_for_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, ask_data, 0
+    )
+    claimed = await env.alice_client.erc20.buy_erc20_for_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 150}, ask_data, 0
+    )
+    latest = await env.bob_client.erc20.buy_erc20_for_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, ask_data, 0
+    )
+
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    await env.bob_client.erc20.pay_erc20_for_erc20(claimed["log"]["uid"])
+
+    escrows = await env.alice_client.recent_escrows()
+    uids = [escrow["attestation"].uid for escrow in escrows]
+    # Newest first, across depositors, without the claimed one
+    assert uids == [latest["log"]["uid"], first["log"]["uid"]]
+    assert escrows[0]["token_type"] == "erc20"
+    assert escrows[0]["block_number"] > escrows[1]["block_number"]
+    assert escrows[0]["transaction_hash"] == latest["transaction_hash"]
+
+    limited = await env.alice_client.recent_escrows(limit=1)
+    assert [escrow["attestation"].uid for escrow in limited] == [latest["log"]["uid"]]
+
+    other_schema = "0x" + "00" * 32
+    assert await env.alice_client.recent_escrows(other_schema) == []
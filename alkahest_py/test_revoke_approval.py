@@ -0,0 +1,27 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_revoke_approval():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token = env.mock_addresses.erc20_a
+    escrow = env.addresses.erc20_addresses.escrow_obligation
+    payment = env.addresses.erc20_addresses.payment_obligation
+
+    await env.alice_client.erc20.approve({"address": token, "value": 100}, "escrow")
+    await env.alice_client.erc20.approve({"address": token, "value": 50}, "payment")
+
+    tx_hash = await env.alice_client.erc20.revoke_approval(token, "escrow")
+    assert tx_hash.startswith("0x")
+    assert mock_erc20.allowance(env.alice, escrow) == 0
+    # Only the allowance for the given purpose is cleared
+    assert mock_erc20.allowance(env.alice, payment) == 50
+
+
+@pytest.mark.asyncio
+async def test_revoke_approval_invalid_purpose():
+    env = EnvTestManager()
+    with pytest.raises(ValueError, match="Invalid purpose"):
+        await env.alice_client.erc20.revoke_approval(env.mock_addresses.erc20_a, "collateral")
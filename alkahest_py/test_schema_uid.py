@@ -0,0 +1,60 @@
+import pytest
+from eth_utils import keccak
+from alkahest_py import (
+    Attestation,
+    AttestationBuilder,
+    EnvTestManager,
+    compute_schema_uid,
+    normalize_schema_uid,
+    OracleAttestation,
+)
+
+ZERO_ADDRESS = "0x0000000000000000000000000000000000000000"
+
+
+def test_normalize_schema_uid():
+    uid = "0x" + "ab" * 32
+    assert normalize_schema_uid(uid) == uid
+    assert normalize_schema_uid(uid.upper().replace("0X", "0x")) == uid
+    assert normalize_schema_uid("ab" * 32) == uid
+
+    with pytest.raises(ValueError):
+        normalize_schema_uid("0x1234")
+
+
+@pytest.mark.asyncio
+async def test_schema_uid_matches_attestation_schema():
+    env = EnvTestManager()
+    schema = "0x" + keccak(b"schema uid test").hex()
+    registered = await env.alice_client.attestation.register_schema_with_uid(
+        schema, ZERO_ADDRESS, True
+    )
+
+    uid = compute_schema_uid(schema, ZERO_ADDRESS, True)
+    assert uid == normalize_schema_uid(uid)
+    assert registered["schema_uid"] == uid
+    assert registered["transaction_hash"].startswith("0x")
+
+    # register_schema itself still returns just the transaction hash
+    other = "0x" + keccak(b"schema uid test 2").hex()
+    transaction_hash = await env.alice_client.attestation.register_schema(other, ZERO_ADDRESS, True)
+    assert isinstance(transaction_hash, str) and transaction_hash.startswith("0x")
+
+    request = AttestationBuilder().schema(uid.upper().replace("0X", "0x")).recipient(env.bob).build()
+    result = await env.alice_client.attestation.attest(request)
+
+    attestation = await env.alice_client.attestation.get_attestation(result["log"]["uid"])
+    assert attestation.schema == uid
+    assert attestation.to_oracle_attestation().schema == uid
+
+
+def test_hand_built_attestations_normalize_schema():
+    uid = "0x" + "ab" * 32
+    recipient = "0x" + "12" * 20
+    ref_uid = "0x" + "00" * 32
+    attestation = Attestation(uid, "AB" * 32, 1, 0, 0, ref_uid, recipient, recipient, True, b"")
+    assert attestation.schema == uid
+    oracle_attestation = OracleAttestation(
+        uid, uid.upper().replace("0X", "0x"), ref_uid, 1, 0, 0, recipient, recipient, True, "0x"
+    )
+    assert oracle_attestation.schema == uid
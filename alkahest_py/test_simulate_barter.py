@@ -0,0 +1,67 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, MockERC721, MockERC1155
+
+
+@pytest.mark.asyncio
+async def test_simulate_barter_succeeds_without_sending():
+    env = EnvTestManager()
+    mock_erc20_a = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20_b = MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider)
+    mock_erc20_a.transfer(env.alice, 100)
+    mock_erc20_b.transfer(env.bob, 200)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask = {"address": env.mock_addresses.erc20_b, "value": 200}
+    report = await env.alice_client.simulate_barter(price, ask, 0, fulfiller=env.bob)
+
+    assert report.success
+    assert [step.name for step in report.steps] == [
+        "approve",
+        "escrow",
+        "approve_payment",
+        "pay",
+        "collect",
+    ]
+    assert all(step.gas_used > 0 for step in report.steps)
+    assert report.escrow_uid is not None
+    assert report.fulfillment_uid is not None
+
+    # Nothing was sent
+    assert mock_erc20_a.balance_of(env.alice) == 100
+    assert mock_erc20_b.balance_of(env.bob) == 200
+    assert mock_erc20_a.allowance(env.alice, env.addresses.erc20_addresses.escrow_obligation) == 0
+
+
+@pytest.mark.asyncio
+async def test_simulate_barter_reports_failing_step():
+    env = EnvTestManager()
+    # Alice holds none of the price token, so the escrow's transfer fails
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask = {"address": env.mock_addresses.erc20_b, "value": 200}
+    report = await env.alice_client.simulate_barter(price, ask, 0)
+
+    assert not report.success
+    assert report.steps[0].success
+    assert report.failed_step.name == "escrow"
+    assert report.failed_step.revert_reason
+    assert report.escrow_uid is None
+    # Without an escrow there is nothing to collect
+    assert "collect" not in [step.name for step in report.steps]
+
+
+@pytest.mark.asyncio
+async def test_simulate_barter_with_nft_price_and_ask():
+    env = EnvTestManager()
+    mock_erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+    mock_erc1155 = MockERC1155(env.mock_addresses.erc1155_a, env.god_wallet_provider)
+    token_id = mock_erc721.mint(env.alice)
+    mock_erc1155.mint(env.bob, 1, 10)
+
+    price = {"address": env.mock_addresses.erc721_a, "id": token_id}
+    ask = {"address": env.mock_addresses.erc1155_a, "id": 1, "value": 10}
+    report = await env.alice_client.simulate_barter(price, ask, 0, fulfiller=env.bob)
+
+    assert report.success, report
+    assert [step.name for step in report.steps][-1] == "collect"
+    assert mock_erc721.owner_of(token_id).lower() == env.alice.lower()
+    assert mock_erc1155.balance_of(env.bob, 1) == 10
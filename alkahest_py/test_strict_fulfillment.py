@@ -0,0 +1,50 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, TrustedOracleArbiterDemandData
+
+
+async def erc20_escrow(env, arbiter=None):
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    if arbiter is None:
+        escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    else:
+        escrow = await env.alice_client.erc20.buy_with_erc20(bid_data, arbiter, 0)
+    return escrow["log"]["uid"]
+
+
+@pytest.mark.asyncio
+async def test_strict_pay_accepts_matching_demand():
+    env = EnvTestManager()
+    buy_uid = await erc20_escrow(env)
+
+    payment = await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid, strict=True)
+    assert payment["log"]["uid"]
+
+
+@pytest.mark.asyncio
+async def test_strict_pay_rejects_wrong_payment_type():
+    env = EnvTestManager()
+    buy_uid = await erc20_escrow(env)
+
+    # The escrow demands ERC20, so an ERC721 payment would be rejected
+    with pytest.raises(ValueError, match="erc721 payment"):
+        await env.bob_client.erc721.pay_erc721_for_erc20(buy_uid, strict=True)
+    # Paying as though the escrow held an ERC721 misreads its data
+    with pytest.raises(ValueError, match="erc721 escrow data"):
+        await env.bob_client.erc20.pay_erc20_for_erc721(buy_uid, strict=True)
+
+
+@pytest.mark.asyncio
+async def test_strict_pay_rejects_other_arbiters():
+    env = EnvTestManager()
+    demand = TrustedOracleArbiterDemandData(env.bob, []).encode_self()
+    arbiter = {"arbiter": env.addresses.arbiters_addresses.trusted_oracle_arbiter, "demand": demand}
+    buy_uid = await erc20_escrow(env, arbiter)
+
+    with pytest.raises(ValueError, match="arbiter"):
+        await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid, strict=True)
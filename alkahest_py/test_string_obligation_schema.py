@@ -0,0 +1,71 @@
+import pytest
+from alkahest_py import EnvTestManager
+
+SCHEMA = {
+    "type": "object",
+    "properties": {
+        "task": {"type": "string", "minLength": 1},
+        "reward": {"type": "integer", "minimum": 0},
+        "tags": {"type": "array", "items": {"type": "string"}},
+    },
+    "required": ["task", "reward"],
+    "additionalProperties": False,
+}
+
+
+@pytest.mark.asyncio
+async def test_do_obligation_json_schema_valid():
+    env = EnvTestManager()
+    string_client = env.alice_client.string_obligation
+
+    uid = await string_client.do_obligation_json(
+        {"task": "translate", "reward": 10, "tags": ["fr"]}, schema=SCHEMA
+    )
+    assert uid.startswith("0x") and len(uid) == 66
+
+
+@pytest.mark.asyncio
+async def test_do_obligation_json_schema_invalid():
+    env = EnvTestManager()
+    string_client = env.alice_client.string_obligation
+
+    with pytest.raises(ValueError, match='missing required property "reward"'):
+        await string_client.do_obligation_json({"task": "translate"}, schema=SCHEMA)
+
+    with pytest.raises(ValueError, match="/reward: -1 is less than 0"):
+        await string_client.do_obligation_json({"task": "translate", "reward": -1}, schema=SCHEMA)
+
+    with pytest.raises(ValueError, match="/tags/1: expected string, got number"):
+        await string_client.do_obligation_json(
+            {"task": "translate", "reward": 1, "tags": ["fr", 2]}, schema=SCHEMA
+        )
+
+    with pytest.raises(ValueError, match='unexpected property "extra"'):
+        await string_client.do_obligation_json(
+            {"task": "translate", "reward": 1, "extra": True}, schema=SCHEMA
+        )
+
+
+@pytest.mark.asyncio
+async def test_do_obligation_json_schema_unsupported_keywords():
+    env = EnvTestManager()
+    string_client = env.alice_client.string_obligation
+
+    with pytest.raises(ValueError, match='/properties/task: unsupported keyword "pattern"'):
+        await string_client.do_obligation_json(
+            {"task": "translate"},
+            schema={"type": "object", "properties": {"task": {"type": "string", "pattern": "^t"}}},
+        )
+
+    with pytest.raises(ValueError, match='unsupported keyword "\\$ref"'):
+        await string_client.do_obligation_json(
+            {"task": "translate"},
+            schema={"$defs": {"task": {"type": "string"}}, "properties": {"task": {"$ref": "#/$defs/task"}}},
+        )
+
+    # Annotations don't affect validity, so they are accepted
+    uid = await string_client.do_obligation_json(
+        {"task": "translate", "reward": 1},
+        schema={**SCHEMA, "title": "Task", "description": "A translation task"},
+    )
+    assert uid.startswith("0x")
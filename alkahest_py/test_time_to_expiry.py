@@ -0,0 +1,32 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, expiration_in
+
+
+@pytest.mark.asyncio
+async def test_time_to_expiry():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+
+    never = (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0))["log"]["uid"]
+    assert await env.alice_client.time_to_expiry(never) is None
+
+    expiring = (await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, expiration_in(3600)))["log"]["uid"]
+    remaining = await env.alice_client.time_to_expiry(expiring)
+    assert 3000 < remaining <= 3700
+
+    await env.god_wallet_provider.anvil_increase_time(7200)
+    # Mine a block so the latest block's timestamp moves forward
+    mock_erc20.transfer(env.bob, 1)
+    assert await env.alice_client.time_to_expiry(expiring) < 0
+
+
+@pytest.mark.asyncio
+async def test_time_to_expiry_unknown_attestation():
+    env = EnvTestManager()
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.time_to_expiry("0x" + "11" * 32)
@@ -0,0 +1,49 @@
+import base64
+
+import pytest
+from alkahest_py import (
+    ERC20EscrowObligationData,
+    TrustedOracleArbiterDemandData,
+)
+
+ARBITER = "0x0000000000000000000000000000000000000002"
+TOKEN = "0x0000000000000000000000000000000000000001"
+
+
+def test_to_dict_defaults_to_hex():
+    data = ERC20EscrowObligationData(TOKEN, 100, ARBITER, b"\x01\x02\xff")
+    d = data.to_dict()
+    assert d == {"token": TOKEN, "amount": 100, "arbiter": ARBITER, "demand": "0x0102ff"}
+
+
+@pytest.mark.parametrize("payload", [b"", b"a", b"ab", b"abc", b"hello world", bytes(range(256))])
+def test_to_dict_base64(payload):
+    data = ERC20EscrowObligationData(TOKEN, 1, ARBITER, payload)
+    d = data.to_dict(bytes_encoding="base64")
+    assert d["demand"] == base64.b64encode(payload).decode()
+
+
+def test_to_dict_raw():
+    demand = TrustedOracleArbiterDemandData(ARBITER, b"\x00\x01")
+    d = demand.to_dict(bytes_encoding="raw")
+    assert d["data"] == b"\x00\x01"
+    assert d["oracle"] == ARBITER
+
+
+def test_to_dict_unknown_encoding():
+    data = ERC20EscrowObligationData(TOKEN, 1, ARBITER, b"")
+    with pytest.raises(ValueError):
+        data.to_dict(bytes_encoding="base32")
+
+
+def test_attestation_to_dict():
+    from alkahest_py import Attestation, OracleAttestation
+
+    uid = "0x" + "11" * 32
+    zero = "0x" + "00" * 32
+    attestation = Attestation(uid, zero, 1, 0, 0, zero, TOKEN, ARBITER, True, b"\xde\xad")
+    assert attestation.to_dict()["data"] == "0xdead"
+    assert attestation.to_dict(bytes_encoding="base64")["data"] == "3q0="
+
+    oracle_attestation = OracleAttestation(uid, zero, zero, 1, 0, 0, TOKEN, ARBITER, True, "0xdead")
+    assert oracle_attestation.to_dict(bytes_encoding="raw")["data"] == b"\xde\xad"
@@ -0,0 +1,23 @@
+import pytest
+from alkahest_py import EnvTestManager
+
+
+@pytest.mark.asyncio
+async def test_trace_obligation_chain():
+    env = EnvTestManager()
+    string_client = env.alice_client.string_obligation
+
+    root = await string_client.do_obligation("root", None)
+    middle = await string_client.do_obligation("middle", root)
+    leaf = await string_client.do_obligation("leaf", middle)
+
+    chain = await env.alice_client.trace_obligation_chain(leaf)
+    assert [a.uid.lower() for a in chain] == [leaf.lower(), middle.lower(), root.lower()]
+    assert chain[0].ref_uid.lower() == middle.lower()
+    assert int(chain[-1].ref_uid, 16) == 0
+
+    limited = await env.alice_client.trace_obligation_chain(leaf, max_depth=2)
+    assert [a.uid.lower() for a in limited] == [leaf.lower(), middle.lower()]
+
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.trace_obligation_chain("0x" + "00" * 31 + "01")
@@ -0,0 +1,91 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, TransactionRevertError
+
+
+def encode_transfer(to: str, amount: int) -> bytes:
+    return (
+        bytes.fromhex("a9059cbb")
+        + bytes(12)
+        + bytes.fromhex(to[2:])
+        + amount.to_bytes(32, "big")
+    )
+
+
+@pytest.mark.asyncio
+async def test_batch_sends_on_exit_with_consecutive_nonces():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    bob_before = mock_erc20.balance_of(env.bob)
+
+    async with env.alice_client.batch() as batch:
+        assert batch.send_transaction(token, encode_transfer(env.bob, 10)) == 0
+        assert batch.send_transaction(token, encode_transfer(env.bob, 20)) == 1
+        assert batch.send_transaction(token, encode_transfer(env.bob, 30)) == 2
+        assert len(batch) == 3
+        # Nothing is sent until the block exits
+        assert mock_erc20.balance_of(env.bob) == bob_before
+
+    assert mock_erc20.balance_of(env.bob) == bob_before + 60
+    receipts = batch.receipts
+    assert len(receipts) == 3
+    assert [r["nonce"] for r in receipts] == list(range(receipts[0]["nonce"], receipts[0]["nonce"] + 3))
+
+    with pytest.raises(RuntimeError, match="already been submitted"):
+        batch.send_transaction(token, encode_transfer(env.bob, 1))
+
+
+@pytest.mark.asyncio
+async def test_batch_discarded_when_block_raises():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    bob_before = mock_erc20.balance_of(env.bob)
+
+    with pytest.raises(KeyError):
+        async with env.alice_client.batch() as batch:
+            batch.send_transaction(token, encode_transfer(env.bob, 10))
+            raise KeyError("abort")
+
+    assert mock_erc20.balance_of(env.bob) == bob_before
+    assert batch.receipts == []
+
+
+@pytest.mark.asyncio
+async def test_batch_stops_at_revert():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    bob_before = mock_erc20.balance_of(env.bob)
+
+    batch = env.alice_client.batch()
+    batch.send_transaction(token, encode_transfer(env.bob, 40))
+    batch.send_transaction(token, encode_transfer(env.bob, 1000))
+    batch.send_transaction(token, encode_transfer(env.bob, 40))
+    with pytest.raises((RuntimeError, TransactionRevertError)):
+        await batch.submit()
+
+    assert mock_erc20.balance_of(env.bob) == bob_before + 40
+    assert len(batch.receipts) == 1
+
+
+@pytest.mark.asyncio
+async def test_batch_only_queues_its_own_calls():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    escrow = env.addresses.erc20_addresses.escrow_obligation
+
+    async with env.alice_client.batch() as batch:
+        batch.send_transaction(token, encode_transfer(env.bob, 10))
+        # Extension client writes are sent straight away, not queued
+        receipt = await env.alice_client.erc20.approve({"address": token, "value": 25}, "escrow")
+        assert isinstance(receipt, str)
+        assert mock_erc20.allowance(env.alice, escrow) == 25
+        assert len(batch) == 1
+
+    assert len(batch.receipts) == 1
@@ -0,0 +1,22 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, TransactionRevertError
+
+
+def test_transaction_revert_error_is_runtime_error():
+    assert issubclass(TransactionRevertError, RuntimeError)
+
+
+@pytest.mark.asyncio
+async def test_compliant_approve_passes_checks():
+    env = EnvTestManager()
+    token = env.mock_addresses.erc20_a
+    mock_erc20 = MockERC20(token, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    tx_hash = await env.alice_client.erc20.approve({"address": token, "value": 100}, "escrow")
+    assert tx_hash.startswith("0x")
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.escrow_obligation) == 100
+
+    # Lowering an approval is confirmed as well
+    await env.alice_client.erc20.approve({"address": token, "value": 10}, "escrow")
+    assert mock_erc20.allowance(env.alice, env.addresses.erc20_addresses.escrow_obligation) == 10
@@ -0,0 +1,54 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_value_wei_zero_uses_normal_path():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    item = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""}
+
+    await env.alice_client.erc20.approve(price, "escrow")
+    result = await env.alice_client.erc20.buy_with_erc20(price, item, 0, value_wei="0")
+    assert result["log"]["uid"] != "0x" + "00" * 32
+
+
+@pytest.mark.asyncio
+async def test_value_wei_rejected_by_non_payable_escrow():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    item = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""}
+
+    await env.alice_client.erc20.approve(price, "escrow")
+    with pytest.raises(ValueError, match="value_wei"):
+        await env.alice_client.erc20.buy_with_erc20(price, item, 0, value_wei="1000")
+
+    # Nothing was sent, so the tokens are still with Alice
+    assert mock_erc20.balance_of(env.alice) == 100
+
+
+def test_value_wei_must_be_decimal():
+    env = EnvTestManager()
+    price = {"address": env.mock_addresses.erc20_a, "value": 1}
+    item = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""}
+    with pytest.raises(ValueError):
+        env.alice_client.erc20.buy_with_erc20(price, item, 0, value_wei="lots")
+
+
+@pytest.mark.asyncio
+async def test_value_wei_other_failures_keep_their_cause():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    item = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""}
+
+    # No approval, so the escrow would revert with or without value attached
+    with pytest.raises(Exception) as excinfo:
+        await env.alice_client.erc20.buy_with_erc20(price, item, 0, value_wei="1000")
+    assert "value_wei" not in str(excinfo.value)
@@ -0,0 +1,27 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_verify_fulfillment_references():
+    env = EnvTestManager()
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 200)
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 200}, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    other = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+    other_uid = other["log"]["uid"]
+
+    string_client = env.bob_client.string_obligation
+    genuine = await string_client.do_obligation("for the escrow", buy_uid)
+    spoofed = await string_client.do_obligation("for another escrow", other_uid)
+    unrelated = await string_client.do_obligation("for nothing", None)
+
+    assert await env.alice_client.verify_fulfillment_references(buy_uid, genuine) is True
+    assert await env.alice_client.verify_fulfillment_references(buy_uid, spoofed) is False
+    assert await env.alice_client.verify_fulfillment_references(buy_uid, unrelated) is False
+
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.verify_fulfillment_references(buy_uid, "0x" + "00" * 31 + "01")
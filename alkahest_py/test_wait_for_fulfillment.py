@@ -0,0 +1,67 @@
+import asyncio
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+@pytest.mark.asyncio
+async def test_wait_for_fulfillment_block_context():
+    env = EnvTestManager()
+    mock_erc20_a = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20_b = MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider)
+    mock_erc20_a.transfer(env.alice, 100)
+    mock_erc20_b.transfer(env.bob, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    payment = await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid)
+
+    claim = await env.alice_client.wait_for_fulfillment(
+        env.addresses.erc20_addresses.escrow_obligation, buy_uid, 0
+    )
+
+    assert claim["payment"] == buy_uid
+    assert claim["fulfillment"] == payment["log"]["uid"]
+    assert claim["block_number"] is not None and claim["block_number"] > 0
+    assert claim["transaction_hash"] == payment["transaction_hash"]
+
+
+@pytest.mark.asyncio
+async def test_wait_for_fulfillment_confirmations():
+    env = EnvTestManager()
+    mock_erc20_a = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20_b = MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider)
+    mock_erc20_a.transfer(env.alice, 100)
+    mock_erc20_b.transfer(env.bob, 200)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    buy_uid = escrow["log"]["uid"]
+
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    payment = await env.bob_client.erc20.pay_erc20_for_erc20(buy_uid)
+
+    waiter = asyncio.ensure_future(
+        env.alice_client.wait_for_fulfillment(
+            env.addresses.erc20_addresses.escrow_obligation, buy_uid, 0, confirmations=3
+        )
+    )
+    await asyncio.sleep(2)
+    assert not waiter.done(), "Claim resolved before reaching the requested depth"
+
+    # Each transfer mines a block on anvil, taking the claim to 3 confirmations
+    mock_erc20_a.transfer(env.bob, 1)
+    mock_erc20_a.transfer(env.bob, 1)
+
+    claim = await asyncio.wait_for(waiter, timeout=30)
+    assert claim["fulfillment"] == payment["log"]["uid"]
+    assert claim["transaction_hash"] == payment["transaction_hash"]
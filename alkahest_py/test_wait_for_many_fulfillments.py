@@ -0,0 +1,54 @@
+import pytest
+from alkahest_py import EnvTestManager, MockERC20
+
+
+async def open_escrows(env, count):
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 10 * count)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 20 * count)
+
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 10}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 20}
+    await env.alice_client.erc20.approve({**bid_data, "value": 10 * count}, "escrow")
+    await env.bob_client.erc20.approve({**ask_data, "value": 20 * count}, "payment")
+
+    uids = []
+    for _ in range(count):
+        escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+        uids.append(escrow["log"]["uid"])
+    return uids
+
+
+@pytest.mark.asyncio
+async def test_wait_for_any_fulfillment():
+    env = EnvTestManager()
+    first, second = await open_escrows(env, 2)
+
+    payment = await env.bob_client.erc20.pay_erc20_for_erc20(second)
+    claim = await env.alice_client.wait_for_any_fulfillment([first, second], timeout=30)
+
+    assert claim["payment"] == second
+    assert claim["fulfillment"] == payment["log"]["uid"]
+    assert claim["transaction_hash"] == payment["transaction_hash"]
+
+
+@pytest.mark.asyncio
+async def test_wait_for_all_fulfillment():
+    env = EnvTestManager()
+    first, second = await open_escrows(env, 2)
+
+    await env.bob_client.erc20.pay_erc20_for_erc20(first)
+    with pytest.raises(TimeoutError):
+        await env.alice_client.wait_for_all_fulfillment([first, second], timeout=2)
+
+    await env.bob_client.erc20.pay_erc20_for_erc20(second)
+    claims = await env.alice_client.wait_for_all_fulfillment([second, first], timeout=30)
+    assert [claim["payment"] for claim in claims] == [second, first]
+
+
+@pytest.mark.asyncio
+async def test_wait_for_fulfillment_of_unknown_escrow():
+    env = EnvTestManager()
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.wait_for_any_fulfillment(["0x" + "00" * 31 + "01"], timeout=5)
+    with pytest.raises(ValueError, match="empty"):
+        await env.alice_client.wait_for_any_fulfillment([])
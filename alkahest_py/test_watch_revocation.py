@@ -0,0 +1,62 @@
+import asyncio
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, Revoked
+
+
+async def make_escrow(env):
+    MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider).transfer(env.alice, 100)
+    MockERC20(env.mock_addresses.erc20_b, env.god_wallet_provider).transfer(env.bob, 200)
+    bid_data = {"address": env.mock_addresses.erc20_a, "value": 100}
+    ask_data = {"address": env.mock_addresses.erc20_b, "value": 200}
+    await env.alice_client.erc20.approve(bid_data, "escrow")
+    escrow = await env.alice_client.erc20.buy_erc20_for_erc20(bid_data, ask_data, 0)
+    await env.bob_client.erc20.approve(ask_data, "payment")
+    return escrow["log"]["uid"]
+
+
+@pytest.mark.asyncio
+async def test_watch_revocation_fires():
+    env = EnvTestManager()
+    uid = await make_escrow(env)
+    seen = []
+
+    watch = asyncio.ensure_future(
+        env.alice_client.watch_revocation(uid, seen.append, timeout=30)
+    )
+    # Claiming the escrow revokes its attestation
+    await env.bob_client.erc20.pay_erc20_for_erc20(uid)
+    revoked = await watch
+
+    assert isinstance(revoked, Revoked)
+    assert revoked.uid == uid
+    assert [event.uid for event in seen] == [uid]
+
+
+@pytest.mark.asyncio
+async def test_watch_revocation_async_callback():
+    env = EnvTestManager()
+    uid = await make_escrow(env)
+    await env.bob_client.erc20.pay_erc20_for_erc20(uid)
+    seen = []
+
+    async def on_revoked(event):
+        seen.append(event.uid)
+
+    # Already revoked, so it fires straight away
+    revoked = await env.alice_client.watch_revocation(uid, on_revoked, timeout=10)
+    assert revoked.uid == uid
+    assert seen == [uid]
+
+
+@pytest.mark.asyncio
+async def test_watch_revocation_times_out():
+    env = EnvTestManager()
+    uid = await make_escrow(env)
+    seen = []
+
+    assert await env.alice_client.watch_revocation(uid, seen.append, timeout=2) is None
+    assert seen == []
+
+    with pytest.raises(ValueError, match="not found"):
+        await env.alice_client.watch_revocation("0x" + "00" * 31 + "01", seen.append, timeout=2)
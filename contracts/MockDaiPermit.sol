@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @notice Minimal DAI-style token for testing the DAI permit path
+/// @dev The deployer is minted 10^24 tokens. DAI's `permit` takes the holder's
+/// nonce and an `allowed` flag instead of an amount and deadline, and sets an
+/// unlimited or zero allowance. There is no EIP-2612 `permit`, so permit type
+/// detection finds DAI's typehash.
+contract MockDaiPermit {
+    bytes32 public constant PERMIT_TYPEHASH =
+        keccak256("Permit(address holder,address spender,uint256 nonce,uint256 expiry,bool allowed)");
+    bytes32 public immutable DOMAIN_SEPARATOR;
+
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+    mapping(address => uint256) public nonces;
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    constructor() {
+        DOMAIN_SEPARATOR = keccak256(
+            abi.encode(
+                keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"),
+                keccak256(bytes("Mock Dai")),
+                keccak256(bytes("1")),
+                block.chainid,
+                address(this)
+            )
+        );
+        balanceOf[msg.sender] = 1e24;
+        emit Transfer(address(0), msg.sender, 1e24);
+    }
+
+    function transfer(address to, uint256 value) external returns (bool) {
+        return transferFrom(msg.sender, to, value);
+    }
+
+    function transferFrom(address from, address to, uint256 value) public returns (bool) {
+        require(balanceOf[from] >= value, "insufficient balance");
+        if (from != msg.sender && allowance[from][msg.sender] != type(uint256).max) {
+            require(allowance[from][msg.sender] >= value, "insufficient allowance");
+            allowance[from][msg.sender] -= value;
+        }
+        balanceOf[from] -= value;
+        balanceOf[to] += value;
+        emit Transfer(from, to, value);
+        return true;
+    }
+
+    function approve(address spender, uint256 value) external returns (bool) {
+        allowance[msg.sender][spender] = value;
+        emit Approval(msg.sender, spender, value);
+        return true;
+    }
+
+    function permit(
+        address holder,
+        address spender,
+        uint256 nonce,
+        uint256 expiry,
+        bool allowed,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external {
+        bytes32 digest = keccak256(
+            abi.encodePacked(
+                "\x19\x01",
+                DOMAIN_SEPARATOR,
+                keccak256(abi.encode(PERMIT_TYPEHASH, holder, spender, nonce, expiry, allowed))
+            )
+        );
+        require(holder != address(0), "invalid holder");
+        require(holder == ecrecover(digest, v, r, s), "invalid permit");
+        require(expiry == 0 || block.timestamp <= expiry, "permit expired");
+        require(nonce == nonces[holder]++, "invalid nonce");
+        uint256 value = allowed ? type(uint256).max : 0;
+        allowance[holder][spender] = value;
+        emit Approval(holder, spender, value);
+    }
+}
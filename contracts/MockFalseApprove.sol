@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @notice Minimal non-compliant ERC20 for testing approval checks
+/// @dev The deployer is minted 10^24 tokens. `approve` returns false without
+/// setting an allowance instead of reverting, as some older tokens do.
+contract MockFalseApprove {
+    mapping(address => uint256) public balanceOf;
+    mapping(address => mapping(address => uint256)) public allowance;
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    constructor() {
+        balanceOf[msg.sender] = 1e24;
+        emit Transfer(address(0), msg.sender, 1e24);
+    }
+
+    function transfer(address to, uint256 value) external returns (bool) {
+        require(balanceOf[msg.sender] >= value, "insufficient balance");
+        balanceOf[msg.sender] -= value;
+        balanceOf[to] += value;
+        emit Transfer(msg.sender, to, value);
+        return true;
+    }
+
+    function approve(address, uint256) external pure returns (bool) {
+        return false;
+    }
+}
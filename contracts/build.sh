@@ -0,0 +1,7 @@
+#!/bin/sh
+# Compile the contracts the tests deploy into the artifacts they load
+set -e
+cd "$(dirname "$0")"
+mkdir -p out
+solc --optimize --combined-json abi,bin MockDaiPermit.sol > out/MockDaiPermit.json
+solc --optimize --combined-json abi,bin MockFalseApprove.sol > out/MockFalseApprove.json
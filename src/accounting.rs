@@ -0,0 +1,119 @@
+use alkahest_rs::contracts::IEAS;
+use alloy::primitives::FixedBytes;
+use pyo3::{exceptions::PyValueError, IntoPyObject, PyResult};
+
+use crate::{
+    clients::{
+        erc1155::PyERC1155PaymentObligationData, erc20::PyERC20PaymentObligationData,
+        erc721::PyERC721PaymentObligationData, string_obligation::PyStringObligationData,
+    },
+    error_handling::map_parse_to_pyerr,
+    escrow::{EscrowContracts, EscrowObligation, PaymentDemand},
+    explain::ObligationKind,
+    types::EscowClaimedLog,
+};
+
+/// A fulfillment's obligation data, decoded according to the contract that
+/// attested it
+#[derive(IntoPyObject)]
+pub enum Fulfillment {
+    Payment(PaymentDemand),
+    String(PyStringObligationData),
+    /// The undecoded data, for obligation contracts the binding has no type for
+    Raw(Vec<u8>),
+}
+
+impl Fulfillment {
+    fn decode(kind: Option<ObligationKind>, data: &[u8]) -> Self {
+        let data = data.to_vec();
+        let decoded = match kind {
+            Some(ObligationKind::Erc20Payment) => {
+                PyERC20PaymentObligationData::decode(data.clone())
+                    .ok()
+                    .map(|payment| Self::Payment(PaymentDemand::Erc20(payment)))
+            }
+            Some(ObligationKind::Erc721Payment) => {
+                PyERC721PaymentObligationData::decode(data.clone())
+                    .ok()
+                    .map(|payment| Self::Payment(PaymentDemand::Erc721(payment)))
+            }
+            Some(ObligationKind::Erc1155Payment) => {
+                PyERC1155PaymentObligationData::decode(data.clone())
+                    .ok()
+                    .map(|payment| Self::Payment(PaymentDemand::Erc1155(payment)))
+            }
+            Some(ObligationKind::StringObligation) => {
+                PyStringObligationData::decode(data.clone()).ok().map(Self::String)
+            }
+            _ => None,
+        };
+        decoded.unwrap_or(Self::Raw(data))
+    }
+}
+
+/// A completed barter, from the side of the buyer who made the escrow
+#[derive(IntoPyObject)]
+pub struct BarterResult {
+    pub buy_attestation: String,
+    pub fulfillment: String,
+    /// The escrow's depositor
+    pub buyer: String,
+    /// The fulfiller who claimed the escrow
+    pub counterparty: String,
+    /// Token type of the escrow: "erc20", "erc721", "erc1155" or "token_bundle"
+    pub gave_type: String,
+    pub gave: EscrowObligation,
+    /// Kind of obligation the fulfillment is, such as "ERC20 payment", or "unknown"
+    pub received_type: String,
+    pub received: Fulfillment,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+}
+
+/// Summarize a barter from its escrow, the fulfillment that claimed it, and the
+/// claim itself
+///
+/// The claim must be for this escrow and its fulfillment must exist. The claim's
+/// `EscrowClaimed` log is what ties the two together: payment fulfillments don't
+/// set a `refUID`, so the fulfillment's own reference isn't checked.
+pub fn barter_result(
+    escrow: &IEAS::Attestation,
+    fulfillment: &IEAS::Attestation,
+    claim: EscowClaimedLog,
+    contracts: &EscrowContracts,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> PyResult<BarterResult> {
+    let payment: FixedBytes<32> = claim.payment.parse().map_err(map_parse_to_pyerr)?;
+    if payment != escrow.uid {
+        return Err(PyValueError::new_err(format!(
+            "Claim is for escrow {}, not {}",
+            payment, escrow.uid
+        )));
+    }
+    if fulfillment.uid == FixedBytes::<32>::ZERO {
+        return Err(PyValueError::new_err(format!(
+            "Fulfillment attestation {} not found",
+            claim.fulfillment
+        )));
+    }
+    let gave_type = contracts.token_type(escrow.attester);
+    let gave = gave_type
+        .and_then(|token_type| EscrowObligation::decode(token_type, &escrow.data))
+        .ok_or_else(|| {
+            PyValueError::new_err(format!("Attestation {} is not a known escrow", escrow.uid))
+        })?;
+    let kind = ObligationKind::of(fulfillment.attester, addresses);
+
+    Ok(BarterResult {
+        buy_attestation: escrow.uid.to_string(),
+        fulfillment: claim.fulfillment,
+        buyer: format!("{:?}", escrow.recipient),
+        counterparty: claim.fulfiller,
+        gave_type: gave_type.unwrap_or_default().to_string(),
+        gave,
+        received_type: kind.map_or("unknown", ObligationKind::label).to_string(),
+        received: Fulfillment::decode(kind, &fulfillment.data),
+        block_number: claim.block_number,
+        transaction_hash: claim.transaction_hash,
+    })
+}
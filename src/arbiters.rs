@@ -0,0 +1,146 @@
+use alkahest_rs::contracts::ERC20PaymentObligation;
+use alloy::{
+    primitives::{keccak256, Address, Bytes, FixedBytes, U256},
+    sol_types::SolValue,
+};
+use pyo3::{pyfunction, IntoPyObject, PyResult};
+
+use crate::{
+    error_handling::map_parse_to_pyerr,
+    escrow::resolve_addresses,
+    types::{ArbiterData, DefaultExtensionConfig},
+};
+
+mod uid_arbiter {
+    alloy::sol! {
+        struct DemandData {
+            bytes32 uid;
+        }
+    }
+}
+
+mod recipient_arbiter {
+    alloy::sol! {
+        struct DemandData {
+            address recipient;
+        }
+    }
+}
+
+mod intrinsics_arbiter_2 {
+    alloy::sol! {
+        struct DemandData {
+            bytes32 schema;
+        }
+    }
+}
+
+/// An arbiter address paired with an encoded demand for it, in the shape escrow
+/// methods accept as their `item`/`demand` argument
+#[derive(IntoPyObject)]
+pub struct EncodedArbiterDemand {
+    pub arbiter: String,
+    pub demand: Vec<u8>,
+}
+
+/// Encode a demand for the UID arbiter, which only accepts the fulfillment
+/// attestation with the given UID
+#[pyfunction]
+#[pyo3(signature = (uid, addresses=None))]
+pub fn encode_uid_arbiter_demand(
+    uid: String,
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<EncodedArbiterDemand> {
+    let addresses = resolve_addresses(addresses)?;
+    let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+    Ok(EncodedArbiterDemand {
+        arbiter: format!("{:?}", addresses.arbiters_addresses.uid_arbiter),
+        demand: uid_arbiter::DemandData { uid }.abi_encode(),
+    })
+}
+
+/// Encode a demand for the recipient arbiter, which only accepts fulfillment
+/// attestations made out to the given recipient
+#[pyfunction]
+#[pyo3(signature = (recipient, addresses=None))]
+pub fn encode_recipient_arbiter_demand(
+    recipient: String,
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<EncodedArbiterDemand> {
+    let addresses = resolve_addresses(addresses)?;
+    let recipient: Address = recipient.parse().map_err(map_parse_to_pyerr)?;
+    Ok(EncodedArbiterDemand {
+        arbiter: format!("{:?}", addresses.arbiters_addresses.recipient_arbiter),
+        demand: recipient_arbiter::DemandData { recipient }.abi_encode(),
+    })
+}
+
+/// Encode a demand for the intrinsics arbiter, which accepts any fulfillment
+/// attestation that is itself valid: not expired and not revoked
+///
+/// The arbiter reads nothing from the demand, so it is empty.
+#[pyfunction]
+#[pyo3(signature = (addresses=None))]
+pub fn encode_intrinsics_arbiter_demand(
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<EncodedArbiterDemand> {
+    let addresses = resolve_addresses(addresses)?;
+    Ok(EncodedArbiterDemand {
+        arbiter: format!("{:?}", addresses.arbiters_addresses.intrinsics_arbiter),
+        demand: Vec::new(),
+    })
+}
+
+/// Encode a demand for the second intrinsics arbiter, which performs the same
+/// expiry and revocation checks and also requires the fulfillment attestation
+/// to use the given schema
+#[pyfunction]
+#[pyo3(signature = (schema, addresses=None))]
+pub fn encode_intrinsics_arbiter_2_demand(
+    schema: String,
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<EncodedArbiterDemand> {
+    let addresses = resolve_addresses(addresses)?;
+    let schema: FixedBytes<32> = schema.parse().map_err(map_parse_to_pyerr)?;
+    Ok(EncodedArbiterDemand {
+        arbiter: format!("{:?}", addresses.arbiters_addresses.intrinsics_arbiter_2),
+        demand: intrinsics_arbiter_2::DemandData { schema }.abi_encode(),
+    })
+}
+
+/// Build an escrow demand for an ERC20 payment of `amount` of `token` to `payee`
+///
+/// Uses the same arbiter and demand as the `buy_*_for_erc20` helpers: the ERC20
+/// payment obligation contract, which accepts a fulfillment made with
+/// `pay_with_erc20` for exactly this payment. The result can be passed as the
+/// `item` of any `buy_with_*` method.
+#[pyfunction]
+#[pyo3(signature = (token, amount, payee, addresses=None))]
+pub fn demand_erc20_payment(
+    token: String,
+    amount: u64,
+    payee: String,
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<EncodedArbiterDemand> {
+    let addresses = resolve_addresses(addresses)?;
+    let demand = ERC20PaymentObligation::ObligationData {
+        token: token.parse().map_err(map_parse_to_pyerr)?,
+        amount: U256::from(amount),
+        payee: payee.parse().map_err(map_parse_to_pyerr)?,
+    };
+    Ok(EncodedArbiterDemand {
+        arbiter: format!("{:?}", addresses.erc20_addresses.payment_obligation),
+        demand: demand.abi_encode(),
+    })
+}
+
+/// Hash an arbiter and demand, so parties agreeing on terms off-chain can check
+/// they are looking at the same ones
+///
+/// The hash is `keccak256(abi.encode(arbiter, demand))`, returned as `0x`-hex.
+#[pyfunction]
+pub fn demand_hash(item: ArbiterData) -> PyResult<String> {
+    let arbiter: Address = item.arbiter.parse().map_err(map_parse_to_pyerr)?;
+    let demand = Bytes::from(item.demand);
+    Ok(keccak256((arbiter, demand).abi_encode_params()).to_string())
+}
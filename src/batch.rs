@@ -0,0 +1,208 @@
+use std::sync::{Arc, Mutex};
+
+use alkahest_rs::types::WalletProvider;
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+};
+use pyo3::{pyclass, pymethods, IntoPyObject, Py, PyAny, PyObject, PyResult, Python};
+
+use crate::{
+    error_handling::{map_parse_to_pyerr, TransactionRevertError},
+    gas::with_gas_multiplier,
+    nonce_manager::NonceManager,
+};
+
+/// Outcome of one transaction sent by a batch
+#[derive(Clone, IntoPyObject)]
+pub struct BatchReceipt {
+    pub transaction_hash: String,
+    pub block_number: Option<u64>,
+    pub gas_used: u64,
+    pub nonce: u64,
+}
+
+/// Transactions queued to be sent together, with consecutive nonces, when the
+/// batch's `async with` block exits
+///
+/// Only calls queued through the batch's own `send_transaction` are grouped; the
+/// extension clients' methods send immediately even inside the block. The signer's
+/// nonce lock is held for the whole submission, so no other write from the signer
+/// can take a nonce in the middle of the batch. Transactions are sent in queue
+/// order, each after the previous one is mined so later steps are estimated
+/// against the state earlier ones leave behind. If one reverts the rest are not
+/// sent, and `receipts` holds the transactions that were mined.
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTransactionBatch {
+    wallet_provider: WalletProvider,
+    nonce_manager: NonceManager,
+    gas_multiplier: f64,
+    nonce: Option<u64>,
+    queued: Arc<Mutex<Option<Vec<TransactionRequest>>>>,
+    receipts: Arc<Mutex<Vec<BatchReceipt>>>,
+}
+
+impl PyTransactionBatch {
+    pub fn new(
+        wallet_provider: WalletProvider,
+        nonce_manager: NonceManager,
+        gas_multiplier: f64,
+        nonce: Option<u64>,
+    ) -> Self {
+        Self {
+            wallet_provider,
+            nonce_manager,
+            gas_multiplier,
+            nonce,
+            queued: Arc::new(Mutex::new(Some(Vec::new()))),
+            receipts: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Take the queued transactions, leaving the batch closed to further calls
+    fn take_queued(&self) -> PyResult<Vec<TransactionRequest>> {
+        self.queued
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(batch_closed)
+    }
+
+    async fn submit(self, queued: Vec<TransactionRequest>) -> PyResult<()> {
+        let _nonce_guard = self.nonce_manager.acquire().await?;
+        let signer = self.nonce_manager.signer();
+        let base = match self.nonce {
+            Some(nonce) => nonce,
+            None => self
+                .wallet_provider
+                .get_transaction_count(signer)
+                .pending()
+                .await
+                .map_err(|e| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+                })?,
+        };
+
+        for (i, tx) in queued.into_iter().enumerate() {
+            let nonce = base + i as u64;
+            let tx = tx.from(signer).nonce(nonce);
+            // Scaled by the multiplier on its way back through the transport
+            let estimate = with_gas_multiplier(self.gas_multiplier, async {
+                self.wallet_provider.estimate_gas(tx.clone()).await
+            })
+            .await
+            .map_err(|e| {
+                pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                    "Batch transaction {} failed to estimate: {}",
+                    i, e
+                ))
+            })?;
+            let tx = tx.gas_limit(estimate);
+
+            let receipt = self
+                .wallet_provider
+                .send_transaction(tx)
+                .await
+                .map_err(|e| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+                })?
+                .get_receipt()
+                .await
+                .map_err(|e| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+                })?;
+            if !receipt.status() {
+                return Err(TransactionRevertError::new_err(format!(
+                    "Batch transaction {} ({}) reverted, later transactions were not sent",
+                    i, receipt.transaction_hash
+                )));
+            }
+
+            self.receipts
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(BatchReceipt {
+                    transaction_hash: receipt.transaction_hash.to_string(),
+                    block_number: receipt.block_number,
+                    gas_used: receipt.gas_used,
+                    nonce,
+                });
+        }
+        Ok(())
+    }
+}
+
+fn batch_closed() -> pyo3::PyErr {
+    pyo3::exceptions::PyRuntimeError::new_err("Batch has already been submitted or discarded")
+}
+
+#[pymethods]
+impl PyTransactionBatch {
+    /// Queue a raw transaction, returning its position in the batch
+    #[pyo3(signature = (to, data, value=0))]
+    pub fn send_transaction(&self, to: String, data: Vec<u8>, value: u128) -> PyResult<usize> {
+        let to: Address = to.parse().map_err(map_parse_to_pyerr)?;
+        let mut queued = self.queued.lock().unwrap_or_else(|e| e.into_inner());
+        let queued = queued.as_mut().ok_or_else(batch_closed)?;
+        queued.push(
+            TransactionRequest::default()
+                .to(to)
+                .value(U256::from(value))
+                .input(Bytes::from(data).into()),
+        );
+        Ok(queued.len() - 1)
+    }
+
+    /// Number of transactions queued so far
+    pub fn __len__(&self) -> usize {
+        self.queued
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map_or(0, Vec::len)
+    }
+
+    /// Receipts of the batch's mined transactions, in order
+    #[getter]
+    pub fn receipts(&self) -> Vec<BatchReceipt> {
+        self.receipts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Send the queued transactions now, returning their receipts
+    pub fn submit<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let queued = self.take_queued()?;
+        let batch = self.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            batch.clone().submit(queued).await?;
+            Ok(batch.receipts())
+        })
+    }
+
+    pub fn __aenter__<'py>(slf: Py<Self>, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move { Ok(slf) })
+    }
+
+    /// Submit the batch, unless the block raised, in which case it is discarded
+    /// unsent
+    pub fn __aexit__<'py>(
+        &self,
+        py: Python<'py>,
+        exc_type: Option<PyObject>,
+        _exc_value: Option<PyObject>,
+        _traceback: Option<PyObject>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let queued = self.take_queued()?;
+        let batch = self.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            if exc_type.is_none() {
+                batch.submit(queued).await?;
+            }
+            Ok(false)
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use pyo3::{exceptions::PyValueError, PyResult, Python};
+
+/// Upper bound on the threads one batch is decoded across
+const MAX_THREADS: usize = 8;
+
+/// Batches smaller than this per thread are decoded on the calling thread, where
+/// spawning would cost more than the decoding
+const MIN_CHUNK: usize = 64;
+
+/// Decode each of `items` with `decode`, without holding the GIL
+///
+/// The items are split into contiguous chunks decoded on scoped threads, so
+/// results keep their input order. If any item fails, the error names the
+/// lowest failing index, as decoding one by one would.
+pub fn decode_many<T, E, F>(py: Python<'_>, items: Vec<Vec<u8>>, decode: F) -> PyResult<Vec<T>>
+where
+    T: Send,
+    E: Display + Send,
+    F: Fn(Vec<u8>) -> Result<T, E> + Send + Sync,
+{
+    py.allow_threads(|| decode_chunks(items, &decode))
+        .map_err(|(index, e)| {
+            PyValueError::new_err(format!("Failed to decode item {}: {}", index, e))
+        })
+}
+
+fn decode_chunks<T, E, F>(items: Vec<Vec<u8>>, decode: &F) -> Result<Vec<T>, (usize, E)>
+where
+    T: Send,
+    E: Send,
+    F: Fn(Vec<u8>) -> Result<T, E> + Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |threads| threads.get())
+        .min(MAX_THREADS);
+    let chunk_size = items.len().div_ceil(threads).max(MIN_CHUNK);
+    if items.len() <= chunk_size {
+        return decode_chunk(0, items, decode);
+    }
+
+    let mut chunks = Vec::new();
+    let mut items = items.into_iter();
+    loop {
+        let chunk: Vec<Vec<u8>> = items.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| scope.spawn(move || decode_chunk(i * chunk_size, chunk, decode)))
+            .collect();
+        // Joined in order, so the first error seen is the lowest failing index
+        let mut decoded = Vec::new();
+        for handle in handles {
+            let chunk = handle
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+            decoded.extend(chunk);
+        }
+        Ok(decoded)
+    })
+}
+
+/// Decode one chunk, stopping at its first failure
+fn decode_chunk<T, E, F>(
+    offset: usize,
+    chunk: Vec<Vec<u8>>,
+    decode: &F,
+) -> Result<Vec<T>, (usize, E)>
+where
+    F: Fn(Vec<u8>) -> Result<T, E>,
+{
+    chunk
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| decode(item).map_err(|e| (offset + i, e)))
+        .collect()
+}
@@ -1,75 +1,213 @@
-use alkahest_rs::extensions::AttestationModule;
-use alloy::primitives::{Address, FixedBytes};
-use pyo3::{pyclass, pymethods, PyResult};
+use std::{collections::HashMap, future::Future};
+
+use alkahest_rs::{
+    contracts::IEAS::{self, Attested},
+    extensions::AttestationModule,
+    types::WalletProvider,
+};
+use alloy::{
+    consensus::Transaction as _,
+    primitives::{Address, FixedBytes, TxHash},
+    providers::{DynProvider, Provider},
+    rpc::types::Filter,
+    sol_types::SolEvent,
+};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use pyo3::{pyclass, pymethods, IntoPyObject, PyResult};
 
 use crate::{
+    attested_log_with_hash,
+    contract::{schema_uid, PyAttestation},
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{ArbiterData, AttestationRequest, AttestedLog, LogWithHash},
+    escrow::check_expiration,
+    gas,
+    nonce_manager::NonceManager,
+    types::{ArbiterData, AttestationRequest, DefaultArbiter, GasMultiplier},
 };
 
+/// Most attestations `get_attestations` requests at once, so a long list of UIDs
+/// doesn't flood the node
+const GET_ATTESTATIONS_CONCURRENCY: usize = 16;
+
+/// A schema registered by `register_schema_with_uid`
+#[derive(IntoPyObject)]
+pub struct RegisteredSchema {
+    /// UID the registry assigned, for use as an attestation's `schema`
+    pub schema_uid: String,
+    pub transaction_hash: String,
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct AttestationClient {
     pub(crate) inner: AttestationModule,
+    nonce_manager: NonceManager,
+    wallet_provider: WalletProvider,
+    // Serves the binding's own reads and pre-checks; the wallet provider when
+    // no separate read RPC was configured
+    read_provider: DynProvider,
+    default_arbiter: DefaultArbiter,
+    gas_multiplier: GasMultiplier,
 }
 
 impl AttestationClient {
-    pub fn new(inner: AttestationModule) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: AttestationModule,
+        nonce_manager: NonceManager,
+        wallet_provider: WalletProvider,
+        read_provider: DynProvider,
+        default_arbiter: DefaultArbiter,
+        gas_multiplier: GasMultiplier,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            wallet_provider,
+            read_provider,
+            default_arbiter,
+            gas_multiplier,
+        }
     }
-}
 
-#[pymethods]
-impl AttestationClient {
-    pub fn register_schema<'py>(
+    /// Register a schema, resolving to the UID it was registered under along with
+    /// the transaction hash
+    fn register(
         &self,
-        py: pyo3::Python<'py>,
         schema: String,
         resolver: String,
         revocable: bool,
-    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+    ) -> impl Future<Output = PyResult<RegisteredSchema>> + Send + 'static {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let schema: FixedBytes<32> = schema.parse().map_err(map_parse_to_pyerr)?;
             let resolver: Address = resolver.parse().map_err(map_parse_to_pyerr)?;
+            let schema = schema.to_string();
             let receipt = inner
-                .register_schema(schema.to_string(), resolver, revocable)
+                .register_schema(schema.clone(), resolver, revocable)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(RegisteredSchema {
+                schema_uid: schema_uid(&schema, resolver, revocable).to_string(),
+                transaction_hash: receipt.transaction_hash.to_string(),
+            })
+        }
+    }
+}
+
+#[pymethods]
+impl AttestationClient {
+    /// Register a schema, returning the transaction hash
+    ///
+    /// Use `register_schema_with_uid` to also get the UID the schema was registered
+    /// under.
+    #[pyo3(signature = (schema, resolver, revocable, gas_multiplier=None))]
+    pub fn register_schema<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        schema: String,
+        resolver: String,
+        revocable: bool,
+        gas_multiplier: Option<f64>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let register = self.register(schema, resolver, revocable);
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            Ok(register.await?.transaction_hash)
         })
     }
 
+    /// Register a schema, returning a dict with the `schema_uid` it was registered
+    /// under, for use as an attestation's `schema`, and the `transaction_hash`
+    #[pyo3(signature = (schema, resolver, revocable, gas_multiplier=None))]
+    pub fn register_schema_with_uid<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        schema: String,
+        resolver: String,
+        revocable: bool,
+        gas_multiplier: Option<f64>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let register = self.register(schema, resolver, revocable);
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, register)
+    }
+
+    /// Make an attestation
+    ///
+    /// With `idempotent` set, a retry of a call whose transaction went through but
+    /// whose response was lost returns the earlier attestation instead of making a
+    /// duplicate. Before sending, the signer's attestations with the same schema and
+    /// recipient are checked for an unrevoked one with the same ref UID, data,
+    /// expiration, revocability and value sent, and the most recent match is
+    /// returned. The search covers the last 10,000 blocks unless `from_block`
+    /// reaches further back, which is plenty for a retry and keeps the lookup
+    /// cheap. The check runs under the signer's nonce lock, so it also covers
+    /// concurrent calls from this process, but not from other processes using the
+    /// same key. It also means an intentionally repeated attestation can't be made
+    /// while an identical one is live: leave `idempotent` off for those.
+    #[pyo3(signature = (attestation, include_logs=false, idempotent=false, from_block=None, gas_multiplier=None))]
     pub fn attest<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
+        include_logs: bool,
+        idempotent: bool,
+        from_block: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .attest(attestation.try_into().map_err(map_eyre_to_pyerr)?)
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let request: IEAS::AttestationRequest =
+                attestation.try_into().map_err(map_eyre_to_pyerr)?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            if idempotent {
+                let prior = find_matching_attestation(
+                    &read_provider,
+                    inner.addresses.eas,
+                    nonce_manager.signer(),
+                    &request,
+                    from_block,
+                )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                if let Some(transaction_hash) = prior {
+                    let receipt = read_provider
+                        .get_transaction_receipt(transaction_hash)
+                        .await
+                        .map_err(|e| map_eyre_to_pyerr(e.into()))?
+                        .ok_or_else(|| {
+                            map_eyre_to_pyerr(eyre::eyre!(
+                                "Receipt for {} not found",
+                                transaction_hash
+                            ))
+                        })?;
+                    return attested_log_with_hash(receipt, include_logs)
+                        .map_err(map_eyre_to_pyerr);
+                }
+            }
+            let receipt = inner.attest(request).await.map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, gas_multiplier=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .collect_escrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
@@ -81,14 +219,19 @@ impl AttestationClient {
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, gas_multiplier=None))]
     pub fn collect_escrow_2<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .collect_escrow_2(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
@@ -100,84 +243,108 @@ impl AttestationClient {
         })
     }
 
+    /// Escrow an attestation request, to be attested once `demand` is fulfilled
+    ///
+    /// `demand` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    #[pyo3(signature = (attestation, demand, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn create_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
-        demand: ArbiterData,
+        demand: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let demand = self.default_arbiter.resolve(demand)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .create_escrow(
                     attestation.try_into().map_err(map_eyre_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+                    demand,
                     expiration,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Escrow an existing attestation, by UID, until `demand` is fulfilled
+    ///
+    /// `demand` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    #[pyo3(signature = (attestation, demand, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn create_escrow_2<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: String,
-        demand: ArbiterData,
+        demand: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let demand = self.default_arbiter.resolve(demand)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .create_escrow_2(
                     attestation.parse().map_err(map_parse_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+                    demand,
                     expiration,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Make an attestation and escrow it until `demand` is fulfilled
+    ///
+    /// `demand` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    #[pyo3(signature = (attestation, demand, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn attest_and_create_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
-        demand: ArbiterData,
+        demand: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let demand = self.default_arbiter.resolve(demand)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .attest_and_create_escrow(
                     attestation.try_into().map_err(map_eyre_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+                    demand,
                     expiration,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
@@ -197,4 +364,127 @@ impl AttestationClient {
             Ok(crate::contract::PyAttestation::from(attestation))
         })
     }
+
+    /// Get several attestations by UID, in the order given
+    ///
+    /// Each distinct UID is fetched once, at most 16 at a time, and repeated UIDs
+    /// share the result, so walking an obligation graph that reaches the same
+    /// attestation by several paths costs one RPC call per attestation.
+    pub fn get_attestations<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        uids: Vec<String>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let inner = self.inner.clone();
+        let uids = uids
+            .iter()
+            .map(|uid| uid.parse::<FixedBytes<32>>().map_err(map_parse_to_pyerr))
+            .collect::<PyResult<Vec<_>>>()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut unique = Vec::new();
+            let mut positions = HashMap::new();
+            for uid in &uids {
+                positions.entry(*uid).or_insert_with(|| {
+                    unique.push(*uid);
+                    unique.len() - 1
+                });
+            }
+
+            let fetched: Vec<_> =
+                stream::iter(unique.iter().map(|uid| inner.get_attestation(*uid)))
+                    .buffered(GET_ATTESTATIONS_CONCURRENCY)
+                    .try_collect()
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+            let fetched: Vec<PyAttestation> = fetched.into_iter().map(Into::into).collect();
+
+            Ok(uids
+                .iter()
+                .map(|uid| fetched[positions[uid]].clone())
+                .collect::<Vec<_>>())
+        })
+    }
+
+    /// Re-read an attestation from the chain and report whether it is revoked now,
+    /// rather than when a `PyAttestation` for it was fetched
+    pub fn is_revoked_onchain<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        uid: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let inner = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+            let attestation = inner
+                .get_attestation(uid)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Attestation {} not found",
+                    uid
+                )));
+            }
+            Ok(attestation.revocationTime != 0)
+        })
+    }
+}
+
+/// Blocks searched for a prior attestation when `attest` is given no `from_block`
+const IDEMPOTENCY_WINDOW: u64 = 10_000;
+
+/// Find the transaction of the signer's most recent live attestation matching a
+/// request, for idempotent `attest`
+///
+/// The `Attested` logs are narrowed by recipient, attester and schema, which are
+/// indexed, so only attestations that can match are fetched, newest first. EAS
+/// doesn't store the value an attestation was paid with, so a candidate's is read
+/// from the transaction that made it.
+async fn find_matching_attestation<P: Provider>(
+    provider: &P,
+    eas: Address,
+    attester: Address,
+    request: &IEAS::AttestationRequest,
+    from_block: Option<u64>,
+) -> eyre::Result<Option<TxHash>> {
+    let from_block = match from_block {
+        Some(from_block) => from_block,
+        None => provider
+            .get_block_number()
+            .await?
+            .saturating_sub(IDEMPOTENCY_WINDOW - 1),
+    };
+    let filter = Filter::new()
+        .address(eas)
+        .event_signature(Attested::SIGNATURE_HASH)
+        .topic1(request.data.recipient.into_word())
+        .topic2(attester.into_word())
+        .topic3(request.schema)
+        .from_block(from_block);
+    let logs = provider.get_logs(&filter).await?;
+
+    let eas = IEAS::new(eas, provider);
+    for log in logs.iter().rev() {
+        let uid = log.log_decode::<Attested>()?.inner.data.uid;
+        let existing = eas.getAttestation(uid).call().await?;
+        let matches = existing.revocationTime == 0
+            && existing.refUID == request.data.refUID
+            && existing.data == request.data.data
+            && existing.expirationTime == request.data.expirationTime
+            && existing.revocable == request.data.revocable;
+        if !matches {
+            continue;
+        }
+        let Some(transaction_hash) = log.transaction_hash else {
+            continue;
+        };
+        let value = provider
+            .get_transaction_by_hash(transaction_hash)
+            .await?
+            .map(|transaction| transaction.value());
+        if value == Some(request.data.value) {
+            return Ok(Some(transaction_hash));
+        }
+    }
+    Ok(None)
 }
@@ -1,12 +1,27 @@
-use alkahest_rs::extensions::Erc1155Module;
-use alloy::primitives::Address;
-use pyo3::{pyclass, pymethods, PyResult};
+use alkahest_rs::{
+    contracts::ERC1155EscrowObligation, extensions::Erc1155Module, types::WalletProvider,
+};
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::DynProvider,
+};
+use pyo3::{pyclass, pymethods, types::PyDict, Bound, PyResult, Python};
 
 use crate::{
+    attested_log_with_hash,
+    demand_decoders::{decode_demand, Demand},
+    encoding::BytesEncoding,
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
+    escrow::{
+        cancel_escrow_unsupported, check_expiration, decode_payment_demand, parse_value_wei,
+        payee_or_signer, resolve_addresses, send_with_value, PaymentDemand, StrictDemandCheck,
+    },
+    gas,
+    nonce_manager::NonceManager,
+    tokens::IERC1155,
     types::{
-        ArbiterData, AttestedLog, Erc1155Data, Erc20Data, Erc721Data, LogWithHash, TokenBundleData,
+        ArbiterData, DefaultArbiter, DefaultExtensionConfig, Erc1155Data, Erc20Data, Erc721Data,
+        GasMultiplier, OperatorApprovalStatus, TokenBundleData,
     },
 };
 
@@ -14,24 +29,63 @@ use crate::{
 #[derive(Clone)]
 pub struct Erc1155Client {
     inner: Erc1155Module,
+    nonce_manager: NonceManager,
+    wallet_provider: WalletProvider,
+    // Serves the binding's own reads and pre-checks; the wallet provider when
+    // no separate read RPC was configured
+    read_provider: DynProvider,
+    default_arbiter: DefaultArbiter,
+    gas_multiplier: GasMultiplier,
 }
 
 impl Erc1155Client {
-    pub fn new(inner: Erc1155Module) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Erc1155Module,
+        nonce_manager: NonceManager,
+        wallet_provider: WalletProvider,
+        read_provider: DynProvider,
+        default_arbiter: DefaultArbiter,
+        gas_multiplier: GasMultiplier,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            wallet_provider,
+            read_provider,
+            default_arbiter,
+            gas_multiplier,
+        }
+    }
+
+    /// The `strict=True` check of a `pay_erc1155_for_*` method for an escrow of
+    /// `escrowed` tokens
+    fn strict_demand_check(&self, strict: bool, escrowed: &'static str) -> StrictDemandCheck {
+        StrictDemandCheck::new(
+            self.read_provider.clone(),
+            self.inner.addresses.eas,
+            self.inner.addresses.payment_obligation,
+            escrowed,
+            "erc1155",
+            strict,
+        )
     }
 }
 
 #[pymethods]
 impl Erc1155Client {
+    #[pyo3(signature = (token_contract, purpose, gas_multiplier=None))]
     pub fn approve_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
             let purpose = match purpose.as_str() {
                 "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
@@ -46,14 +100,19 @@ impl Erc1155Client {
         })
     }
 
+    #[pyo3(signature = (token_contract, purpose, gas_multiplier=None))]
     pub fn revoke_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
             let purpose = match purpose.as_str() {
                 "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
@@ -68,14 +127,69 @@ impl Erc1155Client {
         })
     }
 
+    /// Check whether the escrow and payment obligation contracts are approved
+    /// operators of the signer's tokens in a ERC1155 collection
+    pub fn approval_status<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let owner = self.nonce_manager.own_address()?;
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let read_provider = self.read_provider.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let token: Address = token.parse().map_err(map_parse_to_pyerr)?;
+            let token = IERC1155::new(token, &read_provider);
+            let escrow_approval = token.isApprovedForAll(owner, escrow);
+            let payment_approval = token.isApprovedForAll(owner, payment);
+            let (escrow, payment) =
+                tokio::try_join!(escrow_approval.call(), payment_approval.call())
+                    .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(OperatorApprovalStatus { escrow, payment })
+        })
+    }
+
+    /// Check whether `operator` is approved for all of `owner`'s tokens in a
+    /// ERC1155 collection, `owner` defaulting to the signer
+    #[pyo3(signature = (token, operator, owner=None))]
+    pub fn is_operator_approved<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: String,
+        operator: String,
+        owner: Option<String>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let signer = self.nonce_manager.own_address();
+        let read_provider = self.read_provider.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let token: Address = token.parse().map_err(map_parse_to_pyerr)?;
+            let operator: Address = operator.parse().map_err(map_parse_to_pyerr)?;
+            let owner: Address = match owner {
+                Some(owner) => owner.parse().map_err(map_parse_to_pyerr)?,
+                None => signer?,
+            };
+            IERC1155::new(token, &read_provider)
+                .isApprovedForAll(owner, operator)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))
+        })
+    }
+
+    #[pyo3(signature = (buy_attestation, fulfillment, gas_multiplier=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .collect_escrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
@@ -87,13 +201,18 @@ impl Erc1155Client {
         })
     }
 
+    #[pyo3(signature = (buy_attestation, gas_multiplier=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
                 .await
@@ -102,65 +221,119 @@ impl Erc1155Client {
         })
     }
 
+    /// Withdraw an unfulfilled escrow before its expiration
+    ///
+    /// Always raises `NotImplementedError`: the escrow obligation contracts have no
+    /// early cancellation, so escrowed tokens can only be returned to the depositor
+    /// with `reclaim_expired` after the escrow expires.
+    pub fn cancel_escrow<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let _: FixedBytes<32> = buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            Err::<String, _>(cancel_escrow_unsupported(&buy_attestation))
+        })
+    }
+
+    /// Escrow an ERC1155 token until `item` is fulfilled
+    ///
+    /// `item` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    ///
+    /// `value_wei` sends native value along with the escrow, for obligation
+    /// contracts that charge a native fee. Contracts that don't accept value reject
+    /// it, which is reported as a `ValueError` before anything is sent.
+    #[pyo3(signature = (price, item, expiration, include_logs=false, force=false, value_wei=None, gas_multiplier=None))]
     pub fn buy_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc1155Data,
-        item: ArbiterData,
+        item: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        value_wei: Option<String>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let item = self.default_arbiter.resolve(item)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_erc1155(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let value_wei = parse_value_wei(value_wei)?;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let price: alkahest_rs::types::Erc1155Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let receipt = if value_wei.is_zero() {
+                inner
+                    .buy_with_erc1155(&price, &item, expiration)
+                    .await
                     .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            } else {
+                let escrow = ERC1155EscrowObligation::new(
+                    inner.addresses.escrow_obligation,
+                    &wallet_provider,
+                );
+                let data = ERC1155EscrowObligation::ObligationData {
+                    arbiter: item.arbiter,
+                    demand: item.demand,
+                    token: price.address,
+                    tokenId: price.id,
+                    amount: price.value,
+                };
+                send_with_value(escrow.doObligation(data, expiration), value_wei).await?
+            };
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Pay `price` to `payee`, or to the signer's own address if `payee` is None
+    #[pyo3(signature = (price, payee=None, include_logs=false, gas_multiplier=None))]
     pub fn pay_with_erc_1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc1155Data,
-        payee: String,
+        payee: Option<String>,
+        include_logs: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let payee: Address = payee.parse().map_err(map_parse_to_pyerr)?;
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let payee: Address = payee_or_signer(payee, nonce_manager.signer())?;
             let receipt = inner
                 .pay_with_erc1155(&price.try_into().map_err(map_eyre_to_pyerr)?, payee)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_erc1155_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc1155Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc1155_for_erc1155(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -169,46 +342,52 @@ impl Erc1155Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc1155_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc1155");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc1155_for_erc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc1155_for_erc1155(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_erc20_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc20Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc20_with_erc1155(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -217,46 +396,52 @@ impl Erc1155Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc20");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc1155_for_erc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc1155_for_erc20(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_erc721_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc721Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc721_with_erc1155(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -265,46 +450,52 @@ impl Erc1155Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc1155_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc721");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc1155_for_erc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc1155_for_erc721(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_bundle_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: TokenBundleData,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_bundle_with_erc1155(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -313,34 +504,31 @@ impl Erc1155Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc1155_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "token_bundle");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc1155_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc1155_for_bundle(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 }
@@ -348,15 +536,15 @@ impl Erc1155Client {
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC1155EscrowObligationData {
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token_id: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub amount: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub arbiter: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub demand: Vec<u8>,
 }
 
@@ -386,6 +574,30 @@ impl PyERC1155EscrowObligationData {
         )
     }
 
+    /// The amount in whole tokens, as a fixed-point string with exactly `decimals`
+    /// fractional digits that `decimal.Decimal` parses without rounding
+    pub fn amount_decimal(&self, decimals: u8) -> PyResult<String> {
+        let amount: alloy::primitives::U256 = self.amount.parse().map_err(map_parse_to_pyerr)?;
+        Ok(crate::types::format_units(amount, decimals))
+    }
+
+    /// Convert to a dict, rendering byte fields as `0x`-hex (the default), base64
+    /// or raw `bytes` according to `bytes_encoding`
+    #[pyo3(signature = (bytes_encoding=BytesEncoding::Hex))]
+    pub fn to_dict<'py>(
+        &self,
+        py: Python<'py>,
+        bytes_encoding: BytesEncoding,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("token", &self.token)?;
+        dict.set_item("token_id", &self.token_id)?;
+        dict.set_item("amount", &self.amount)?;
+        dict.set_item("arbiter", &self.arbiter)?;
+        dict.set_item("demand", bytes_encoding.encode(py, &self.demand)?)?;
+        Ok(dict)
+    }
+
     #[staticmethod]
     pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyERC1155EscrowObligationData> {
         use alloy::primitives::Bytes;
@@ -396,6 +608,24 @@ impl PyERC1155EscrowObligationData {
         Ok(decoded.into())
     }
 
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyERC1155EscrowObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyERC1155EscrowObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?)
+    }
+
     #[staticmethod]
     pub fn encode(obligation: &PyERC1155EscrowObligationData) -> PyResult<Vec<u8>> {
         use alkahest_rs::contracts::ERC1155EscrowObligation;
@@ -424,6 +654,32 @@ impl PyERC1155EscrowObligationData {
     pub fn encode_self(&self) -> PyResult<Vec<u8>> {
         PyERC1155EscrowObligationData::encode(self)
     }
+
+    /// Decode the demand as the payment this escrow asks for, using the
+    /// arbiter to pick the payment type. `addresses` defaults to the
+    /// alkahest-rs default deployment.
+    #[pyo3(signature = (addresses=None))]
+    pub fn decode_payment_demand(
+        &self,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<PaymentDemand> {
+        let addresses = resolve_addresses(addresses)?;
+        decode_payment_demand(&self.arbiter, &self.demand, &addresses)
+    }
+
+    /// Decode the demand according to the arbiter: as payment data for the
+    /// payment arbiters, with the decoder registered through
+    /// `register_demand_decoder` for custom arbiters, and otherwise as raw bytes.
+    /// `addresses` defaults to the alkahest-rs default deployment.
+    #[pyo3(signature = (addresses=None))]
+    pub fn decode_demand(
+        &self,
+        py: Python<'_>,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<Demand> {
+        let addresses = resolve_addresses(addresses)?;
+        decode_demand(py, &self.arbiter, &self.demand, &addresses)
+    }
 }
 
 impl From<alkahest_rs::contracts::ERC1155EscrowObligation::ObligationData>
@@ -443,13 +699,13 @@ impl From<alkahest_rs::contracts::ERC1155EscrowObligation::ObligationData>
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC1155PaymentObligationData {
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token_id: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub amount: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub payee: String,
 }
 
@@ -472,6 +728,13 @@ impl PyERC1155PaymentObligationData {
         )
     }
 
+    /// The amount in whole tokens, as a fixed-point string with exactly `decimals`
+    /// fractional digits that `decimal.Decimal` parses without rounding
+    pub fn amount_decimal(&self, decimals: u8) -> PyResult<String> {
+        let amount: alloy::primitives::U256 = self.amount.parse().map_err(map_parse_to_pyerr)?;
+        Ok(crate::types::format_units(amount, decimals))
+    }
+
     #[staticmethod]
     pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyERC1155PaymentObligationData> {
         use alloy::primitives::Bytes;
@@ -482,6 +745,24 @@ impl PyERC1155PaymentObligationData {
         Ok(decoded.into())
     }
 
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyERC1155PaymentObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyERC1155PaymentObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?)
+    }
+
     #[staticmethod]
     pub fn encode(obligation: &PyERC1155PaymentObligationData) -> PyResult<Vec<u8>> {
         use alkahest_rs::contracts::ERC1155PaymentObligation;
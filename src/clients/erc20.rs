@@ -1,11 +1,37 @@
-use alkahest_rs::extensions::Erc20Module;
-use pyo3::{pyclass, pymethods, PyResult};
+use alkahest_rs::{
+    contracts::{ERC20EscrowObligation, IEAS},
+    extensions::Erc20Module,
+    types::WalletProvider,
+};
+use alloy::{
+    primitives::{Address, Bytes, FixedBytes},
+    providers::DynProvider,
+    signers::local::PrivateKeySigner,
+};
+use pyo3::{
+    exceptions::PyValueError, pyclass, pymethods, types::PyDict, Bound, PyResult, Python,
+};
 
 use crate::{
+    attested_log_with_hash,
+    demand_decoders::{decode_demand, Demand},
+    encoding::BytesEncoding,
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
+    escrow::{
+        cancel_escrow_unsupported, check_expiration, decode_payment_demand, parse_value_wei,
+        payee_or_signer, resolve_addresses, send_with_value, EscrowObligation, PaymentDemand,
+        StrictDemandCheck,
+    },
+    gas,
+    nonce_manager::NonceManager,
+    simulation::compare_permit_cost,
+    tokens::{
+        check_erc20_approve, check_erc20_balance, confirm_erc20_allowance, detect_permit_type,
+        send_dai_permit, PermitType, IERC20,
+    },
     types::{
-        ArbiterData, AttestedLog, Erc1155Data, Erc20Data, Erc721Data, LogWithHash, TokenBundleData,
+        ArbiterData, DefaultArbiter, DefaultExtensionConfig, Erc1155Data, Erc20ApprovalStatus,
+        Erc20Data, Erc721Data, GasMultiplier, TokenBundleData,
     },
 };
 
@@ -13,68 +39,408 @@ use crate::{
 #[derive(Clone)]
 pub struct Erc20Client {
     inner: Erc20Module,
+    nonce_manager: NonceManager,
+    wallet_provider: WalletProvider,
+    // Serves the binding's own reads and pre-checks; the wallet provider when
+    // no separate read RPC was configured
+    read_provider: DynProvider,
+    // Needed to sign DAI-style permits, which alkahest-rs doesn't build; only
+    // known when the client was created from a private key
+    signer: Option<PrivateKeySigner>,
+    default_arbiter: DefaultArbiter,
+    gas_multiplier: GasMultiplier,
 }
 
 impl Erc20Client {
-    pub fn new(inner: Erc20Module) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Erc20Module,
+        nonce_manager: NonceManager,
+        wallet_provider: WalletProvider,
+        read_provider: DynProvider,
+        signer: Option<PrivateKeySigner>,
+        default_arbiter: DefaultArbiter,
+        gas_multiplier: GasMultiplier,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            wallet_provider,
+            read_provider,
+            signer,
+            default_arbiter,
+            gas_multiplier,
+        }
     }
+
+    /// The `strict=True` check of a `pay_erc20_for_*` method for an escrow of
+    /// `escrowed` tokens
+    fn strict_demand_check(&self, strict: bool, escrowed: &'static str) -> StrictDemandCheck {
+        StrictDemandCheck::new(
+            self.read_provider.clone(),
+            self.inner.addresses.eas,
+            self.inner.addresses.payment_obligation,
+            escrowed,
+            "erc20",
+            strict,
+        )
+    }
+}
+
+/// Send a DAI-style permit for `token` if that's the format it uses, returning
+/// whether one was sent, in which case the plain (non-permit) variant of the
+/// call should follow
+async fn send_permit_if_dai(
+    wallet_provider: &WalletProvider,
+    read_provider: &DynProvider,
+    signer: Option<&PrivateKeySigner>,
+    token: Address,
+    spender: Address,
+    permit_type: Option<PermitType>,
+    dai_expiry: Option<u64>,
+) -> PyResult<bool> {
+    let permit_type = match permit_type {
+        Some(permit_type) => permit_type,
+        None => detect_permit_type(read_provider, token)
+            .await
+            .map_err(map_eyre_to_pyerr)?,
+    };
+    if permit_type == PermitType::Eip2612 {
+        return Ok(false);
+    }
+    let signer = signer.ok_or_else(|| {
+        PyValueError::new_err("DAI-style permits need a client created from a private key")
+    })?;
+    let expiry = dai_expiry.ok_or_else(|| {
+        PyValueError::new_err(
+            "DAI-style permits grant an unlimited allowance; pass dai_expiry (a unix \
+             timestamp, or 0 for one that never expires) to sign one",
+        )
+    })?;
+    send_dai_permit(wallet_provider, signer, token, spender, expiry)
+        .await
+        .map_err(map_eyre_to_pyerr)?;
+    Ok(true)
+}
+
+/// The ERC20 token an escrow holding `escrowed` tokens demands payment in
+async fn demanded_erc20_token(
+    read_provider: &DynProvider,
+    eas: Address,
+    buy_attestation: FixedBytes<32>,
+    escrowed: &str,
+) -> PyResult<Address> {
+    let escrow = IEAS::new(eas, read_provider)
+        .getAttestation(buy_attestation)
+        .call()
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+    let obligation = EscrowObligation::decode(escrowed, &escrow.data).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "Escrow {} doesn't hold {} escrow data",
+            buy_attestation, escrowed
+        ))
+    })?;
+    let demand = Erc20Module::decode_payment_obligation(&Bytes::copy_from_slice(
+        obligation.demand(),
+    ))
+    .map_err(map_eyre_to_pyerr)?;
+    Ok(demand.token)
 }
 
 #[pymethods]
 impl Erc20Client {
+    #[pyo3(signature = (token, purpose, gas_multiplier=None))]
     pub fn approve<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: Erc20Data,
         purpose: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let (purpose, spender) = match purpose.as_str() {
+                "payment" => (alkahest_rs::types::ApprovalPurpose::Payment, payment),
+                "escrow" => (alkahest_rs::types::ApprovalPurpose::Escrow, escrow),
                 _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
             };
+            let token: alkahest_rs::types::Erc20Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let owner = nonce_manager.signer();
+
+            check_erc20_approve(&read_provider, &token, owner, spender).await?;
             let receipt = inner
-                .approve(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
+                .approve(&token, purpose)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
+            confirm_erc20_allowance(
+                &wallet_provider,
+                &token,
+                owner,
+                spender,
+                receipt.transaction_hash,
+            )
+            .await?;
 
             Ok(receipt.transaction_hash.to_string())
         })
     }
 
+    #[pyo3(signature = (token, purpose, gas_multiplier=None))]
     pub fn approve_if_less<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: Erc20Data,
         purpose: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let (purpose, spender) = match purpose.as_str() {
+                "payment" => (alkahest_rs::types::ApprovalPurpose::Payment, payment),
+                "escrow" => (alkahest_rs::types::ApprovalPurpose::Escrow, escrow),
                 _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
             };
+            let token: alkahest_rs::types::Erc20Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let owner = nonce_manager.signer();
+
+            // Only an approval that will actually be sent needs simulating
+            let allowance = IERC20::new(token.address, &read_provider)
+                .allowance(owner, spender)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            if allowance < token.value {
+                check_erc20_approve(&read_provider, &token, owner, spender).await?;
+            }
+
             let receipt = inner
-                .approve_if_less(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
+                .approve_if_less(&token, purpose)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
+            if let Some(receipt) = &receipt {
+                confirm_erc20_allowance(
+                    &wallet_provider,
+                    &token,
+                    owner,
+                    spender,
+                    receipt.transaction_hash,
+                )
+                .await?;
+            }
 
             Ok(receipt.map(|x| x.transaction_hash.to_string()))
         })
     }
 
+    /// Set the signer's allowance of `token` to the escrow or payment obligation
+    /// contract (by `purpose`) back to zero, returning the transaction hash
+    #[pyo3(signature = (token, purpose, gas_multiplier=None))]
+    pub fn revoke_approval<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: String,
+        purpose: String,
+        gas_multiplier: Option<f64>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let inner = self.inner.clone();
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let (purpose, spender) = match purpose.as_str() {
+                "payment" => (alkahest_rs::types::ApprovalPurpose::Payment, payment),
+                "escrow" => (alkahest_rs::types::ApprovalPurpose::Escrow, escrow),
+                _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
+            };
+            let token = alkahest_rs::types::Erc20Data {
+                address: token.parse().map_err(map_parse_to_pyerr)?,
+                value: alloy::primitives::U256::ZERO,
+            };
+            let owner = nonce_manager.signer();
+
+            check_erc20_approve(&read_provider, &token, owner, spender).await?;
+            let receipt = inner
+                .approve(&token, purpose)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+
+            let allowance = IERC20::new(token.address, &wallet_provider)
+                .allowance(owner, spender)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            if !allowance.is_zero() {
+                return Err(crate::error_handling::TransactionRevertError::new_err(format!(
+                    "Revocation transaction {} mined but token {:?} still reports an allowance of {}",
+                    receipt.transaction_hash, token.address, allowance
+                )));
+            }
+
+            Ok(receipt.transaction_hash.to_string())
+        })
+    }
+
+    /// Read an address's balance of a token as a decimal string, defaulting to the
+    /// signer's own balance
+    #[pyo3(signature = (token, owner=None))]
+    pub fn balance_of<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: String,
+        owner: Option<String>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let signer = self.nonce_manager.own_address();
+        let read_provider = self.read_provider.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let token: Address = token.parse().map_err(map_parse_to_pyerr)?;
+            let owner: Address = match owner {
+                Some(owner) => owner.parse().map_err(map_parse_to_pyerr)?,
+                None => signer?,
+            };
+            let balance = IERC20::new(token, &read_provider)
+                .balanceOf(owner)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(balance.to_string())
+        })
+    }
+
+    /// Read the signer's allowances to the escrow and payment obligation contracts
+    /// for a token, as decimal strings
+    pub fn approval_status<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let owner = self.nonce_manager.own_address()?;
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let read_provider = self.read_provider.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let token: Address = token.parse().map_err(map_parse_to_pyerr)?;
+            let token = IERC20::new(token, &read_provider);
+            let escrow_allowance = token.allowance(owner, escrow);
+            let payment_allowance = token.allowance(owner, payment);
+            let (escrow, payment) =
+                tokio::try_join!(escrow_allowance.call(), payment_allowance.call())
+                    .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(Erc20ApprovalStatus {
+                escrow: escrow.to_string(),
+                payment: payment.to_string(),
+            })
+        })
+    }
+
+    /// Read the signer's allowances to the escrow and payment obligation contracts
+    /// for each of `tokens`, keyed by token address
+    ///
+    /// Meant for periodic reviews of outstanding approvals; stale ones can be
+    /// cleared with `revoke_approval`.
+    pub fn audit_allowances<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        tokens: Vec<String>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let owner = self.nonce_manager.own_address()?;
+        let escrow = self.inner.addresses.escrow_obligation;
+        let payment = self.inner.addresses.payment_obligation;
+        let read_provider = self.read_provider.clone();
+        let tokens = tokens
+            .iter()
+            .map(|token| token.parse::<Address>().map_err(map_parse_to_pyerr))
+            .collect::<PyResult<Vec<_>>>()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let statuses = futures::future::try_join_all(tokens.iter().map(|token| {
+                let token = IERC20::new(*token, &read_provider);
+                async move {
+                    let escrow_allowance = token.allowance(owner, escrow);
+                    let payment_allowance = token.allowance(owner, payment);
+                    tokio::try_join!(escrow_allowance.call(), payment_allowance.call())
+                }
+            }))
+            .await
+            .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+
+            Ok(tokens
+                .iter()
+                .zip(statuses)
+                .map(|(token, (escrow, payment))| {
+                    (
+                        format!("{:?}", token),
+                        Erc20ApprovalStatus {
+                            escrow: escrow.to_string(),
+                            payment: payment.to_string(),
+                        },
+                    )
+                })
+                .collect::<std::collections::HashMap<_, _>>())
+        })
+    }
+
+    /// Estimate the gas of escrowing `price` with `buy_with_erc20` after a separate
+    /// `approve`, versus `permit_and_buy_with_erc20`
+    ///
+    /// Returns a dict with `approve_path` and `permit_path` gas totals, the latter
+    /// None if the token doesn't support EIP-2612 permits, and `cheaper` naming
+    /// the path estimated to cost less. Nothing is sent: both paths are simulated
+    /// with `eth_simulateV1`, and the permit path is an approximation. Signing the
+    /// permit needs a client created from a private key, and the signer must hold
+    /// `price` for the escrow to simulate.
+    pub fn compare_permit_cost<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        price: Erc20Data,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let signer = self.signer.clone().ok_or_else(|| {
+            PyValueError::new_err(
+                "Estimating the permit path needs a client created from a private key",
+            )
+        })?;
+        let escrow = self.inner.addresses.escrow_obligation;
+        let read_provider = self.read_provider.clone();
+        let price: alkahest_rs::types::Erc20Data =
+            price.try_into().map_err(map_eyre_to_pyerr)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            compare_permit_cost(&read_provider, &signer, escrow, price)
+                .await
+                .map_err(map_eyre_to_pyerr)
+        })
+    }
+
+    #[pyo3(signature = (buy_attestation, fulfillment, gas_multiplier=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .collect_escrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
@@ -86,13 +452,18 @@ impl Erc20Client {
         })
     }
 
+    #[pyo3(signature = (buy_attestation, gas_multiplier=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
                 .await
@@ -101,124 +472,238 @@ impl Erc20Client {
         })
     }
 
+    /// Withdraw an unfulfilled escrow before its expiration
+    ///
+    /// Always raises `NotImplementedError`: the escrow obligation contracts have no
+    /// early cancellation, so escrowed tokens can only be returned to the depositor
+    /// with `reclaim_expired` after the escrow expires.
+    pub fn cancel_escrow<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let _: FixedBytes<32> = buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            Err::<String, _>(cancel_escrow_unsupported(&buy_attestation))
+        })
+    }
+
+    /// Escrow ERC20 tokens until `item` is fulfilled
+    ///
+    /// `item` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    ///
+    /// `value_wei` sends native value along with the escrow, for obligation
+    /// contracts that charge a native fee. Contracts that don't accept value reject
+    /// it, which is reported as a `ValueError` before anything is sent.
+    #[pyo3(signature = (price, item, expiration, include_logs=false, force=false, strict=false, value_wei=None, gas_multiplier=None))]
     pub fn buy_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
-        item: ArbiterData,
+        item: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        value_wei: Option<String>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let item = self.default_arbiter.resolve(item)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_erc20(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let value_wei = parse_value_wei(value_wei)?;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &price, nonce_manager.signer()).await?;
+            }
+            let price: alkahest_rs::types::Erc20Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let receipt = if value_wei.is_zero() {
+                inner
+                    .buy_with_erc20(&price, &item, expiration)
+                    .await
                     .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            } else {
+                let escrow = ERC20EscrowObligation::new(
+                    inner.addresses.escrow_obligation,
+                    &wallet_provider,
+                );
+                let data = ERC20EscrowObligation::ObligationData {
+                    token: price.address,
+                    amount: price.value,
+                    arbiter: item.arbiter,
+                    demand: item.demand,
+                };
+                send_with_value(escrow.doObligation(data, expiration), value_wei).await?
+            };
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Escrow `price` for `item`, approving it with a signed permit instead of an
+    /// approval transaction
+    ///
+    /// `item` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    ///
+    /// `permit_type` is the token's permit format: "eip2612" or "dai" (the
+    /// amount-less permit of DAI and some older tokens). When None it is detected
+    /// from the token's `PERMIT_TYPEHASH`. Obligation contracts only accept EIP-2612
+    /// permits, so a DAI-style permit is sent as its own transaction first and the
+    /// escrow is then made without a permit.
+    ///
+    /// A DAI-style permit can't be limited to `price`: it grants an unlimited
+    /// allowance, so it's only signed when `dai_expiry` is given. That's the unix
+    /// timestamp the allowance lapses at, or 0 for one that never expires.
+    #[pyo3(signature = (price, item, expiration, include_logs=false, force=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_buy_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
-        item: ArbiterData,
+        item: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let item = self.default_arbiter.resolve(item)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &price, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
             let price: alkahest_rs::types::Erc20Data =
                 price.try_into().map_err(map_eyre_to_pyerr)?;
-            let item: alkahest_rs::types::ArbiterData =
-                item.try_into().map_err(map_eyre_to_pyerr)?;
 
-            match inner
-                .permit_and_buy_with_erc20(&price, &item, expiration)
-                .await
-            {
-                Ok(receipt) => Ok(LogWithHash::<AttestedLog> {
-                    log: get_attested_event(receipt.clone())
-                        .map_err(map_eyre_to_pyerr)?
-                        .data
-                        .into(),
-                    transaction_hash: receipt.transaction_hash.to_string(),
-                }),
-                Err(e) => {
-                    Err(map_eyre_to_pyerr(e))
-                }
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                price.address,
+                inner.addresses.escrow_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.buy_with_erc20(&price, &item, expiration).await
+            } else {
+                inner.permit_and_buy_with_erc20(&price, &item, expiration).await
             }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Pay `price` to `payee`, or to the signer's own address if `payee` is None
+    #[pyo3(signature = (price, payee=None, include_logs=false, gas_multiplier=None))]
     pub fn pay_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
-        payee: String,
+        payee: Option<String>,
+        include_logs: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .pay_with_erc20(
                     &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
+                    payee_or_signer(payee, nonce_manager.signer())?,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Pay `price` to `payee` (the signer if None), approving it with a signed
+    /// permit; `permit_type` works as in `permit_and_buy_with_erc20`
+    #[pyo3(signature = (price, payee=None, include_logs=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_pay_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
-        payee: String,
+        payee: Option<String>,
+        include_logs: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_with_erc20(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let price: alkahest_rs::types::Erc20Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let payee = payee_or_signer(payee, nonce_manager.signer())?;
+
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                price.address,
+                inner.addresses.payment_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.pay_with_erc20(&price, payee).await
+            } else {
+                inner.permit_and_pay_with_erc20(&price, payee).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, gas_multiplier=None))]
     pub fn buy_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc20Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc20_for_erc20(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -227,96 +712,152 @@ impl Erc20Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` works as in `permit_and_buy_with_erc20`
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_buy_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc20Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc20_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc20Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                bid.address,
+                inner.addresses.escrow_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.buy_erc20_for_erc20(&bid, &ask, expiration).await
+            } else {
+                inner.permit_and_buy_erc20_for_erc20(&bid, &ask, expiration).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc20");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc20_for_erc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc20_for_erc20(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` and `dai_expiry` work as in `permit_and_buy_with_erc20`, for the
+    /// token the escrow's demand asks to be paid in
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_pay_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc20(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc20");
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let eas = self.inner.addresses.eas;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let token =
+                demanded_erc20_token(&read_provider, eas, buy_attestation, "erc20").await?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                token,
+                payment_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.pay_erc20_for_erc20(buy_attestation).await
+            } else {
+                inner.permit_and_pay_erc20_for_erc20(buy_attestation).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, gas_multiplier=None))]
     pub fn buy_erc721_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc721Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc721_for_erc20(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -325,96 +866,152 @@ impl Erc20Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` works as in `permit_and_buy_with_erc20`
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_buy_erc721_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc721Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc721_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc721Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                bid.address,
+                inner.addresses.escrow_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.buy_erc721_for_erc20(&bid, &ask, expiration).await
+            } else {
+                inner.permit_and_buy_erc721_for_erc20(&bid, &ask, expiration).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc20_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc721");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc20_for_erc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc20_for_erc721(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` and `dai_expiry` work as in `permit_and_buy_with_erc20`, for the
+    /// token the escrow's demand asks to be paid in
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_pay_erc20_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc721(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc721");
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let eas = self.inner.addresses.eas;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let token =
+                demanded_erc20_token(&read_provider, eas, buy_attestation, "erc721").await?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                token,
+                payment_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.pay_erc20_for_erc721(buy_attestation).await
+            } else {
+                inner.permit_and_pay_erc20_for_erc721(buy_attestation).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, gas_multiplier=None))]
     pub fn buy_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc1155Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_erc1155_for_erc20(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -423,97 +1020,152 @@ impl Erc20Client {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` works as in `permit_and_buy_with_erc20`
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_buy_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc1155Data,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc1155_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc1155Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                bid.address,
+                inner.addresses.escrow_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.buy_erc1155_for_erc20(&bid, &ask, expiration).await
+            } else {
+                inner.permit_and_buy_erc1155_for_erc20(&bid, &ask, expiration).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc20_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc1155");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc20_for_erc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc20_for_erc1155(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` and `dai_expiry` work as in `permit_and_buy_with_erc20`, for the
+    /// token the escrow's demand asks to be paid in
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_pay_erc20_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc1155(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "erc1155");
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let eas = self.inner.addresses.eas;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let token =
+                demanded_erc20_token(&read_provider, eas, buy_attestation, "erc1155").await?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                token,
+                payment_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.pay_erc20_for_erc1155(buy_attestation).await
+            } else {
+                inner.permit_and_pay_erc20_for_erc1155(buy_attestation).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, gas_multiplier=None))]
     pub fn buy_bundle_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: TokenBundleData,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_bundle_for_erc20(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -523,84 +1175,128 @@ impl Erc20Client {
                 .await
                 .map_err(map_eyre_to_pyerr)?;
 
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` works as in `permit_and_buy_with_erc20`
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_buy_bundle_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: TokenBundleData,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_bundle_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            if strict {
+                check_erc20_balance(&read_provider, &bid, nonce_manager.signer()).await?;
+            }
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                bid.address,
+                inner.addresses.escrow_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.buy_bundle_for_erc20(&bid, &ask, expiration).await
+            } else {
+                inner.permit_and_buy_bundle_for_erc20(&bid, &ask, expiration).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_erc20_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "token_bundle");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_erc20_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_erc20_for_bundle(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// `permit_type` and `dai_expiry` work as in `permit_and_buy_with_erc20`, for the
+    /// token the escrow's demand asks to be paid in
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, permit_type=None, dai_expiry=None, gas_multiplier=None))]
     pub fn permit_and_pay_erc20_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        permit_type: Option<PermitType>,
+        dai_expiry: Option<u64>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_bundle(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "token_bundle");
+        let wallet_provider = self.wallet_provider.clone();
+        let read_provider = self.read_provider.clone();
+        let signer = self.signer.clone();
+        let eas = self.inner.addresses.eas;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let token =
+                demanded_erc20_token(&read_provider, eas, buy_attestation, "token_bundle").await?;
+            let dai = send_permit_if_dai(
+                &wallet_provider,
+                &read_provider,
+                signer.as_ref(),
+                token,
+                payment_obligation,
+                permit_type,
+                dai_expiry,
+            )
+            .await?;
+            let receipt = if dai {
+                inner.pay_erc20_for_bundle(buy_attestation).await
+            } else {
+                inner.permit_and_pay_erc20_for_bundle(buy_attestation).await
+            }
+            .map_err(map_eyre_to_pyerr)?;
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 }
@@ -608,13 +1304,13 @@ impl Erc20Client {
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC20EscrowObligationData {
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub amount: u64,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub arbiter: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub demand: Vec<u8>,
 }
 
@@ -636,14 +1332,53 @@ impl PyERC20EscrowObligationData {
             self.token, self.amount, self.arbiter, self.demand
         )
     }
+
+    /// The amount in whole tokens, as a fixed-point string with exactly `decimals`
+    /// fractional digits that `decimal.Decimal` parses without rounding
+    pub fn amount_decimal(&self, decimals: u8) -> String {
+        crate::types::format_units(alloy::primitives::U256::from(self.amount), decimals)
+    }
+
+    /// Convert to a dict, rendering byte fields as `0x`-hex (the default), base64
+    /// or raw `bytes` according to `bytes_encoding`
+    #[pyo3(signature = (bytes_encoding=BytesEncoding::Hex))]
+    pub fn to_dict<'py>(
+        &self,
+        py: Python<'py>,
+        bytes_encoding: BytesEncoding,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("token", &self.token)?;
+        dict.set_item("amount", self.amount)?;
+        dict.set_item("arbiter", &self.arbiter)?;
+        dict.set_item("demand", bytes_encoding.encode(py, &self.demand)?)?;
+        Ok(dict)
+    }
     #[staticmethod]
     pub fn decode(obligation_data: Vec<u8>) -> eyre::Result<PyERC20EscrowObligationData> {
-        use alloy::primitives::Bytes;
         let bytes = Bytes::from(obligation_data);
         let decoded = alkahest_rs::extensions::Erc20Module::decode_escrow_obligation(&bytes)?;
         Ok(decoded.into())
     }
 
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyERC20EscrowObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyERC20EscrowObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?).map_err(map_eyre_to_pyerr)
+    }
+
     #[staticmethod]
     pub fn encode(obligation: &PyERC20EscrowObligationData) -> eyre::Result<Vec<u8>> {
         use alkahest_rs::contracts::ERC20EscrowObligation;
@@ -652,9 +1387,9 @@ impl PyERC20EscrowObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse()?;
+        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
         let amount: U256 = U256::from(obligation.amount);
-        let arbiter: Address = obligation.arbiter.parse()?;
+        let arbiter: Address = obligation.arbiter.parse().map_err(map_parse_to_pyerr)?;
         let demand = Bytes::from(obligation.demand.clone());
 
         let obligation_data = ERC20EscrowObligation::ObligationData {
@@ -670,6 +1405,32 @@ impl PyERC20EscrowObligationData {
     pub fn encode_self(&self) -> eyre::Result<Vec<u8>> {
         PyERC20EscrowObligationData::encode(self)
     }
+
+    /// Decode the demand as the payment this escrow asks for, using the
+    /// arbiter to pick the payment type. `addresses` defaults to the
+    /// alkahest-rs default deployment.
+    #[pyo3(signature = (addresses=None))]
+    pub fn decode_payment_demand(
+        &self,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<PaymentDemand> {
+        let addresses = resolve_addresses(addresses)?;
+        decode_payment_demand(&self.arbiter, &self.demand, &addresses)
+    }
+
+    /// Decode the demand according to the arbiter: as payment data for the
+    /// payment arbiters, with the decoder registered through
+    /// `register_demand_decoder` for custom arbiters, and otherwise as raw bytes.
+    /// `addresses` defaults to the alkahest-rs default deployment.
+    #[pyo3(signature = (addresses=None))]
+    pub fn decode_demand(
+        &self,
+        py: Python<'_>,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<Demand> {
+        let addresses = resolve_addresses(addresses)?;
+        decode_demand(py, &self.arbiter, &self.demand, &addresses)
+    }
 }
 
 impl From<alkahest_rs::contracts::ERC20EscrowObligation::ObligationData>
@@ -688,11 +1449,11 @@ impl From<alkahest_rs::contracts::ERC20EscrowObligation::ObligationData>
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC20PaymentObligationData {
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub token: String,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub amount: u64,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub payee: String,
 }
 
@@ -714,6 +1475,12 @@ impl PyERC20PaymentObligationData {
         )
     }
 
+    /// The amount in whole tokens, as a fixed-point string with exactly `decimals`
+    /// fractional digits that `decimal.Decimal` parses without rounding
+    pub fn amount_decimal(&self, decimals: u8) -> String {
+        crate::types::format_units(alloy::primitives::U256::from(self.amount), decimals)
+    }
+
     #[staticmethod]
     pub fn encode(obligation: &PyERC20PaymentObligationData) -> eyre::Result<Vec<u8>> {
         use alkahest_rs::contracts::ERC20PaymentObligation;
@@ -722,7 +1489,7 @@ impl PyERC20PaymentObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse()?;
+        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
         let amount: U256 = U256::from(obligation.amount);
         let payee: Address = obligation.payee.parse().map_err(map_parse_to_pyerr)?;
 
@@ -737,12 +1504,29 @@ impl PyERC20PaymentObligationData {
 
     #[staticmethod]
     pub fn decode(obligation_data: Vec<u8>) -> eyre::Result<PyERC20PaymentObligationData> {
-        use alloy::primitives::Bytes;
         let bytes = Bytes::from(obligation_data);
         let decoded = alkahest_rs::extensions::Erc20Module::decode_payment_obligation(&bytes)?;
         Ok(decoded.into())
     }
 
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyERC20PaymentObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyERC20PaymentObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?).map_err(map_eyre_to_pyerr)
+    }
+
     pub fn encode_self(&self) -> eyre::Result<Vec<u8>> {
         PyERC20PaymentObligationData::encode(self)
     }
@@ -1,16 +1,29 @@
 use alkahest_rs::{
     extensions::OracleModule as InnerOracleClient,
-    contracts::StringObligation,
+    contracts::{StringObligation, IEAS},
+    types::WalletProvider,
+};
+use alloy::{
+    primitives::FixedBytes,
+    providers::DynProvider,
+    rpc::types::Filter,
+    sol_types::SolEvent,
+};
+use pyo3::{
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDict},
+    Bound, PyAny, PyObject, PyResult, Python,
 };
-use alloy::primitives::FixedBytes;
-use pyo3::{pyclass, pymethods, types::PyAnyMethods, PyAny, PyObject, PyResult, Python};
 use pyo3_async_runtimes::tokio::{future_into_py, into_future};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
 use crate::{
+    encoding::BytesEncoding,
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
+    logs::get_logs_windowed,
+    nonce_manager::NonceManager,
 };
 use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
 
@@ -18,11 +31,26 @@ use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
 #[derive(Clone)]
 pub struct OracleClient {
     inner: InnerOracleClient,
+    nonce_manager: NonceManager,
+    wallet_provider: WalletProvider,
+    // Serves the binding's own reads and pre-checks; the wallet provider when
+    // no separate read RPC was configured
+    read_provider: DynProvider,
 }
 
 impl OracleClient {
-    pub fn new(inner: InnerOracleClient) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: InnerOracleClient,
+        nonce_manager: NonceManager,
+        wallet_provider: WalletProvider,
+        read_provider: DynProvider,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            wallet_provider,
+            read_provider,
+        }
     }
 }
 
@@ -43,7 +71,9 @@ impl OracleClient {
         oracle: String,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
         let inner = self.inner.clone();
+        let nonce_manager = self.nonce_manager.clone();
         future_into_py(py, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let uid: FixedBytes<32> = obligation_uid.parse().map_err(map_parse_to_pyerr)?;
             let oracle_addr = oracle.parse().map_err(map_parse_to_pyerr)?;
 
@@ -91,6 +121,58 @@ impl OracleClient {
         Ok(PyTrustedOracleArbiterDemandData::from(demand_data))
     }
 
+    /// Get the decisions this client's signer has already submitted to the trusted
+    /// oracle arbiter, oldest first
+    ///
+    /// Read from the arbiter's `ArbitrationMade` events, so a restarted oracle can
+    /// tell which obligations it has decided without keeping its own state. Each
+    /// decision carries the obligation attestation as it is now and the hash of the
+    /// transaction that submitted the decision. The events are filtered by oracle on
+    /// the node and fetched in fixed block windows, so each request stays within
+    /// providers' block range limits; pass `from_block`, such as the arbiter's
+    /// deployment block, to skip the windows before it rather than scanning from
+    /// genesis, the default.
+    #[pyo3(signature = (from_block=None))]
+    pub fn my_arbitration_decisions<'py>(
+        &self,
+        py: Python<'py>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let addresses = self.inner.addresses.clone();
+        let oracle = self.nonce_manager.own_address()?;
+        let read_provider = self.read_provider.clone();
+        future_into_py(py, async move {
+            let filter = Filter::new()
+                .address(addresses.trusted_oracle_arbiter)
+                .event_signature(TrustedOracleArbiter::ArbitrationMade::SIGNATURE_HASH)
+                .topic2(oracle.into_word());
+            let logs = get_logs_windowed(&read_provider, &filter, from_block.unwrap_or(0))
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+
+            let eas = IEAS::new(addresses.eas, &read_provider);
+            let mut decisions = Vec::new();
+            for log in logs {
+                let event = log
+                    .log_decode::<TrustedOracleArbiter::ArbitrationMade>()
+                    .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+                let attestation = eas
+                    .getAttestation(event.inner.data.obligation)
+                    .call()
+                    .await
+                    .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+                decisions.push(PyDecision::__new__(
+                    PyOracleAttestation::from(&attestation),
+                    event.inner.data.decision,
+                    log.transaction_hash
+                        .map(|hash| hash.to_string())
+                        .unwrap_or_default(),
+                ));
+            }
+            Ok(decisions)
+        })
+    }
+
     pub fn unsubscribe<'py>(
         &self,
         py: Python<'py>,
@@ -114,7 +196,9 @@ impl OracleClient {
         options: Option<PyArbitrateOptions>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
         let inner = self.inner.clone();
+        let nonce_manager = self.nonce_manager.clone();
         future_into_py(py, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let opts = options.unwrap_or_default();
 
             let arbitrate_options = alkahest_rs::clients::oracle::ArbitrateOptions {
@@ -189,6 +273,9 @@ impl OracleClient {
         options: Option<PyArbitrateOptions>,
         timeout_seconds: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        // Listeners aren't sequenced, but they still submit decisions
+        self.nonce_manager.ensure_writable()?;
+
         // Check if decision_func is async
         let is_async = Python::with_gil(|py| {
             let inspect = py.import("inspect").ok()?;
@@ -481,6 +568,10 @@ impl Default for PyArbitrateOptions {
     }
 }
 
+/// An EAS attestation as the oracle client hands it out
+///
+/// Has the same fields as `Attestation`, except that `data` is a `0x`-hex string
+/// rather than `bytes`.
 #[pyclass]
 #[derive(Clone)]
 pub struct PyOracleAttestation {
@@ -502,6 +593,7 @@ pub struct PyOracleAttestation {
     pub attester: String,
     #[pyo3(get)]
     pub revocable: bool,
+    /// The attestation data, as `0x`-hex
     #[pyo3(get)]
     pub data: String,
 }
@@ -523,7 +615,7 @@ impl PyOracleAttestation {
     ) -> Self {
         Self {
             uid,
-            schema,
+            schema: crate::contract::normalize_schema_field(schema),
             ref_uid,
             time,
             expiration_time,
@@ -535,6 +627,18 @@ impl PyOracleAttestation {
         }
     }
 
+    /// The attestation data as `bytes`, the form `Attestation.data` uses
+    #[getter]
+    pub fn data_bytes(&self) -> PyResult<Vec<u8>> {
+        crate::encoding::decode_hex(&self.data)
+    }
+
+    /// The attestation data as lowercase `0x`-hex, normalized from `data`
+    #[getter]
+    pub fn data_hex(&self) -> PyResult<String> {
+        Ok(format!("0x{}", alloy::hex::encode(self.data_bytes()?)))
+    }
+
     pub fn __str__(&self) -> String {
         format!(
             "PyOracleAttestation(uid={}, schema={}, attester={}, recipient={})",
@@ -545,13 +649,40 @@ impl PyOracleAttestation {
     pub fn __repr__(&self) -> String {
         self.__str__()
     }
+
+    /// Convert to a dict, rendering byte fields as `0x`-hex (the default), base64
+    /// or raw `bytes` according to `bytes_encoding`
+    #[pyo3(signature = (bytes_encoding=BytesEncoding::Hex))]
+    pub fn to_dict<'py>(
+        &self,
+        py: Python<'py>,
+        bytes_encoding: BytesEncoding,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("uid", &self.uid)?;
+        dict.set_item("schema", &self.schema)?;
+        dict.set_item("ref_uid", &self.ref_uid)?;
+        dict.set_item("time", self.time)?;
+        dict.set_item("expiration_time", self.expiration_time)?;
+        dict.set_item("revocation_time", self.revocation_time)?;
+        dict.set_item("recipient", &self.recipient)?;
+        dict.set_item("attester", &self.attester)?;
+        dict.set_item("revocable", self.revocable)?;
+        dict.set_item("data", bytes_encoding.encode_hex(py, &self.data)?)?;
+        Ok(dict)
+    }
+
+    /// Convert to the `Attestation` type returned by `get_attestation`
+    pub fn to_attestation(&self) -> PyResult<crate::contract::PyAttestation> {
+        self.clone().try_into().map_err(map_parse_to_pyerr)
+    }
 }
 
 impl From<&alkahest_rs::contracts::IEAS::Attestation> for PyOracleAttestation {
     fn from(att: &alkahest_rs::contracts::IEAS::Attestation) -> Self {
         Self::__new__(
             format!("0x{}", alloy::hex::encode(att.uid.as_slice())),
-            format!("0x{}", alloy::hex::encode(att.schema.as_slice())),
+            att.schema.to_string(),
             format!("0x{}", alloy::hex::encode(att.refUID.as_slice())),
             att.time,
             att.expirationTime,
@@ -603,6 +734,16 @@ impl PyDecision {
         )
     }
 
+    /// Serialize the decision as a single-line JSON object for audit logs
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "attestation_uid": self.attestation.uid,
+            "decision": self.decision,
+            "transaction_hash": self.transaction_hash,
+        })
+        .to_string()
+    }
+
     pub fn __repr__(&self) -> String {
         self.__str__()
     }
@@ -635,6 +776,15 @@ impl PyListenResult {
         )
     }
 
+    /// Serialize the decisions as JSON Lines, one `PyDecision.to_json` object per
+    /// line, ready to append to a decision log
+    pub fn to_jsonl(&self) -> String {
+        self.decisions
+            .iter()
+            .map(|decision| format!("{}\n", decision.to_json()))
+            .collect()
+    }
+
     pub fn __repr__(&self) -> String {
         self.__str__()
     }
@@ -664,6 +814,20 @@ impl PyTrustedOracleArbiterDemandData {
         )
     }
 
+    /// Convert to a dict, rendering byte fields as `0x`-hex (the default), base64
+    /// or raw `bytes` according to `bytes_encoding`
+    #[pyo3(signature = (bytes_encoding=BytesEncoding::Hex))]
+    pub fn to_dict<'py>(
+        &self,
+        py: Python<'py>,
+        bytes_encoding: BytesEncoding,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("oracle", &self.oracle)?;
+        dict.set_item("data", bytes_encoding.encode(py, &self.data)?)?;
+        Ok(dict)
+    }
+
     #[staticmethod]
     pub fn decode(demand_bytes: Vec<u8>) -> eyre::Result<PyTrustedOracleArbiterDemandData> {
         use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
@@ -675,6 +839,12 @@ impl PyTrustedOracleArbiterDemandData {
         Ok(decoded.into())
     }
 
+    /// Decode demand given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(demand_bytes: String) -> PyResult<PyTrustedOracleArbiterDemandData> {
+        Self::decode(crate::encoding::decode_hex(&demand_bytes)?).map_err(map_eyre_to_pyerr)
+    }
+
     #[staticmethod]
     pub fn encode(demand_data: &PyTrustedOracleArbiterDemandData) -> eyre::Result<Vec<u8>> {
         use alkahest_rs::extensions::ArbitersModule;
@@ -1,11 +1,14 @@
 use alkahest_rs::extensions::StringObligationModule;
 use alloy::primitives::FixedBytes;
 use pyo3::prelude::PyAnyMethods;
-use pyo3::{pyclass, pymethods, types::PyAny, Bound, PyResult};
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, types::PyAny, Bound, PyResult, Python};
 
 use crate::{
     contract::PyDecodedAttestation,
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, map_serde_to_pyerr},
+    gas, json_schema,
+    nonce_manager::NonceManager,
+    types::GasMultiplier,
 };
 
 // Helper function to convert Python object to JSON string
@@ -20,11 +23,21 @@ fn python_to_json_string(py_obj: &Bound<'_, PyAny>) -> eyre::Result<String> {
 #[derive(Clone)]
 pub struct StringObligationClient {
     inner: StringObligationModule,
+    nonce_manager: NonceManager,
+    gas_multiplier: GasMultiplier,
 }
 
 impl StringObligationClient {
-    pub fn new(inner: StringObligationModule) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: StringObligationModule,
+        nonce_manager: NonceManager,
+        gas_multiplier: GasMultiplier,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            gas_multiplier,
+        }
     }
 }
 
@@ -45,14 +58,19 @@ impl StringObligationClient {
         })
     }
 
+    #[pyo3(signature = (item, ref_uid=None, gas_multiplier=None))]
     pub fn do_obligation<'py>(
         &self,
         py: pyo3::Python<'py>,
         item: String,
         ref_uid: Option<String>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let ref_uid = if let Some(ref_uid_str) = ref_uid {
                 Some(ref_uid_str.parse().map_err(map_parse_to_pyerr)?)
             } else {
@@ -75,15 +93,43 @@ impl StringObligationClient {
         })
     }
 
+    /// Attest to a JSON obligation, optionally validating it against a JSON Schema
+    /// first so malformed payloads are rejected before anything is sent
+    ///
+    /// Only a structural subset of JSON Schema is supported; a schema using other
+    /// keywords, such as `$ref`, `pattern` or `format`, raises ValueError rather than
+    /// being partly enforced.
+    #[pyo3(signature = (json_data, ref_uid=None, schema=None, gas_multiplier=None))]
     pub fn do_obligation_json<'py>(
         &self,
         py: pyo3::Python<'py>,
         json_data: &Bound<'_, PyAny>,
         ref_uid: Option<String>,
+        schema: Option<&Bound<'_, PyAny>>,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let json_string = python_to_json_string(json_data).map_err(map_eyre_to_pyerr)?;
+        if let Some(schema) = schema {
+            let schema_string = python_to_json_string(schema).map_err(map_eyre_to_pyerr)?;
+            let schema: serde_json::Value =
+                serde_json::from_str(&schema_string).map_err(map_serde_to_pyerr)?;
+            json_schema::check_schema(&schema).map_err(|errors| {
+                PyValueError::new_err(format!("Unsupported JSON schema: {}", errors.join("; ")))
+            })?;
+            let json_value: serde_json::Value =
+                serde_json::from_str(&json_string).map_err(map_serde_to_pyerr)?;
+            json_schema::validate(&schema, &json_value).map_err(|errors| {
+                PyValueError::new_err(format!(
+                    "JSON schema validation failed: {}",
+                    errors.join("; ")
+                ))
+            })?;
+        }
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let json_value: serde_json::Value =
                 serde_json::from_str(&json_string).map_err(map_serde_to_pyerr)?;
 
@@ -113,7 +159,7 @@ impl StringObligationClient {
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct PyStringObligationData {
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     pub item: String,
 }
 
@@ -150,6 +196,24 @@ impl PyStringObligationData {
         Ok(decoded.into())
     }
 
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyStringObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyStringObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?)
+    }
+
     #[staticmethod]
     pub fn decode_json(obligation_data: Vec<u8>) -> PyResult<String> {
         use alloy::primitives::Bytes;
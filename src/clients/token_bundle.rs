@@ -1,34 +1,90 @@
-use alkahest_rs::extensions::TokenBundleModule;
-use pyo3::{pyclass, pymethods, PyResult};
+use alkahest_rs::{
+    contracts::{TokenBundleEscrowObligation, IEAS},
+    extensions::TokenBundleModule,
+    types::WalletProvider,
+};
+use alloy::{
+    primitives::{Address, FixedBytes, U256},
+    providers::DynProvider,
+};
+use pyo3::{pyclass, pymethods, IntoPyObject, PyResult, Python};
 
 use crate::{
+    attested_log_with_hash,
+    demand_decoders::{decode_demand, Demand},
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{ArbiterData, AttestedLog, LogWithHash, TokenBundleData},
+    escrow::{
+        cancel_escrow_unsupported, check_expiration, payee_or_signer, resolve_addresses,
+        StrictDemandCheck,
+    },
+    gas,
+    nonce_manager::NonceManager,
+    types::{
+        ArbiterData, DefaultArbiter, DefaultExtensionConfig, GasMultiplier, TokenBundleData,
+    },
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct TokenBundleClient {
     inner: TokenBundleModule,
+    nonce_manager: NonceManager,
+    wallet_provider: WalletProvider,
+    // Serves the binding's own reads and pre-checks; the wallet provider when
+    // no separate read RPC was configured
+    read_provider: DynProvider,
+    default_arbiter: DefaultArbiter,
+    gas_multiplier: GasMultiplier,
 }
 
 impl TokenBundleClient {
-    pub fn new(inner: TokenBundleModule) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: TokenBundleModule,
+        nonce_manager: NonceManager,
+        wallet_provider: WalletProvider,
+        read_provider: DynProvider,
+        default_arbiter: DefaultArbiter,
+        gas_multiplier: GasMultiplier,
+    ) -> Self {
+        Self {
+            inner,
+            nonce_manager,
+            wallet_provider,
+            read_provider,
+            default_arbiter,
+            gas_multiplier,
+        }
+    }
+
+    /// The `strict=True` check of a `pay_token_bundle_for_*` method for an escrow of
+    /// `escrowed` tokens
+    fn strict_demand_check(&self, strict: bool, escrowed: &'static str) -> StrictDemandCheck {
+        StrictDemandCheck::new(
+            self.read_provider.clone(),
+            self.inner.addresses.eas,
+            self.inner.addresses.payment_obligation,
+            escrowed,
+            "token_bundle",
+            strict,
+        )
     }
 }
 
 #[pymethods]
 impl TokenBundleClient {
+    #[pyo3(signature = (token, purpose, gas_multiplier=None))]
     pub fn approve<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: TokenBundleData,
         purpose: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let purpose = match purpose.as_str() {
                 "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
                 "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
@@ -47,14 +103,19 @@ impl TokenBundleClient {
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, gas_multiplier=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .collect_escrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
@@ -66,13 +127,18 @@ impl TokenBundleClient {
         })
     }
 
+    #[pyo3(signature = (buy_attestation, gas_multiplier=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
                 .await
@@ -81,67 +147,136 @@ impl TokenBundleClient {
         })
     }
 
+    /// Withdraw an unfulfilled escrow before its expiration
+    ///
+    /// Always raises `NotImplementedError`: the escrow obligation contracts have no
+    /// early cancellation, so escrowed tokens can only be returned to the depositor
+    /// with `reclaim_expired` after the escrow expires.
+    pub fn cancel_escrow<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let _: FixedBytes<32> = buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            Err::<String, _>(cancel_escrow_unsupported(&buy_attestation))
+        })
+    }
+
+    /// Get every asset held by a token bundle escrow
+    ///
+    /// The result has the same `erc20s`/`erc721s`/`erc1155s` shape as the bundles
+    /// passed to `buy_with_bundle`.
+    pub fn bundle_contents<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let inner = self.inner.clone();
+        let read_provider = self.read_provider.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let uid: FixedBytes<32> = buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            let attestation = IEAS::new(inner.addresses.eas, &read_provider)
+                .getAttestation(uid)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Attestation {} not found",
+                    uid
+                )));
+            }
+            if attestation.attester != inner.addresses.escrow_obligation {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Attestation {} is not a token bundle escrow",
+                    uid
+                )));
+            }
+            let data = PyTokenBundleEscrowObligationData::decode(attestation.data.to_vec())?;
+            Ok(data.contents())
+        })
+    }
+
+    /// Escrow a bundle of tokens until `item` is fulfilled
+    ///
+    /// `item` may be None to use the arbiter set with the client's
+    /// `set_default_arbiter`.
+    #[pyo3(signature = (price, item, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_with_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: TokenBundleData,
-        item: ArbiterData,
+        item: Option<ArbiterData>,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let item = self.default_arbiter.resolve(item)?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_with_bundle(
                     &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
+                    &item,
                     expiration,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Pay `price` to `payee`, or to the signer's own address if `payee` is None
+    #[pyo3(signature = (price, payee=None, include_logs=false, gas_multiplier=None))]
     pub fn pay_with_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: TokenBundleData,
-        payee: String,
+        payee: Option<String>,
+        include_logs: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .pay_with_bundle(
                     &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
+                    payee_or_signer(payee, nonce_manager.signer())?,
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, include_logs=false, force=false, gas_multiplier=None))]
     pub fn buy_bundle_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: TokenBundleData,
         ask: TokenBundleData,
         expiration: u64,
+        include_logs: bool,
+        force: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let read_provider = self.read_provider.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            check_expiration(&read_provider, expiration, force).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
                 .buy_bundle_for_bundle(
                     &bid.try_into().map_err(map_eyre_to_pyerr)?,
@@ -150,34 +285,227 @@ impl TokenBundleClient {
                 )
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, include_logs=false, strict=false, gas_multiplier=None))]
     pub fn pay_bundle_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        include_logs: bool,
+        strict: bool,
+        gas_multiplier: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let nonce_manager = self.nonce_manager.clone();
+        let demand_check = self.strict_demand_check(strict, "token_bundle");
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let buy_attestation = demand_check.buy_attestation(&buy_attestation).await?;
+            let _nonce_guard = nonce_manager.acquire().await?;
             let receipt = inner
-                .pay_bundle_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .pay_bundle_for_bundle(buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
+            attested_log_with_hash(receipt, include_logs).map_err(map_eyre_to_pyerr)
+        })
+    }
+}
+
+/// An ERC20 entry of a token bundle
+#[derive(Clone, IntoPyObject)]
+pub struct BundleErc20 {
+    pub address: String,
+    pub value: u128,
+}
+
+/// An ERC721 entry of a token bundle
+#[derive(Clone, IntoPyObject)]
+pub struct BundleErc721 {
+    pub address: String,
+    pub id: u128,
+}
+
+/// An ERC1155 entry of a token bundle
+#[derive(Clone, IntoPyObject)]
+pub struct BundleErc1155 {
+    pub address: String,
+    pub id: u128,
+    pub value: u128,
+}
+
+/// The assets of a token bundle, in the shape `TokenBundleData` arguments take
+#[derive(Clone, IntoPyObject)]
+pub struct BundleContents {
+    pub erc20s: Vec<BundleErc20>,
+    pub erc721s: Vec<BundleErc721>,
+    pub erc1155s: Vec<BundleErc1155>,
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTokenBundleEscrowObligationData {
+    #[pyo3(get)]
+    pub arbiter: String,
+    #[pyo3(get)]
+    pub demand: Vec<u8>,
+    #[pyo3(get)]
+    pub erc20s: Vec<BundleErc20>,
+    #[pyo3(get)]
+    pub erc721s: Vec<BundleErc721>,
+    #[pyo3(get)]
+    pub erc1155s: Vec<BundleErc1155>,
+}
+
+#[pymethods]
+impl PyTokenBundleEscrowObligationData {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyTokenBundleEscrowObligationData(arbiter='{}', erc20s={}, erc721s={}, erc1155s={}, demand={} bytes)",
+            self.arbiter,
+            self.erc20s.len(),
+            self.erc721s.len(),
+            self.erc1155s.len(),
+            self.demand.len()
+        )
+    }
+
+    #[staticmethod]
+    pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyTokenBundleEscrowObligationData> {
+        use alloy::primitives::Bytes;
+        let bytes = Bytes::from(obligation_data);
+        let decoded = TokenBundleModule::decode_escrow_obligation(&bytes)
+            .map_err(map_eyre_to_pyerr)?;
+        decoded.try_into()
+    }
+
+    /// Decode many obligation datas at once, without holding the GIL
+    ///
+    /// Large batches are spread over a few threads. Fails naming the index of the
+    /// first item that doesn't decode.
+    #[staticmethod]
+    pub fn decode_many(
+        py: Python<'_>,
+        obligation_datas: Vec<Vec<u8>>,
+    ) -> PyResult<Vec<PyTokenBundleEscrowObligationData>> {
+        crate::bulk_decode::decode_many(py, obligation_datas, Self::decode)
+    }
+
+    /// Decode obligation data given as a hex string, such as one copied from a block explorer
+    #[staticmethod]
+    pub fn decode_hex(obligation_data: String) -> PyResult<PyTokenBundleEscrowObligationData> {
+        Self::decode(crate::encoding::decode_hex(&obligation_data)?)
+    }
+
+    /// Every asset in the bundle, without the arbiter and demand
+    pub fn contents(&self) -> BundleContents {
+        BundleContents {
+            erc20s: self.erc20s.clone(),
+            erc721s: self.erc721s.clone(),
+            erc1155s: self.erc1155s.clone(),
+        }
+    }
+
+    /// Total amount of `token` in the bundle: its ERC20 value, its ERC1155 values
+    /// summed across token IDs, or for an ERC721 collection the number of its
+    /// tokens. Zero if the bundle doesn't hold it.
+    pub fn total_value_of(&self, token: String) -> PyResult<u128> {
+        let token: Address = token.parse().map_err(map_parse_to_pyerr)?;
+        let holds = |address: &str| address.parse::<Address>().is_ok_and(|a| a == token);
+        let values = self
+            .erc20s
+            .iter()
+            .filter(|erc20| holds(&erc20.address))
+            .map(|erc20| erc20.value)
+            .chain(self.erc721s.iter().filter(|erc721| holds(&erc721.address)).map(|_| 1))
+            .chain(
+                self.erc1155s
+                    .iter()
+                    .filter(|erc1155| holds(&erc1155.address))
+                    .map(|erc1155| erc1155.value),
+            );
+        values
+            .try_fold(0u128, u128::checked_add)
+            .ok_or_else(|| {
+                pyo3::exceptions::PyOverflowError::new_err(format!(
+                    "Total of {:?} in the bundle does not fit in 128 bits",
+                    token
+                ))
+            })
+    }
+
+    /// Decode the demand according to the arbiter: as payment data for the
+    /// payment arbiters, with the decoder registered through
+    /// `register_demand_decoder` for custom arbiters, and otherwise as raw bytes.
+    /// `addresses` defaults to the alkahest-rs default deployment.
+    #[pyo3(signature = (addresses=None))]
+    pub fn decode_demand(
+        &self,
+        py: Python<'_>,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<Demand> {
+        let addresses = resolve_addresses(addresses)?;
+        decode_demand(py, &self.arbiter, &self.demand, &addresses)
+    }
+}
+
+impl TryFrom<TokenBundleEscrowObligation::ObligationData> for PyTokenBundleEscrowObligationData {
+    type Error = pyo3::PyErr;
+
+    fn try_from(data: TokenBundleEscrowObligation::ObligationData) -> PyResult<Self> {
+        let amount = |value: U256| -> PyResult<u128> {
+            value.try_into().map_err(|_| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Bundle amount {} does not fit in 128 bits",
+                    value
+                ))
             })
+        };
+
+        let erc20s = data
+            .erc20Tokens
+            .iter()
+            .zip(&data.erc20Amounts)
+            .map(|(token, value)| {
+                Ok(BundleErc20 {
+                    address: format!("{:?}", token),
+                    value: amount(*value)?,
+                })
+            })
+            .collect::<PyResult<_>>()?;
+        let erc721s = data
+            .erc721Tokens
+            .iter()
+            .zip(&data.erc721TokenIds)
+            .map(|(token, id)| {
+                Ok(BundleErc721 {
+                    address: format!("{:?}", token),
+                    id: amount(*id)?,
+                })
+            })
+            .collect::<PyResult<_>>()?;
+        let erc1155s = data
+            .erc1155Tokens
+            .iter()
+            .zip(&data.erc1155TokenIds)
+            .zip(&data.erc1155Amounts)
+            .map(|((token, id), value)| {
+                Ok(BundleErc1155 {
+                    address: format!("{:?}", token),
+                    id: amount(*id)?,
+                    value: amount(*value)?,
+                })
+            })
+            .collect::<PyResult<_>>()?;
+
+        Ok(Self {
+            arbiter: format!("{:?}", data.arbiter),
+            demand: data.demand.to_vec(),
+            erc20s,
+            erc721s,
+            erc1155s,
         })
     }
 }
@@ -1,8 +1,67 @@
-use pyo3::{pyclass, pymethods, IntoPyObject};
+use alloy::primitives::{keccak256, Address, FixedBytes};
+use pyo3::{
+    exceptions::PyValueError, pyclass, pyfunction, pymethods, types::PyDict, Bound, IntoPyObject,
+    PyRefMut, PyResult, Python,
+};
+
+use crate::{
+    encoding::{BytesEncoding, BytesOrHex},
+    error_handling::map_parse_to_pyerr,
+};
 
 // Python bindings for IEAS (Ethereum Attestation Service) types
 
+// Schema UIDs, like every other bytes32 the binding hands out (attestation and
+// ref UIDs, event `schema_uid` fields, `register_schema`'s result), are lowercase
+// `0x`-prefixed 64-digit hex, so they compare equal as plain strings. Attestations
+// constructed by hand normalize their schema to the same form.
+
+/// Normalize a schema UID to lowercase `0x`-prefixed 32-byte hex
+///
+/// Accepts any casing, with or without the `0x` prefix, so UIDs copied from
+/// elsewhere can be compared against the ones the binding returns.
+#[pyfunction]
+pub fn normalize_schema_uid(schema_uid: String) -> PyResult<String> {
+    let schema_uid: FixedBytes<32> = schema_uid.trim().parse().map_err(map_parse_to_pyerr)?;
+    Ok(schema_uid.to_string())
+}
+
+/// Compute the UID the schema registry assigns to a schema
+///
+/// EAS derives it as `keccak256(abi.encodePacked(schema, resolver, revocable))`,
+/// so it is known before registration. Note that `register_schema` registers its
+/// `schema` argument in normalized `0x`-hex form, so pass that form here to get
+/// the UID it registers; `register_schema` also returns that UID.
+#[pyfunction]
+pub fn compute_schema_uid(schema: String, resolver: String, revocable: bool) -> PyResult<String> {
+    let resolver: Address = resolver.parse().map_err(map_parse_to_pyerr)?;
+    Ok(schema_uid(&schema, resolver, revocable).to_string())
+}
+
+/// The UID the schema registry assigns to a schema, as in `compute_schema_uid`
+pub fn schema_uid(schema: &str, resolver: Address, revocable: bool) -> FixedBytes<32> {
+    let mut packed = schema.as_bytes().to_vec();
+    packed.extend_from_slice(resolver.as_slice());
+    packed.push(revocable as u8);
+    keccak256(packed)
+}
+
+/// Normalize a schema UID given to a constructor, leaving it as given if it
+/// isn't one so constructing an attestation by hand never fails on it
+pub(crate) fn normalize_schema_field(schema: String) -> String {
+    schema
+        .trim()
+        .parse::<FixedBytes<32>>()
+        .map_or(schema, |schema| schema.to_string())
+}
+
 /// Python representation of IEAS::Attestation
+///
+/// Field names here are the canonical ones, and `OracleAttestation` uses the same
+/// names for the same attestation. The two differ only in `data`, which is `bytes`
+/// here and a `0x`-hex string there; both have `data_hex` and `data_bytes` for
+/// the other form, and `to_oracle_attestation` and
+/// `OracleAttestation.to_attestation` convert between them without loss.
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct PyAttestation {
@@ -51,11 +110,11 @@ impl PyAttestation {
         recipient: String,
         attester: String,
         revocable: bool,
-        data: Vec<u8>,
+        data: BytesOrHex,
     ) -> Self {
         Self {
             uid,
-            schema,
+            schema: normalize_schema_field(schema),
             time,
             expiration_time,
             revocation_time,
@@ -63,10 +122,22 @@ impl PyAttestation {
             recipient,
             attester,
             revocable,
-            data,
+            data: data.0,
         }
     }
 
+    /// The attestation data as `0x`-hex, the form `OracleAttestation.data` uses
+    #[getter]
+    pub fn data_hex(&self) -> String {
+        format!("0x{}", alloy::hex::encode(&self.data))
+    }
+
+    /// The attestation data as `bytes`, the same as `data`
+    #[getter]
+    pub fn data_bytes(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PyAttestation(uid='{}', schema='{}', attester='{}', recipient='{}', time={}, revocable={})",
@@ -74,6 +145,33 @@ impl PyAttestation {
         )
     }
 
+    /// Convert to a dict, rendering byte fields as `0x`-hex (the default), base64
+    /// or raw `bytes` according to `bytes_encoding`
+    #[pyo3(signature = (bytes_encoding=BytesEncoding::Hex))]
+    pub fn to_dict<'py>(
+        &self,
+        py: Python<'py>,
+        bytes_encoding: BytesEncoding,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("uid", &self.uid)?;
+        dict.set_item("schema", &self.schema)?;
+        dict.set_item("time", self.time)?;
+        dict.set_item("expiration_time", self.expiration_time)?;
+        dict.set_item("revocation_time", self.revocation_time)?;
+        dict.set_item("ref_uid", &self.ref_uid)?;
+        dict.set_item("recipient", &self.recipient)?;
+        dict.set_item("attester", &self.attester)?;
+        dict.set_item("revocable", self.revocable)?;
+        dict.set_item("data", bytes_encoding.encode(py, &self.data)?)?;
+        Ok(dict)
+    }
+
+    /// Convert to the oracle client's representation of the same attestation
+    pub fn to_oracle_attestation(&self) -> crate::clients::oracle::PyOracleAttestation {
+        self.clone().into()
+    }
+
     /// Check if the attestation is expired
     pub fn is_expired(&self) -> bool {
         if self.expiration_time == 0 {
@@ -117,6 +215,42 @@ impl From<alkahest_rs::contracts::IEAS::Attestation> for PyAttestation {
     }
 }
 
+impl From<PyAttestation> for crate::clients::oracle::PyOracleAttestation {
+    fn from(attestation: PyAttestation) -> Self {
+        Self {
+            uid: attestation.uid,
+            schema: attestation.schema,
+            ref_uid: attestation.ref_uid,
+            time: attestation.time,
+            expiration_time: attestation.expiration_time,
+            revocation_time: attestation.revocation_time,
+            recipient: attestation.recipient,
+            attester: attestation.attester,
+            revocable: attestation.revocable,
+            data: format!("0x{}", alloy::hex::encode(&attestation.data)),
+        }
+    }
+}
+
+impl TryFrom<crate::clients::oracle::PyOracleAttestation> for PyAttestation {
+    type Error = alloy::hex::FromHexError;
+
+    fn try_from(attestation: crate::clients::oracle::PyOracleAttestation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uid: attestation.uid,
+            schema: attestation.schema,
+            time: attestation.time,
+            expiration_time: attestation.expiration_time,
+            revocation_time: attestation.revocation_time,
+            ref_uid: attestation.ref_uid,
+            recipient: attestation.recipient,
+            attester: attestation.attester,
+            revocable: attestation.revocable,
+            data: alloy::hex::decode(&attestation.data)?,
+        })
+    }
+}
+
 /// Python representation of IEAS::AttestationRequestData
 #[pyclass]
 #[derive(Clone, Debug)]
@@ -149,7 +283,7 @@ impl PyAttestationRequestData {
         expiration_time: u64,
         revocable: bool,
         ref_uid: String,
-        data: Vec<u8>,
+        data: BytesOrHex,
         value: u128,
     ) -> Self {
         Self {
@@ -157,11 +291,17 @@ impl PyAttestationRequestData {
             expiration_time,
             revocable,
             ref_uid,
-            data,
+            data: data.0,
             value,
         }
     }
 
+    /// The attestation data as `0x`-hex
+    #[getter]
+    pub fn data_hex(&self) -> String {
+        format!("0x{}", alloy::hex::encode(&self.data))
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PyAttestationRequestData(recipient='{}', expiration_time={}, revocable={}, data_len={})",
@@ -226,6 +366,113 @@ impl TryFrom<PyAttestationRequest> for alkahest_rs::contracts::IEAS::Attestation
     }
 }
 
+/// Fluent builder for PyAttestationRequest
+///
+/// Defaults to a revocable attestation to the zero address with no expiration,
+/// no reference, empty data and no value; only the schema must be set. Addresses
+/// and UIDs are validated when `build` is called.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PyAttestationBuilder {
+    schema: Option<String>,
+    recipient: Option<String>,
+    expiration_time: u64,
+    revocable: Option<bool>,
+    ref_uid: Option<String>,
+    data: Vec<u8>,
+    value: u128,
+}
+
+#[pymethods]
+impl PyAttestationBuilder {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schema(mut slf: PyRefMut<'_, Self>, schema: String) -> PyRefMut<'_, Self> {
+        slf.schema = Some(schema);
+        slf
+    }
+
+    pub fn recipient(mut slf: PyRefMut<'_, Self>, recipient: String) -> PyRefMut<'_, Self> {
+        slf.recipient = Some(recipient);
+        slf
+    }
+
+    /// Set the data, as `bytes` or a hex string
+    pub fn data(mut slf: PyRefMut<'_, Self>, data: BytesOrHex) -> PyRefMut<'_, Self> {
+        slf.data = data.0;
+        slf
+    }
+
+    /// Set the expiration as a unix timestamp (0 = never expires)
+    pub fn expiration(mut slf: PyRefMut<'_, Self>, expiration_time: u64) -> PyRefMut<'_, Self> {
+        slf.expiration_time = expiration_time;
+        slf
+    }
+
+    /// Set the expiration to `seconds` from now
+    pub fn expiration_in(mut slf: PyRefMut<'_, Self>, seconds: u64) -> PyRefMut<'_, Self> {
+        slf.expiration_time = crate::escrow::expiration_in(seconds);
+        slf
+    }
+
+    pub fn revocable(mut slf: PyRefMut<'_, Self>, revocable: bool) -> PyRefMut<'_, Self> {
+        slf.revocable = Some(revocable);
+        slf
+    }
+
+    pub fn ref_uid(mut slf: PyRefMut<'_, Self>, ref_uid: String) -> PyRefMut<'_, Self> {
+        slf.ref_uid = Some(ref_uid);
+        slf
+    }
+
+    pub fn value(mut slf: PyRefMut<'_, Self>, value: u128) -> PyRefMut<'_, Self> {
+        slf.value = value;
+        slf
+    }
+
+    /// Validate the fields set so far and produce the attestation request
+    pub fn build(&self) -> PyResult<PyAttestationRequest> {
+        let schema = self
+            .schema
+            .as_deref()
+            .ok_or_else(|| PyValueError::new_err("Attestation schema is required"))?;
+        let schema: FixedBytes<32> = schema.parse().map_err(map_parse_to_pyerr)?;
+        let recipient: Address = match &self.recipient {
+            Some(recipient) => recipient.parse().map_err(map_parse_to_pyerr)?,
+            None => Address::ZERO,
+        };
+        let ref_uid: FixedBytes<32> = match &self.ref_uid {
+            Some(ref_uid) => ref_uid.parse().map_err(map_parse_to_pyerr)?,
+            None => FixedBytes::ZERO,
+        };
+
+        Ok(PyAttestationRequest {
+            schema: schema.to_string(),
+            data: PyAttestationRequestData {
+                recipient: format!("{:?}", recipient),
+                expiration_time: self.expiration_time,
+                revocable: self.revocable.unwrap_or(true),
+                ref_uid: ref_uid.to_string(),
+                data: self.data.clone(),
+                value: self.value,
+            },
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyAttestationBuilder(schema={:?}, recipient={:?}, expiration_time={}, revocable={})",
+            self.schema,
+            self.recipient,
+            self.expiration_time,
+            self.revocable.unwrap_or(true)
+        )
+    }
+}
+
 /// Python representation of IEAS::RevocationRequestData
 #[pyclass]
 #[derive(Clone, Debug)]
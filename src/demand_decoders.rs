@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use alloy::primitives::Address;
+use pyo3::{
+    exceptions::PyTypeError,
+    pyfunction,
+    types::{PyAnyMethods, PyBytes},
+    Bound, IntoPyObject, Py, PyAny, PyObject, PyResult, Python,
+};
+
+use crate::{
+    error_handling::map_parse_to_pyerr,
+    escrow::{decode_payment_demand, PaymentDemand},
+};
+
+static DECODERS: OnceLock<Mutex<HashMap<Address, Py<PyAny>>>> = OnceLock::new();
+
+fn decoders() -> std::sync::MutexGuard<'static, HashMap<Address, Py<PyAny>>> {
+    DECODERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Register a Python callable that decodes the demands of a custom arbiter
+///
+/// `decoder` is called with the demand as `bytes` and whatever it returns is
+/// handed back by `decode_demand` on escrow obligation data naming `arbiter`.
+/// If the decoder raises, the demand is handed back as raw bytes instead.
+/// Registering again for the same arbiter replaces the earlier decoder. The
+/// registry is process-wide, so every client sees the same decoders.
+#[pyfunction]
+pub fn register_demand_decoder(arbiter: String, decoder: Bound<'_, PyAny>) -> PyResult<()> {
+    let arbiter: Address = arbiter.parse().map_err(map_parse_to_pyerr)?;
+    if !decoder.is_callable() {
+        return Err(PyTypeError::new_err("decoder must be callable"));
+    }
+    decoders().insert(arbiter, decoder.unbind());
+    Ok(())
+}
+
+/// Remove the decoder registered for `arbiter`, returning whether there was one
+#[pyfunction]
+pub fn unregister_demand_decoder(arbiter: String) -> PyResult<bool> {
+    let arbiter: Address = arbiter.parse().map_err(map_parse_to_pyerr)?;
+    Ok(decoders().remove(&arbiter).is_some())
+}
+
+/// Arbiters that have a registered demand decoder
+#[pyfunction]
+pub fn registered_demand_decoders() -> Vec<String> {
+    decoders()
+        .keys()
+        .map(|arbiter| format!("{:?}", arbiter))
+        .collect()
+}
+
+/// An escrow's demand, decoded as far as the binding knows how
+#[derive(IntoPyObject)]
+pub enum Demand {
+    /// A payment, for the alkahest payment obligations and their arbiters
+    Payment(PaymentDemand),
+    /// The result of a decoder registered for the arbiter
+    Custom(PyObject),
+    /// The undecoded demand, for arbiters with no decoder or whose decoder raised
+    Raw(Vec<u8>),
+}
+
+/// Decode an escrow's demand according to its arbiter
+///
+/// Known payment arbiters are decoded as their payment obligation data, and other
+/// arbiters by their registered decoder if they have one; anything else is
+/// returned as raw bytes. A decoder that raises is treated as having none, so one
+/// bad decoder can't break the offers and explanations that describe a demand.
+pub fn decode_demand(
+    py: Python<'_>,
+    arbiter: &str,
+    demand: &[u8],
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> PyResult<Demand> {
+    if let Ok(payment) = decode_payment_demand(arbiter, demand, addresses) {
+        return Ok(Demand::Payment(payment));
+    }
+
+    let address: Address = arbiter.parse().map_err(map_parse_to_pyerr)?;
+    // Release the registry before calling into Python, which may register decoders
+    let decoder = decoders().get(&address).map(|decoder| decoder.clone_ref(py));
+    match decoder.map(|decoder| decoder.call1(py, (PyBytes::new(py, demand),))) {
+        Some(Ok(decoded)) => Ok(Demand::Custom(decoded)),
+        Some(Err(_)) | None => Ok(Demand::Raw(demand.to_vec())),
+    }
+}
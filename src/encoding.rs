@@ -0,0 +1,68 @@
+use base64::{prelude::BASE64_STANDARD, Engine as _};
+use pyo3::{
+    exceptions::PyValueError,
+    types::{PyAnyMethods, PyBytes},
+    Bound, FromPyObject, IntoPyObjectExt, PyAny, PyResult, Python,
+};
+
+use crate::error_handling::map_parse_to_pyerr;
+
+/// How `to_dict` renders byte fields such as `data` and `demand`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BytesEncoding {
+    /// `0x`-prefixed lowercase hex string
+    #[default]
+    Hex,
+    /// Standard base64 string, with padding
+    Base64,
+    /// Python `bytes`
+    Raw,
+}
+
+impl<'py> FromPyObject<'py> for BytesEncoding {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "raw" => Ok(Self::Raw),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown bytes_encoding '{}', expected 'hex', 'base64' or 'raw'",
+                other
+            ))),
+        }
+    }
+}
+
+impl BytesEncoding {
+    /// Render bytes as a Python value in this encoding
+    pub fn encode<'py>(self, py: Python<'py>, bytes: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            Self::Hex => format!("0x{}", alloy::hex::encode(bytes)).into_bound_py_any(py),
+            Self::Base64 => BASE64_STANDARD.encode(bytes).into_bound_py_any(py),
+            Self::Raw => Ok(PyBytes::new(py, bytes).into_any()),
+        }
+    }
+
+    /// Render a `0x`-hex string field in this encoding
+    pub fn encode_hex<'py>(self, py: Python<'py>, hex: &str) -> PyResult<Bound<'py, PyAny>> {
+        self.encode(py, &decode_hex(hex)?)
+    }
+}
+
+/// Bytes given either as `bytes` or as a hex string, with or without a `0x`
+/// prefix, so data read back as either form can be passed in again
+pub struct BytesOrHex(pub Vec<u8>);
+
+impl<'py> FromPyObject<'py> for BytesOrHex {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>() {
+            Ok(hex) => decode_hex(&hex).map(Self),
+            Err(_) => Ok(Self(ob.extract()?)),
+        }
+    }
+}
+
+/// Parse a hex string, with or without a `0x` prefix, into bytes
+pub fn decode_hex(hex: &str) -> PyResult<Vec<u8>> {
+    alloy::hex::decode(hex.trim()).map_err(map_parse_to_pyerr)
+}
@@ -19,3 +19,24 @@ pub fn map_serde_to_pyerr<T: std::fmt::Display>(err: T) -> PyErr {
 pub fn map_sol_decode_to_pyerr(err: alloy::sol_types::Error) -> PyErr {
     pyo3::exceptions::PyValueError::new_err(format!("Sol decode error: {}", err))
 }
+
+pyo3::create_exception!(
+    alkahest_py,
+    TransactionRevertError,
+    pyo3::exceptions::PyRuntimeError,
+    "Raised when a transaction mined but the contract reported that it failed"
+);
+
+pyo3::create_exception!(
+    alkahest_py,
+    InsufficientBalanceError,
+    pyo3::exceptions::PyValueError,
+    "Raised before sending when the signer holds too little of a token"
+);
+
+pyo3::create_exception!(
+    alkahest_py,
+    ReadOnlyClientError,
+    pyo3::exceptions::PyRuntimeError,
+    "Raised when a client built with `read_only` is asked to send a transaction"
+);
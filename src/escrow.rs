@@ -0,0 +1,731 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alkahest_rs::{
+    contracts::IEAS::{self, Attested},
+    sol_types::EscrowClaimed,
+};
+use alloy::{
+    contract::{CallBuilder, CallDecoder},
+    eips::BlockNumberOrTag,
+    primitives::{Address, Bytes, FixedBytes, U256},
+    providers::{DynProvider, Provider},
+    rpc::types::{Filter, Log, TransactionReceipt},
+    sol_types::SolEvent,
+};
+use pyo3::{pyfunction, IntoPyObject, PyResult};
+
+use crate::{
+    clients::{
+        erc1155::{PyERC1155EscrowObligationData, PyERC1155PaymentObligationData},
+        erc20::{PyERC20EscrowObligationData, PyERC20PaymentObligationData},
+        erc721::{PyERC721EscrowObligationData, PyERC721PaymentObligationData},
+        token_bundle::PyTokenBundleEscrowObligationData,
+    },
+    contract::PyAttestation,
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, TransactionRevertError},
+    logs::{get_logs_in_windows, get_logs_windowed},
+    types::DefaultExtensionConfig,
+};
+
+/// A payment an escrow demands from its fulfiller, decoded according to the
+/// escrow's arbiter
+#[derive(IntoPyObject)]
+pub enum PaymentDemand {
+    Erc20(PyERC20PaymentObligationData),
+    Erc721(PyERC721PaymentObligationData),
+    Erc1155(PyERC1155PaymentObligationData),
+}
+
+/// Resolve an optional Python address config, falling back to the
+/// alkahest-rs default deployment
+pub fn resolve_addresses(
+    addresses: Option<DefaultExtensionConfig>,
+) -> PyResult<alkahest_rs::DefaultExtensionConfig> {
+    Ok(addresses
+        .map(TryInto::try_into)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// Decode an escrow's demand as the payment it asks for
+///
+/// Escrows created by the `buy_*_for_*` helpers name a payment obligation
+/// contract as their arbiter, and escrows can also name the matching payment
+/// fulfillment arbiter; both take the payment obligation's data as their demand.
+pub fn decode_payment_demand(
+    arbiter: &str,
+    demand: &[u8],
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> PyResult<PaymentDemand> {
+    let arbiter: Address = arbiter.parse().map_err(map_parse_to_pyerr)?;
+    let demand = Bytes::copy_from_slice(demand);
+    let arbiters = &addresses.arbiters_addresses;
+
+    if arbiter == addresses.erc20_addresses.payment_obligation
+        || arbiter == arbiters.erc20_payment_fulfillment_arbiter
+    {
+        let decoded = alkahest_rs::extensions::Erc20Module::decode_payment_obligation(&demand)
+            .map_err(map_eyre_to_pyerr)?;
+        return Ok(PaymentDemand::Erc20(decoded.into()));
+    }
+
+    if arbiter == addresses.erc721_addresses.payment_obligation
+        || arbiter == arbiters.erc721_payment_fulfillment_arbiter
+    {
+        let decoded = alkahest_rs::extensions::Erc721Module::decode_payment_obligation(&demand)
+            .map_err(map_eyre_to_pyerr)?;
+        return Ok(PaymentDemand::Erc721(decoded.into()));
+    }
+
+    if arbiter == addresses.erc1155_addresses.payment_obligation
+        || arbiter == arbiters.erc1155_payment_fulfillment_arbiter
+    {
+        let decoded = alkahest_rs::extensions::Erc1155Module::decode_payment_obligation(&demand)
+            .map_err(map_eyre_to_pyerr)?;
+        return Ok(PaymentDemand::Erc1155(decoded.into()));
+    }
+
+    Err(pyo3::exceptions::PyValueError::new_err(format!(
+        "Arbiter {:?} is not a known payment arbiter",
+        arbiter
+    )))
+}
+
+/// Look up the `EscrowClaimed` log for an escrow, with its block and
+/// transaction context
+pub async fn find_escrow_claim<P: Provider>(
+    provider: &P,
+    contract_address: Address,
+    buy_attestation: FixedBytes<32>,
+    from_block: Option<u64>,
+) -> eyre::Result<Option<Log<EscrowClaimed>>> {
+    let filter = Filter::new()
+        .address(contract_address)
+        .event_signature(EscrowClaimed::SIGNATURE_HASH)
+        .topic1(buy_attestation)
+        .from_block(from_block.unwrap_or(0));
+
+    let logs = provider.get_logs(&filter).await?;
+    logs.first()
+        .map(|log| log.log_decode::<EscrowClaimed>())
+        .transpose()
+        .map_err(Into::into)
+}
+
+/// Wait until an escrow's `EscrowClaimed` log is `confirmations` blocks deep
+///
+/// The claim block itself counts as the first confirmation. The log is looked up
+/// again on every poll, so a claim that is reorged out is waited for anew and a
+/// claim re-included in a different block has its depth counted from there.
+pub async fn confirm_escrow_claim<P: Provider>(
+    provider: &P,
+    contract_address: Address,
+    buy_attestation: FixedBytes<32>,
+    from_block: Option<u64>,
+    confirmations: u64,
+) -> eyre::Result<Log<EscrowClaimed>> {
+    loop {
+        // Read the head before the logs, so a log seen at this head is at least as
+        // deep as computed even if more blocks arrive in between
+        let head = provider.get_block_number().await?;
+        let claim =
+            find_escrow_claim(provider, contract_address, buy_attestation, from_block).await?;
+        if let Some(log) = claim {
+            if let Some(block_number) = log.block_number {
+                if head + 1 >= block_number + confirmations {
+                    return Ok(log);
+                }
+            }
+        }
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+}
+
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Poll for the `EscrowClaimed` logs of several escrows, each given with its
+/// escrow contract, until every one has been claimed or, unless `all`, until any
+/// one has
+///
+/// Each poll is a single log query for all the escrows still waited on, covering
+/// only the blocks since the previous poll. With `all` the claims are returned in
+/// the order the escrows were given; otherwise the earliest claim found is
+/// returned.
+pub async fn wait_for_escrow_claims<P: Provider>(
+    provider: &P,
+    escrows: &[(Address, FixedBytes<32>)],
+    from_block: Option<u64>,
+    all: bool,
+) -> eyre::Result<Vec<Log<EscrowClaimed>>> {
+    let mut claims: Vec<Option<Log<EscrowClaimed>>> = vec![None; escrows.len()];
+    let mut from_block = from_block.unwrap_or(0);
+    loop {
+        let pending: Vec<_> = escrows
+            .iter()
+            .zip(&claims)
+            .filter(|(_, claim)| claim.is_none())
+            .map(|(escrow, _)| *escrow)
+            .collect();
+        let filter = Filter::new()
+            .address(pending.iter().map(|(contract, _)| *contract).collect::<Vec<_>>())
+            .event_signature(EscrowClaimed::SIGNATURE_HASH)
+            .topic1(pending.iter().map(|(_, uid)| *uid).collect::<Vec<_>>());
+
+        let head = provider.get_block_number().await?;
+        let logs = get_logs_in_windows(provider, &filter, from_block, head).await?;
+        from_block = from_block.max(head + 1);
+
+        // The filter matches any of the contracts with any of the uids, so each
+        // log is paired with its escrow by both
+        for log in logs {
+            let uid = log.topics().get(1).copied();
+            for ((contract_address, buy_attestation), claim) in
+                escrows.iter().zip(claims.iter_mut())
+            {
+                if claim.is_none()
+                    && log.address() == *contract_address
+                    && uid == Some(*buy_attestation)
+                {
+                    let log = log.log_decode::<EscrowClaimed>()?;
+                    if !all {
+                        return Ok(vec![log]);
+                    }
+                    *claim = Some(log);
+                }
+            }
+        }
+        if claims.iter().all(Option::is_some) {
+            return Ok(claims.into_iter().flatten().collect());
+        }
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+}
+
+/// Parse an optional `value_wei` argument, a decimal string of wei to send along
+/// with an escrow; missing means zero
+pub fn parse_value_wei(value_wei: Option<String>) -> PyResult<U256> {
+    value_wei
+        .map(|value| value.parse().map_err(map_parse_to_pyerr))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Parse an optional `payee` argument; missing means the signer pays themselves
+pub fn payee_or_signer(payee: Option<String>, signer: Address) -> PyResult<Address> {
+    payee.map_or(Ok(signer), |payee| payee.parse().map_err(map_parse_to_pyerr))
+}
+
+/// Send an obligation contract call with native value attached
+///
+/// The call is simulated with the value first. Obligation contracts whose
+/// `doObligation` isn't payable reject any value, so if the call fails with the
+/// value but succeeds without it, that surfaces as a `ValueError` before anything
+/// is sent instead of as a reverted transaction. Any other failure, such as a
+/// missing allowance, is reported as the node gave it.
+pub async fn send_with_value<P, D>(
+    call: CallBuilder<P, D>,
+    value: U256,
+) -> PyResult<TransactionReceipt>
+where
+    P: Provider + Clone,
+    D: CallDecoder + Clone,
+{
+    let without_value = call.clone();
+    let call = call.value(value);
+    if let Err(e) = call.call_raw().await {
+        if without_value.call_raw().await.is_ok() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Obligation contract rejected value_wei {}, it does not accept native value: {}",
+                value, e
+            )));
+        }
+        return Err(map_eyre_to_pyerr(e.into()));
+    }
+    let receipt = call
+        .send()
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?
+        .get_receipt()
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+    if !receipt.status() {
+        return Err(TransactionRevertError::new_err(format!(
+            "Transaction {} reverted",
+            receipt.transaction_hash
+        )));
+    }
+    Ok(receipt)
+}
+
+/// Check, for `strict=True`, that a `pay_<payment>_for_<escrowed>` method would
+/// fulfill an escrow in a way its arbiter accepts, before anything is sent
+///
+/// The payment sent is read from the escrow's demand, so it only satisfies the
+/// escrow if the demand really is a `payment` obligation and the arbiter checks
+/// fulfillments against the contract the payment is attested through. The escrow
+/// must also still be open and hold `escrowed` obligation data, which is what the
+/// method decodes it as.
+pub async fn check_fulfillment_matches_demand<P: Provider>(
+    provider: &P,
+    eas: Address,
+    buy_attestation: FixedBytes<32>,
+    escrowed: &str,
+    payment: &str,
+    payment_obligation: Address,
+) -> PyResult<()> {
+    let invalid = |reason: String| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Escrow {} can't be fulfilled with a {} payment: {}",
+            buy_attestation, payment, reason
+        ))
+    };
+
+    let (escrow, now) = tokio::try_join!(
+        async {
+            IEAS::new(eas, provider)
+                .getAttestation(buy_attestation)
+                .call()
+                .await
+                .map_err(eyre::Error::from)
+        },
+        latest_block_timestamp(provider),
+    )
+    .map_err(map_eyre_to_pyerr)?;
+    if escrow.uid == FixedBytes::<32>::ZERO {
+        return Err(invalid("it doesn't exist".to_string()));
+    }
+    if escrow.revocationTime != 0 {
+        return Err(invalid("it has been revoked".to_string()));
+    }
+    if escrow.expirationTime != 0 && escrow.expirationTime <= now {
+        return Err(invalid(format!("it expired at {}", escrow.expirationTime)));
+    }
+
+    let obligation = EscrowObligation::decode(escrowed, &escrow.data)
+        .ok_or_else(|| invalid(format!("it doesn't hold {} escrow data", escrowed)))?;
+    let arbiter: Address = obligation.arbiter().parse().map_err(map_parse_to_pyerr)?;
+    if arbiter != payment_obligation {
+        return Err(invalid(format!(
+            "its arbiter {:?} is not the {} payment obligation {:?} the payment is attested \
+             through, so it may reject the payment",
+            arbiter, payment, payment_obligation
+        )));
+    }
+
+    let demand = Bytes::copy_from_slice(obligation.demand());
+    let payee = match payment {
+        "erc20" => alkahest_rs::extensions::Erc20Module::decode_payment_obligation(&demand)
+            .map(|data| data.payee),
+        "erc721" => alkahest_rs::extensions::Erc721Module::decode_payment_obligation(&demand)
+            .map(|data| data.payee),
+        "erc1155" => alkahest_rs::extensions::Erc1155Module::decode_payment_obligation(&demand)
+            .map(|data| data.payee),
+        _ => alkahest_rs::extensions::TokenBundleModule::decode_payment_obligation(&demand)
+            .map(|data| data.payee),
+    }
+    .map_err(|e| invalid(format!("its demand is not {} payment data: {}", payment, e)))?;
+    if payee == Address::ZERO {
+        return Err(invalid("its demand pays the zero address".to_string()));
+    }
+    Ok(())
+}
+
+/// The `strict=True` check of a `pay_<payment>_for_<escrowed>` method, taken
+/// from its client so the method's future can run it
+pub struct StrictDemandCheck {
+    provider: DynProvider,
+    eas: Address,
+    payment_obligation: Address,
+    escrowed: &'static str,
+    payment: &'static str,
+    strict: bool,
+}
+
+impl StrictDemandCheck {
+    pub fn new(
+        provider: DynProvider,
+        eas: Address,
+        payment_obligation: Address,
+        escrowed: &'static str,
+        payment: &'static str,
+        strict: bool,
+    ) -> Self {
+        Self {
+            provider,
+            eas,
+            payment_obligation,
+            escrowed,
+            payment,
+            strict,
+        }
+    }
+
+    /// Parse the method's `buy_attestation`, running
+    /// [`check_fulfillment_matches_demand`] on it when `strict` is set
+    pub async fn buy_attestation(&self, buy_attestation: &str) -> PyResult<FixedBytes<32>> {
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+        if self.strict {
+            check_fulfillment_matches_demand(
+                &self.provider,
+                self.eas,
+                buy_attestation,
+                self.escrowed,
+                self.payment,
+                self.payment_obligation,
+            )
+            .await?;
+        }
+        Ok(buy_attestation)
+    }
+}
+
+/// Error raised by `cancel_escrow`
+///
+/// The escrow obligation contracts only release escrowed tokens through
+/// `collectEscrow` (to a valid fulfiller) or `reclaimExpired` (to the depositor,
+/// once the escrow's expiration has passed). There is no early cancellation path,
+/// so an unfulfilled escrow stays locked until it expires.
+pub fn cancel_escrow_unsupported(buy_attestation: &str) -> pyo3::PyErr {
+    pyo3::exceptions::PyNotImplementedError::new_err(format!(
+        "Escrow {} cannot be cancelled before expiry: the escrow obligation contracts \
+         have no early cancellation, use reclaim_expired once the expiration has passed",
+        buy_attestation
+    ))
+}
+
+/// The escrow obligation contracts of a deployment, with the EAS they attest on
+pub struct EscrowContracts {
+    pub eas: Address,
+    pub erc20: Address,
+    pub erc721: Address,
+    pub erc1155: Address,
+    pub token_bundle: Address,
+}
+
+impl EscrowContracts {
+    fn all(&self) -> Vec<Address> {
+        vec![self.erc20, self.erc721, self.erc1155, self.token_bundle]
+    }
+
+    /// Token type of the escrow contract that issued an attestation
+    pub fn token_type(&self, attester: Address) -> Option<&'static str> {
+        if attester == self.erc20 {
+            Some("erc20")
+        } else if attester == self.erc721 {
+            Some("erc721")
+        } else if attester == self.erc1155 {
+            Some("erc1155")
+        } else if attester == self.token_bundle {
+            Some("token_bundle")
+        } else {
+            None
+        }
+    }
+}
+
+/// An escrow's obligation data, decoded according to its escrow contract
+#[derive(IntoPyObject)]
+pub enum EscrowObligation {
+    Erc20(PyERC20EscrowObligationData),
+    Erc721(PyERC721EscrowObligationData),
+    Erc1155(PyERC1155EscrowObligationData),
+    TokenBundle(PyTokenBundleEscrowObligationData),
+}
+
+impl EscrowObligation {
+    /// Address of the arbiter that decides whether the escrow is fulfilled
+    pub fn arbiter(&self) -> &str {
+        match self {
+            Self::Erc20(data) => &data.arbiter,
+            Self::Erc721(data) => &data.arbiter,
+            Self::Erc1155(data) => &data.arbiter,
+            Self::TokenBundle(data) => &data.arbiter,
+        }
+    }
+
+    /// The demand the arbiter checks fulfillments against
+    pub fn demand(&self) -> &[u8] {
+        match self {
+            Self::Erc20(data) => &data.demand,
+            Self::Erc721(data) => &data.demand,
+            Self::Erc1155(data) => &data.demand,
+            Self::TokenBundle(data) => &data.demand,
+        }
+    }
+
+    /// Decode escrow obligation data for a token type, if the binding has a
+    /// Python type for it
+    pub fn decode(token_type: &str, data: &[u8]) -> Option<Self> {
+        match token_type {
+            "erc20" => PyERC20EscrowObligationData::decode(data.to_vec())
+                .ok()
+                .map(Self::Erc20),
+            "erc721" => PyERC721EscrowObligationData::decode(data.to_vec())
+                .ok()
+                .map(Self::Erc721),
+            "erc1155" => PyERC1155EscrowObligationData::decode(data.to_vec())
+                .ok()
+                .map(Self::Erc1155),
+            "token_bundle" => PyTokenBundleEscrowObligationData::decode(data.to_vec())
+                .ok()
+                .map(Self::TokenBundle),
+            _ => None,
+        }
+    }
+}
+
+/// An escrow that can still be claimed or, once it expires, reclaimed
+#[derive(IntoPyObject)]
+pub struct OpenEscrow {
+    pub attestation: PyAttestation,
+    pub token_type: String,
+    pub obligation: Option<EscrowObligation>,
+    pub expiration_time: u64,
+}
+
+/// Timestamp of the latest block, which is what escrow expiration is checked against
+pub async fn latest_block_timestamp<P: Provider>(provider: &P) -> eyre::Result<u64> {
+    let block = provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await?
+        .ok_or_else(|| eyre::eyre!("Latest block not found"))?;
+    Ok(block.header.timestamp)
+}
+
+/// Unix timestamp `seconds` from now, for use as an escrow expiration
+///
+/// Uses the local clock; on a dev chain whose time has been advanced, add the
+/// offset or compare against the latest block timestamp instead.
+#[pyfunction]
+pub fn expiration_in(seconds: u64) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    now + seconds
+}
+
+/// Reject an escrow expiration that has already passed
+///
+/// `expiration` is a unix timestamp, and passing a duration such as `3600` by
+/// mistake would create an escrow that is expired on arrival. Zero means the escrow
+/// never expires and is always accepted, as is anything when `force` is set.
+pub async fn check_expiration<P: Provider>(
+    provider: &P,
+    expiration: u64,
+    force: bool,
+) -> PyResult<()> {
+    if force || expiration == 0 {
+        return Ok(());
+    }
+
+    let now = latest_block_timestamp(provider)
+        .await
+        .map_err(map_eyre_to_pyerr)?;
+    if expiration <= now {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Expiration {} is not after the latest block timestamp {}. Expiration is a unix \
+             timestamp, not a duration: use expiration_in({}) to expire {} seconds from now, \
+             or pass force=True",
+            expiration, now, expiration, expiration
+        )));
+    }
+    Ok(())
+}
+
+/// Find escrows deposited by an address that are neither claimed, revoked nor
+/// expired
+///
+/// Escrow attestations are found through the EAS `Attested` events naming one of
+/// the escrow contracts as attester and the depositor as recipient. Claims are
+/// cross-referenced against the escrow contracts' `EscrowClaimed` events for those
+/// escrows, and each remaining attestation is re-read so escrows reclaimed or
+/// revoked since are dropped too. Both event queries are made in fixed block
+/// windows from `from_block`, genesis by default.
+pub async fn find_open_escrows<P: Provider>(
+    provider: &P,
+    contracts: &EscrowContracts,
+    depositor: Address,
+    from_block: Option<u64>,
+) -> eyre::Result<Vec<OpenEscrow>> {
+    let from_block = from_block.unwrap_or(0);
+    let escrow_contracts = contracts.all();
+
+    let attested = Filter::new()
+        .address(contracts.eas)
+        .event_signature(Attested::SIGNATURE_HASH)
+        .topic1(depositor.into_word())
+        .topic2(
+            escrow_contracts
+                .iter()
+                .map(|address| address.into_word())
+                .collect::<Vec<_>>(),
+        );
+    let uids = get_logs_windowed(provider, &attested, from_block)
+        .await?
+        .iter()
+        .map(|log| log.log_decode::<Attested>().map(|log| log.inner.data.uid))
+        .collect::<Result<Vec<_>, _>>()?;
+    if uids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let claimed = Filter::new()
+        .address(escrow_contracts)
+        .event_signature(EscrowClaimed::SIGNATURE_HASH)
+        .topic1(uids.clone());
+    let claimed: HashSet<FixedBytes<32>> = get_logs_windowed(provider, &claimed, from_block)
+        .await?
+        .iter()
+        .filter_map(|log| log.topics().get(1).copied())
+        .collect();
+
+    let now = latest_block_timestamp(provider).await?;
+    let eas = IEAS::new(contracts.eas, provider);
+
+    let mut open = Vec::new();
+    for uid in uids {
+        if claimed.contains(&uid) {
+            continue;
+        }
+
+        let attestation = eas.getAttestation(uid).call().await?;
+        let expired = attestation.expirationTime != 0 && attestation.expirationTime <= now;
+        if attestation.revocationTime != 0 || expired {
+            continue;
+        }
+
+        let token_type = contracts
+            .token_type(attestation.attester)
+            .unwrap_or("unknown");
+        open.push(OpenEscrow {
+            token_type: token_type.to_string(),
+            obligation: EscrowObligation::decode(token_type, &attestation.data),
+            expiration_time: attestation.expirationTime,
+            attestation: attestation.into(),
+        });
+    }
+
+    Ok(open)
+}
+
+/// Blocks covered by each log query when scanning backward for recent escrows
+const RECENT_ESCROWS_WINDOW: u64 = 10_000;
+
+/// Windows scanned for recent escrows when no `from_block` is given, so a chain
+/// with few escrows isn't walked back to genesis one window at a time
+const RECENT_ESCROWS_MAX_WINDOWS: u64 = 100;
+
+/// An open escrow, with the block and transaction that created it
+#[derive(IntoPyObject)]
+pub struct RecentEscrow {
+    pub attestation: PyAttestation,
+    pub token_type: String,
+    pub obligation: Option<EscrowObligation>,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+}
+
+/// Find the most recently created open escrows from any depositor, newest first
+///
+/// Scans backward from the latest block to `from_block` in fixed windows, so a
+/// small `limit` only queries the recent end of the chain. Without `from_block`
+/// the scan stops after [`RECENT_ESCROWS_MAX_WINDOWS`] windows. Escrows are found
+/// and filtered as in `find_open_escrows`; with `schema`, only escrows attested
+/// under that schema are considered.
+pub async fn find_recent_escrows<P: Provider>(
+    provider: &P,
+    contracts: &EscrowContracts,
+    schema: Option<FixedBytes<32>>,
+    limit: usize,
+    from_block: Option<u64>,
+) -> eyre::Result<Vec<RecentEscrow>> {
+    let escrow_contracts = contracts.all();
+    let latest = provider.get_block_number().await?;
+    let from_block = from_block.unwrap_or_else(|| {
+        latest.saturating_sub(RECENT_ESCROWS_WINDOW * RECENT_ESCROWS_MAX_WINDOWS - 1)
+    });
+    let now = latest_block_timestamp(provider).await?;
+    let eas = IEAS::new(contracts.eas, provider);
+
+    let mut recent = Vec::new();
+    let mut to_block = latest;
+    while recent.len() < limit && to_block >= from_block {
+        let start = to_block
+            .saturating_sub(RECENT_ESCROWS_WINDOW - 1)
+            .max(from_block);
+
+        let mut attested = Filter::new()
+            .address(contracts.eas)
+            .event_signature(Attested::SIGNATURE_HASH)
+            .topic2(
+                escrow_contracts
+                    .iter()
+                    .map(|address| address.into_word())
+                    .collect::<Vec<_>>(),
+            )
+            .from_block(start)
+            .to_block(to_block);
+        if let Some(schema) = schema {
+            attested = attested.topic3(schema);
+        }
+        let mut logs = provider.get_logs(&attested).await?;
+        logs.sort_by_key(|log| std::cmp::Reverse((log.block_number, log.log_index)));
+        let uids = logs
+            .iter()
+            .map(|log| log.log_decode::<Attested>().map(|log| log.inner.data.uid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !uids.is_empty() {
+            // Claims can only follow their escrow, so none predate the window
+            let claimed = Filter::new()
+                .address(escrow_contracts.clone())
+                .event_signature(EscrowClaimed::SIGNATURE_HASH)
+                .topic1(uids.clone())
+                .from_block(start);
+            let claimed: HashSet<FixedBytes<32>> = provider
+                .get_logs(&claimed)
+                .await?
+                .iter()
+                .filter_map(|log| log.topics().get(1).copied())
+                .collect();
+
+            for (log, uid) in logs.iter().zip(uids) {
+                if recent.len() == limit {
+                    break;
+                }
+                if claimed.contains(&uid) {
+                    continue;
+                }
+
+                let attestation = eas.getAttestation(uid).call().await?;
+                let expired = attestation.expirationTime != 0 && attestation.expirationTime <= now;
+                if attestation.revocationTime != 0 || expired {
+                    continue;
+                }
+
+                let token_type = contracts
+                    .token_type(attestation.attester)
+                    .unwrap_or("unknown");
+                recent.push(RecentEscrow {
+                    token_type: token_type.to_string(),
+                    obligation: EscrowObligation::decode(token_type, &attestation.data),
+                    attestation: attestation.into(),
+                    block_number: log.block_number,
+                    transaction_hash: log.transaction_hash.map(|hash| hash.to_string()),
+                });
+            }
+        }
+
+        if start == 0 {
+            break;
+        }
+        to_block = start - 1;
+    }
+
+    Ok(recent)
+}
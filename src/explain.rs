@@ -0,0 +1,393 @@
+use std::fmt::Write;
+
+use alkahest_rs::contracts::{
+    ERC1155EscrowObligation, ERC1155PaymentObligation, ERC20EscrowObligation,
+    ERC20PaymentObligation, ERC721EscrowObligation, ERC721PaymentObligation, StringObligation,
+    TokenBundleEscrowObligation, IEAS,
+};
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+    sol,
+    sol_types::SolType,
+};
+use pyo3::{types::PyAnyMethods, PyResult, Python};
+
+use crate::{
+    demand_decoders::{decode_demand, Demand},
+    escrow::PaymentDemand,
+    tokens::is_missing_getter,
+};
+
+/// The kind of obligation an attestation records, identified by its attester
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ObligationKind {
+    Erc20Escrow,
+    Erc20Payment,
+    Erc721Escrow,
+    Erc721Payment,
+    Erc1155Escrow,
+    Erc1155Payment,
+    TokenBundleEscrow,
+    TokenBundlePayment,
+    StringObligation,
+}
+
+impl ObligationKind {
+    /// Classify an attestation by the obligation contract that issued it
+    pub fn of(attester: Address, addresses: &alkahest_rs::DefaultExtensionConfig) -> Option<Self> {
+        Self::known(addresses)
+            .into_iter()
+            .find(|(address, _)| *address == attester)
+            .map(|(_, kind)| kind)
+    }
+
+    /// Every obligation contract in the address config, with its kind
+    fn known(addresses: &alkahest_rs::DefaultExtensionConfig) -> [(Address, Self); 9] {
+        [
+            (addresses.erc20_addresses.escrow_obligation, Self::Erc20Escrow),
+            (addresses.erc20_addresses.payment_obligation, Self::Erc20Payment),
+            (addresses.erc721_addresses.escrow_obligation, Self::Erc721Escrow),
+            (addresses.erc721_addresses.payment_obligation, Self::Erc721Payment),
+            (addresses.erc1155_addresses.escrow_obligation, Self::Erc1155Escrow),
+            (addresses.erc1155_addresses.payment_obligation, Self::Erc1155Payment),
+            (addresses.token_bundle_addresses.escrow_obligation, Self::TokenBundleEscrow),
+            (addresses.token_bundle_addresses.payment_obligation, Self::TokenBundlePayment),
+            (addresses.string_obligation_addresses.obligation, Self::StringObligation),
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Erc20Escrow => "ERC20 escrow",
+            Self::Erc20Payment => "ERC20 payment",
+            Self::Erc721Escrow => "ERC721 escrow",
+            Self::Erc721Payment => "ERC721 payment",
+            Self::Erc1155Escrow => "ERC1155 escrow",
+            Self::Erc1155Payment => "ERC1155 payment",
+            Self::TokenBundleEscrow => "token bundle escrow",
+            Self::TokenBundlePayment => "token bundle payment",
+            Self::StringObligation => "string obligation",
+        }
+    }
+}
+
+sol! {
+    #[sol(rpc)]
+    interface IObligation {
+        function ATTESTATION_SCHEMA() external view returns (bytes32);
+    }
+}
+
+/// Find the obligation contract that makes attestations with `schema`
+///
+/// Each obligation contract registers its own schema, so the schema identifies
+/// the contract even after an upgrade moves the obligation to a new address. The
+/// contracts in the address config are checked first; failing those, `attester`
+/// is, since obligation contracts attest their own obligations, which finds the
+/// older deployment an attestation came from. Returns None when neither uses the
+/// schema, meaning the attestation isn't an alkahest obligation. Errors reaching
+/// the node are returned rather than taken as a contract not matching.
+pub async fn obligation_contract_for_schema<P: Provider>(
+    provider: &P,
+    schema: FixedBytes<32>,
+    attester: Address,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> eyre::Result<Option<Address>> {
+    let candidates = ObligationKind::known(addresses)
+        .into_iter()
+        .map(|(address, _)| address)
+        .chain(std::iter::once(attester));
+    for address in candidates {
+        let contract_schema = match IObligation::new(address, provider)
+            .ATTESTATION_SCHEMA()
+            .call()
+            .await
+        {
+            Ok(contract_schema) => contract_schema,
+            // Contracts without the getter, such as a plain EOA attester, don't match
+            Err(e) if is_missing_getter(&e) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if contract_schema == schema {
+            return Ok(Some(address));
+        }
+    }
+    Ok(None)
+}
+
+macro_rules! arbiter_names {
+    ($arbiters:expr, $address:expr, [$($field:ident),* $(,)?]) => {
+        [$((stringify!($field), $arbiters.$field)),*]
+            .into_iter()
+            .find(|(_, arbiter)| *arbiter == $address)
+            .map(|(name, _)| name)
+    };
+}
+
+/// Name of a known arbiter contract, as it appears in the address config
+///
+/// Payment obligation contracts double as arbiters for the payments they record,
+/// so they are named too.
+pub fn arbiter_name(
+    arbiter: Address,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> Option<&'static str> {
+    let payment_obligations = [
+        (addresses.erc20_addresses.payment_obligation, "erc20_payment_obligation"),
+        (addresses.erc721_addresses.payment_obligation, "erc721_payment_obligation"),
+        (addresses.erc1155_addresses.payment_obligation, "erc1155_payment_obligation"),
+        (
+            addresses.token_bundle_addresses.payment_obligation,
+            "token_bundle_payment_obligation",
+        ),
+        (addresses.string_obligation_addresses.obligation, "string_obligation"),
+    ];
+    if let Some((_, name)) = payment_obligations
+        .into_iter()
+        .find(|(address, _)| *address == arbiter)
+    {
+        return Some(name);
+    }
+
+    arbiter_names!(
+        addresses.arbiters_addresses,
+        arbiter,
+        [
+            trusted_party_arbiter,
+            trivial_arbiter,
+            specific_attestation_arbiter,
+            trusted_oracle_arbiter,
+            intrinsics_arbiter,
+            intrinsics_arbiter_2,
+            any_arbiter,
+            all_arbiter,
+            uid_arbiter,
+            recipient_arbiter,
+            not_arbiter,
+            attester_arbiter_composing,
+            attester_arbiter_non_composing,
+            expiration_time_after_arbiter_composing,
+            expiration_time_before_arbiter_composing,
+            expiration_time_equal_arbiter_composing,
+            recipient_arbiter_composing,
+            ref_uid_arbiter_composing,
+            revocable_arbiter_composing,
+            schema_arbiter_composing,
+            time_after_arbiter_composing,
+            time_before_arbiter_composing,
+            time_equal_arbiter_composing,
+            uid_arbiter_composing,
+            erc20_payment_fulfillment_arbiter,
+            erc721_payment_fulfillment_arbiter,
+            erc1155_payment_fulfillment_arbiter,
+            token_bundle_payment_fulfillment_arbiter,
+            expiration_time_after_arbiter_non_composing,
+            expiration_time_before_arbiter_non_composing,
+            expiration_time_equal_arbiter_non_composing,
+            recipient_arbiter_non_composing,
+            ref_uid_arbiter_non_composing,
+            revocable_arbiter_non_composing,
+            schema_arbiter_non_composing,
+            time_after_arbiter_non_composing,
+            time_before_arbiter_non_composing,
+            time_equal_arbiter_non_composing,
+            uid_arbiter_non_composing,
+            confirmation_arbiter,
+            confirmation_arbiter_composing,
+            revocable_confirmation_arbiter,
+            revocable_confirmation_arbiter_composing,
+            unrevocable_confirmation_arbiter,
+        ]
+    )
+}
+
+/// Render an attestation as a human-readable, multi-line summary
+///
+/// `now` is the timestamp expiration is judged against, normally that of the
+/// latest block. Obligation data is decoded when the attester is a known
+/// obligation contract; otherwise only its size is shown. Escrow demands are
+/// decoded as `decode_demand` decodes them, so arbiters with a registered
+/// decoder show its result.
+pub fn explain(
+    py: Python<'_>,
+    attestation: &IEAS::Attestation,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+    now: u64,
+) -> PyResult<String> {
+    let kind = ObligationKind::of(attestation.attester, addresses);
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Attestation {}", attestation.uid);
+    let _ = writeln!(out, "  type:        {}", kind.map_or("unknown", ObligationKind::label));
+    let _ = writeln!(out, "  schema:      {}", attestation.schema);
+    let _ = writeln!(out, "  attester:    {:?}", attestation.attester);
+    let _ = writeln!(out, "  recipient:   {:?}", attestation.recipient);
+    if attestation.refUID != FixedBytes::<32>::ZERO {
+        let _ = writeln!(out, "  ref UID:     {}", attestation.refUID);
+    }
+    let _ = writeln!(out, "  created:     {}", attestation.time);
+    let expiration = match attestation.expirationTime {
+        0 => "never".to_string(),
+        t if t <= now => format!("{} (expired)", t),
+        t => format!("{} (in {}s)", t, t - now),
+    };
+    let _ = writeln!(out, "  expiration:  {}", expiration);
+    let revocation = match (attestation.revocationTime, attestation.revocable) {
+        (0, true) => "not revoked".to_string(),
+        (0, false) => "not revoked (irrevocable)".to_string(),
+        (t, _) => format!("revoked at {}", t),
+    };
+    let _ = writeln!(out, "  revocation:  {}", revocation);
+
+    let fields = match kind {
+        Some(kind) => describe_data(py, kind, &attestation.data, addresses)?,
+        None => None,
+    };
+    match fields {
+        Some(lines) => {
+            for (label, value) in lines {
+                let _ = writeln!(out, "  {:<12} {}", format!("{}:", label), value);
+            }
+        }
+        None => {
+            let _ = writeln!(out, "  data:        {} bytes", attestation.data.len());
+        }
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
+/// Decode obligation data into labelled fields, for the kinds the binding can decode
+fn describe_data(
+    py: Python<'_>,
+    kind: ObligationKind,
+    data: &[u8],
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> PyResult<Option<Vec<(&'static str, String)>>> {
+    let arbiter = |arbiter: Address| match arbiter_name(arbiter, addresses) {
+        Some(name) => format!("{:?} ({})", arbiter, name),
+        None => format!("{:?}", arbiter),
+    };
+    let demand = |escrow_arbiter: Address, demand: &[u8]| -> PyResult<String> {
+        let escrow_arbiter = format!("{:?}", escrow_arbiter);
+        Ok(match decode_demand(py, &escrow_arbiter, demand, addresses)? {
+            Demand::Payment(payment) => describe_payment(&payment),
+            Demand::Custom(decoded) => decoded.bind(py).repr()?.to_string(),
+            Demand::Raw(demand) => format!("{} bytes", demand.len()),
+        })
+    };
+
+    let fields = match kind {
+        ObligationKind::Erc20Escrow => {
+            let Ok(d) = ERC20EscrowObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("amount", d.amount.to_string()),
+                ("arbiter", arbiter(d.arbiter)),
+                ("demand", demand(d.arbiter, &d.demand)?),
+            ]
+        }
+        ObligationKind::Erc20Payment => {
+            let Ok(d) = ERC20PaymentObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("amount", d.amount.to_string()),
+                ("payee", format!("{:?}", d.payee)),
+            ]
+        }
+        ObligationKind::Erc721Escrow => {
+            let Ok(d) = ERC721EscrowObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("token id", d.tokenId.to_string()),
+                ("arbiter", arbiter(d.arbiter)),
+                ("demand", demand(d.arbiter, &d.demand)?),
+            ]
+        }
+        ObligationKind::Erc721Payment => {
+            let Ok(d) = ERC721PaymentObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("token id", d.tokenId.to_string()),
+                ("payee", format!("{:?}", d.payee)),
+            ]
+        }
+        ObligationKind::Erc1155Escrow => {
+            let Ok(d) = ERC1155EscrowObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("token id", d.tokenId.to_string()),
+                ("amount", d.amount.to_string()),
+                ("arbiter", arbiter(d.arbiter)),
+                ("demand", demand(d.arbiter, &d.demand)?),
+            ]
+        }
+        ObligationKind::Erc1155Payment => {
+            let Ok(d) = ERC1155PaymentObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![
+                ("token", format!("{:?}", d.token)),
+                ("token id", d.tokenId.to_string()),
+                ("amount", d.amount.to_string()),
+                ("payee", format!("{:?}", d.payee)),
+            ]
+        }
+        ObligationKind::StringObligation => {
+            let Ok(d) = StringObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            vec![("item", d.item)]
+        }
+        ObligationKind::TokenBundleEscrow => {
+            let Ok(d) = TokenBundleEscrowObligation::ObligationData::abi_decode(data) else {
+                return Ok(None);
+            };
+            let mut fields = Vec::new();
+            for (token, amount) in d.erc20Tokens.iter().zip(&d.erc20Amounts) {
+                fields.push(("erc20", format!("{:?} amount {}", token, amount)));
+            }
+            for (token, id) in d.erc721Tokens.iter().zip(&d.erc721TokenIds) {
+                fields.push(("erc721", format!("{:?} id {}", token, id)));
+            }
+            for ((token, id), amount) in d
+                .erc1155Tokens
+                .iter()
+                .zip(&d.erc1155TokenIds)
+                .zip(&d.erc1155Amounts)
+            {
+                fields.push(("erc1155", format!("{:?} id {} amount {}", token, id, amount)));
+            }
+            fields.push(("arbiter", arbiter(d.arbiter)));
+            fields.push(("demand", demand(d.arbiter, &d.demand)?));
+            fields
+        }
+        ObligationKind::TokenBundlePayment => return Ok(None),
+    };
+    Ok(Some(fields))
+}
+
+/// Describe a payment demand on one line
+fn describe_payment(payment: &PaymentDemand) -> String {
+    match payment {
+        PaymentDemand::Erc20(d) => format!("ERC20 payment of {} {} to {}", d.amount, d.token, d.payee),
+        PaymentDemand::Erc721(d) => {
+            format!("ERC721 payment of {} id {} to {}", d.token, d.token_id, d.payee)
+        }
+        PaymentDemand::Erc1155(d) => format!(
+            "ERC1155 payment of {} {} id {} to {}",
+            d.amount, d.token, d.token_id, d.payee
+        ),
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    future::Future,
+    task::{Context, Poll},
+};
+
+use alloy::{
+    primitives::U64,
+    rpc::json_rpc::{Id, RequestPacket, Response, ResponsePacket, ResponsePayload},
+    transports::{TransportError, TransportFut},
+};
+use pyo3::{Bound, IntoPyObject, PyAny, PyResult, Python};
+use tower::{Layer, Service};
+
+tokio::task_local! {
+    static GAS_MULTIPLIER: f64;
+}
+
+/// Run `fut` with the gas estimates it requests scaled by `gas_multiplier`
+///
+/// Only estimates requested while polling `fut`, through a provider whose
+/// transport has a [`GasMultiplierLayer`], are scaled. The gas filler then uses
+/// the scaled estimate as the transaction's gas limit, so a write wrapped in this
+/// sends with the larger limit whether the binding or alkahest-rs built it. Reads
+/// and estimates outside a write are left as the node returned them.
+pub fn with_gas_multiplier<F: Future>(
+    gas_multiplier: f64,
+    fut: F,
+) -> impl Future<Output = F::Output> {
+    GAS_MULTIPLIER.scope(gas_multiplier, fut)
+}
+
+/// `future_into_py` for a write method, running `fut` under [`with_gas_multiplier`]
+pub fn future_into_py<F, T>(
+    py: Python<'_>,
+    gas_multiplier: f64,
+    fut: F,
+) -> PyResult<Bound<'_, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'py> IntoPyObject<'py> + Send + 'static,
+{
+    pyo3_async_runtimes::tokio::future_into_py(py, with_gas_multiplier(gas_multiplier, fut))
+}
+
+/// Transport layer scaling `eth_estimateGas` results inside [`with_gas_multiplier`]
+///
+/// It sits beneath the wallet provider rather than being a filler on top, so the
+/// provider keeps the type the alkahest-rs modules are built on and their atomic
+/// calls pick the multiplier up unchanged.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GasMultiplierLayer;
+
+impl<S> Layer<S> for GasMultiplierLayer {
+    type Service = GasMultiplierService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GasMultiplierService { inner }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GasMultiplierService<S> {
+    inner: S,
+}
+
+impl<S> Service<RequestPacket> for GasMultiplierService<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+    S::Future: Send + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        // Read while still in the task that made the request; the response future
+        // is not guaranteed to be polled there
+        let gas_multiplier = GAS_MULTIPLIER.try_with(|m| *m).ok().filter(|m| *m > 1.0);
+        let estimates: Vec<Id> = match gas_multiplier {
+            Some(_) => request
+                .requests()
+                .iter()
+                .filter(|request| request.method() == "eth_estimateGas")
+                .map(|request| request.id().clone())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = response.await?;
+            if let Some(gas_multiplier) = gas_multiplier {
+                for response in responses_mut(&mut response) {
+                    if estimates.contains(&response.id) {
+                        scale_estimate(response, gas_multiplier)?;
+                    }
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+fn responses_mut(packet: &mut ResponsePacket) -> &mut [Response] {
+    match packet {
+        ResponsePacket::Single(response) => std::slice::from_mut(response),
+        ResponsePacket::Batch(responses) => responses,
+    }
+}
+
+fn scale_estimate(response: &mut Response, gas_multiplier: f64) -> Result<(), TransportError> {
+    // Errors are passed through for the provider to report
+    let ResponsePayload::Success(result) = &mut response.payload else {
+        return Ok(());
+    };
+    let estimate: U64 = serde_json::from_str(result.get())
+        .map_err(|e| TransportError::deser_err(e, result.get()))?;
+    let scaled = U64::from((estimate.to::<u64>() as f64 * gas_multiplier).ceil() as u64);
+    *result = serde_json::value::to_raw_value(&scaled).map_err(TransportError::ser_err)?;
+    Ok(())
+}
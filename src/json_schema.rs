@@ -0,0 +1,380 @@
+use serde_json::{Map, Value};
+
+/// Keywords `validate` enforces
+const SUPPORTED_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "minItems",
+    "maxItems",
+    "minLength",
+    "maxLength",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+];
+
+/// Keywords that only annotate a schema and never affect whether a value is valid
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Check that a schema only uses keywords `validate` enforces, returning every
+/// unsupported keyword found
+///
+/// A keyword `validate` doesn't know would otherwise be skipped, letting through
+/// values the schema rejects, so schemas using `$ref`, `pattern`, `format` and
+/// the like are refused instead. Each error is prefixed with the JSON pointer of
+/// the offending subschema.
+pub fn check_schema(schema: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    check_schema_at(schema, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_schema_at(schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(_) => return,
+        Value::Object(schema) => schema,
+        _ => {
+            errors.push(format!("{}: schema must be an object", display_path(path)));
+            return;
+        }
+    };
+
+    for (keyword, subschema) in schema {
+        let keyword_path = format!("{}/{}", path, escape_pointer(keyword));
+        match keyword.as_str() {
+            "properties" => {
+                if let Value::Object(properties) = subschema {
+                    for (key, property) in properties {
+                        check_schema_at(
+                            property,
+                            &format!("{}/{}", keyword_path, escape_pointer(key)),
+                            errors,
+                        );
+                    }
+                }
+            }
+            "additionalProperties" | "items" | "not" => {
+                if subschema.is_array() {
+                    errors.push(format!(
+                        "{}: \"{}\" must be a single schema",
+                        display_path(path),
+                        keyword
+                    ));
+                } else {
+                    check_schema_at(subschema, &keyword_path, errors);
+                }
+            }
+            "allOf" | "anyOf" | "oneOf" => {
+                if let Value::Array(subschemas) = subschema {
+                    for (i, subschema) in subschemas.iter().enumerate() {
+                        check_schema_at(subschema, &format!("{}/{}", keyword_path, i), errors);
+                    }
+                }
+            }
+            keyword if SUPPORTED_KEYWORDS.contains(&keyword) => {}
+            keyword if ANNOTATION_KEYWORDS.contains(&keyword) => {}
+            keyword => errors.push(format!(
+                "{}: unsupported keyword \"{}\"",
+                display_path(path),
+                keyword
+            )),
+        }
+    }
+}
+
+/// Validate a JSON value against a JSON Schema, returning every violation found
+///
+/// Supports the structural subset of JSON Schema used to describe obligation
+/// payloads: `type`, `enum`, `const`, `properties`, `required`,
+/// `additionalProperties`, `items`, `minItems`/`maxItems`, `minLength`/`maxLength`,
+/// `minimum`/`maximum`, `exclusiveMinimum`/`exclusiveMaximum`, `allOf`, `anyOf`,
+/// `oneOf` and `not`. Check the schema with `check_schema` first, since other
+/// keywords are not enforced here. Each error is prefixed with the JSON pointer
+/// of the offending value.
+pub fn validate(schema: &Value, value: &Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", display_path(path)));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => {
+            errors.push(format!("{}: schema must be an object", display_path(path)));
+            return;
+        }
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        if !allowed.iter().any(|t| is_type(value, t)) {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                display_path(path),
+                allowed.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!(
+                "{}: {} is not one of {}",
+                display_path(path),
+                value,
+                Value::Array(options.clone())
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            errors.push(format!(
+                "{}: expected {}, got {}",
+                display_path(path),
+                expected,
+                value
+            ));
+        }
+    }
+
+    match value {
+        Value::Object(object) => validate_object(schema, object, path, errors),
+        Value::Array(items) => validate_array(schema, items, path, errors),
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if len < min {
+                    errors.push(format!(
+                        "{}: string shorter than {} characters",
+                        display_path(path),
+                        min
+                    ));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len > max {
+                    errors.push(format!(
+                        "{}: string longer than {} characters",
+                        display_path(path),
+                        max
+                    ));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or(f64::NAN);
+            let bounds: [(&str, &str, fn(f64, f64) -> bool); 4] = [
+                ("minimum", "less than", |n, b| n < b),
+                ("maximum", "greater than", |n, b| n > b),
+                ("exclusiveMinimum", "less than or equal to", |n, b| n <= b),
+                ("exclusiveMaximum", "greater than or equal to", |n, b| n >= b),
+            ];
+            for (keyword, relation, violates) in bounds {
+                if let Some(bound) = schema.get(keyword).and_then(Value::as_f64) {
+                    if violates(n, bound) {
+                        errors.push(format!(
+                            "{}: {} is {} {}",
+                            display_path(path),
+                            n,
+                            relation,
+                            bound
+                        ));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Array(subschemas)) = schema.get("allOf") {
+        for subschema in subschemas {
+            validate_at(subschema, value, path, errors);
+        }
+    }
+
+    if let Some(Value::Array(subschemas)) = schema.get("anyOf") {
+        if !subschemas.iter().any(|s| matches(s, value, path)) {
+            errors.push(format!(
+                "{}: does not match any schema in anyOf",
+                display_path(path)
+            ));
+        }
+    }
+
+    if let Some(Value::Array(subschemas)) = schema.get("oneOf") {
+        let matched = subschemas.iter().filter(|s| matches(s, value, path)).count();
+        if matched != 1 {
+            errors.push(format!(
+                "{}: matches {} schemas in oneOf, expected exactly 1",
+                display_path(path),
+                matched
+            ));
+        }
+    }
+
+    if let Some(subschema) = schema.get("not") {
+        if matches(subschema, value, path) {
+            errors.push(format!(
+                "{}: must not match the schema in not",
+                display_path(path)
+            ));
+        }
+    }
+}
+
+fn validate_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(key) {
+                errors.push(format!(
+                    "{}: missing required property \"{}\"",
+                    display_path(path),
+                    key
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, child) in object {
+        let child_path = format!("{}/{}", path, escape_pointer(key));
+        match properties.and_then(|p| p.get(key)) {
+            Some(subschema) => validate_at(subschema, child, &child_path, errors),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => errors.push(format!(
+                    "{}: unexpected property \"{}\"",
+                    display_path(path),
+                    key
+                )),
+                Some(subschema @ Value::Object(_)) => {
+                    validate_at(subschema, child, &child_path, errors)
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+fn validate_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let len = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if len < min {
+            errors.push(format!(
+                "{}: fewer than {} items",
+                display_path(path),
+                min
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if len > max {
+            errors.push(format!("{}: more than {} items", display_path(path), max));
+        }
+    }
+    if let Some(subschema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(subschema, item, &format!("{}/{}", path, i), errors);
+        }
+    }
+}
+
+fn matches(schema: &Value, value: &Value, path: &str) -> bool {
+    let mut errors = Vec::new();
+    validate_at(schema, value, path, &mut errors);
+    errors.is_empty()
+}
+
+fn is_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
@@ -12,11 +12,17 @@ use alkahest_rs::{
         HasAttestation, HasErc1155, HasErc20, HasErc721, HasOracle, HasStringObligation,
         HasTokenBundle, NoExtension, OracleModule, StringObligationModule, TokenBundleModule,
     },
+    types::WalletProvider,
     AlkahestClient,
 };
 use alloy::{
-    primitives::{Address, FixedBytes, Log},
-    rpc::types::TransactionReceipt,
+    network::EthereumWallet,
+    primitives::{Address, Bytes, FixedBytes, Log, U256},
+    providers::{DynProvider, Provider, ProviderBuilder},
+    rpc::{
+        client::ClientBuilder,
+        types::{Filter, TransactionReceipt, TransactionRequest},
+    },
     signers::local::PrivateKeySigner,
     sol_types::SolEvent,
 };
@@ -28,10 +34,13 @@ use clients::{
 use pyo3::{
     pyclass, pymethods, pymodule,
     types::{PyAnyMethods, PyModule, PyModuleMethods},
-    Bound, FromPyObject, PyAny, PyResult, Python,
+    wrap_pyfunction, Bound, FromPyObject, PyAny, PyResult, Python,
 };
 use tokio::runtime::Runtime;
-use types::{DefaultExtensionConfig, EscowClaimedLog};
+use types::{
+    check_gas_multiplier, AttestedLog, DefaultArbiter, DefaultExtensionConfig, EscowClaimedLog,
+    GasMultiplier, LogWithHash,
+};
 
 use crate::{
     clients::{
@@ -43,20 +52,42 @@ use crate::{
             PyOracleAttestation, PyTrustedOracleArbiterDemandData,
         },
         string_obligation::PyStringObligationData,
+        token_bundle::PyTokenBundleEscrowObligationData,
     },
     contract::{
-        PyAttestation, PyAttestationRequest, PyAttestationRequestData, PyAttested,
-        PyRevocationRequest, PyRevocationRequestData, PyRevoked, PyTimestamped,
+        PyAttestation, PyAttestationBuilder, PyAttestationRequest, PyAttestationRequestData,
+        PyAttested, PyRevocationRequest, PyRevocationRequestData, PyRevoked, PyTimestamped,
     },
+    error_handling::map_parse_to_pyerr,
     fixtures::{PyMockERC1155, PyMockERC20, PyMockERC721},
+    nonce_manager::NonceManager,
     types::PyErc20Data,
     utils::{EnvTestManager, PyWalletProvider},
 };
 
+pub mod accounting;
+pub mod arbiters;
+pub mod batch;
+pub mod bulk_decode;
 pub mod clients;
 pub mod contract;
+pub mod demand_decoders;
+pub mod encoding;
 pub mod error_handling;
+pub mod escrow;
+pub mod explain;
 pub mod fixtures;
+pub mod gas;
+pub mod json_schema;
+pub mod logs;
+pub mod nonce_manager;
+pub mod offer;
+pub mod pending;
+pub mod revocation;
+pub mod simulation;
+pub mod terms;
+pub mod tokens;
+pub mod transactions;
 pub mod types;
 pub mod utils;
 
@@ -77,28 +108,86 @@ pub struct PyAlkahestClient {
     attestation: Option<AttestationClient>,
     string_obligation: Option<StringObligationClient>,
     oracle: Option<OracleClient>,
+    nonce_manager: NonceManager,
+    // Shared with the extension clients, which scale their writes by it
+    gas_multiplier: GasMultiplier,
+    // Separate provider for the binding's own reads, when a read RPC was given
+    read_provider: Option<DynProvider>,
+    // Shared with the extension clients, which fall back to it for escrows
+    default_arbiter: DefaultArbiter,
 }
 
 impl PyAlkahestClient {
+    /// Wrap an existing alkahest-rs client
+    ///
+    /// alkahest-rs built its wallet provider without a [`gas::GasMultiplierLayer`],
+    /// so the wrapped client's `gas_multiplier` has no effect on its writes.
     pub fn from_client(client: alkahest_rs::DefaultAlkahestClient) -> Self {
+        let nonce_manager = NonceManager::unshared(client.address);
+        let default_arbiter = DefaultArbiter::default();
+        let gas_multiplier = GasMultiplier::default();
+        let extension_read_provider = client.wallet_provider.clone().erased();
         Self {
             inner: std::sync::Arc::new(client.clone()),
             runtime: None,
             private_key: None, // Not available when creating from existing client
             rpc_url: None,     // Not available when creating from existing client
-            erc20: Some(Erc20Client::new(client.extensions.erc20().clone())),
-            erc721: Some(Erc721Client::new(client.extensions.erc721().clone())),
-            erc1155: Some(Erc1155Client::new(client.extensions.erc1155().clone())),
+            erc20: Some(Erc20Client::new(
+                client.extensions.erc20().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                None,
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
+            erc721: Some(Erc721Client::new(
+                client.extensions.erc721().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
+            erc1155: Some(Erc1155Client::new(
+                client.extensions.erc1155().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
             token_bundle: Some(TokenBundleClient::new(
                 client.extensions.token_bundle().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
             )),
             attestation: Some(AttestationClient::new(
                 client.extensions.attestation().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
             )),
             string_obligation: Some(StringObligationClient::new(
                 client.extensions.string_obligation().clone(),
+                nonce_manager.clone(),
+                gas_multiplier.clone(),
+            )),
+            oracle: Some(OracleClient::new(
+                client.extensions.oracle().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
             )),
-            oracle: Some(OracleClient::new(client.extensions.oracle().clone())),
+            nonce_manager,
+            gas_multiplier,
+            read_provider: None,
+            default_arbiter,
         }
     }
 
@@ -117,6 +206,7 @@ impl PyAlkahestClient {
 
         // The client still has the extension functionality in the inner client,
         // but the Python wrapper doesn't expose it through the .erc20, .erc721, etc. properties
+        let address = client.address;
         Self {
             inner: std::sync::Arc::new(client),
             runtime: None, // Runtime is managed externally in this constructor path
@@ -129,56 +219,341 @@ impl PyAlkahestClient {
             attestation: None, // TODO: Extract if extension_type == "attestation"
             string_obligation: None, // TODO: Extract if extension_type == "string_obligation"
             oracle: None,      // TODO: Extract if extension_type == "oracle"
+            nonce_manager: NonceManager::unshared(address),
+            gas_multiplier: GasMultiplier::default(),
+            read_provider: None,
+            default_arbiter: DefaultArbiter::default(),
         }
     }
-}
 
-#[pymethods]
-impl PyAlkahestClient {
-    #[new]
-    #[pyo3(signature = (private_key, rpc_url, address_config=None))]
-    pub fn __new__(
-        private_key: String,
-        rpc_url: String,
-        address_config: Option<DefaultExtensionConfig>,
-    ) -> PyResult<Self> {
-        let address_config = address_config.map(|x| x.try_into()).transpose()?;
+    /// Get the provider for the binding's own reads and log queries: the read RPC
+    /// when one was configured, otherwise the wallet provider
+    ///
+    /// The extension clients are given the same provider for their reads and
+    /// pre-checks. Reads made inside alkahest-rs modules always use the wallet
+    /// provider, as do the checks that confirm a transaction just sent.
+    fn read_provider(&self) -> PyResult<DynProvider> {
+        match &self.read_provider {
+            Some(provider) => Ok(provider.clone()),
+            None => Ok(self.wallet_provider()?.erased()),
+        }
+    }
 
-        // Convert private_key String to LocalSigner
-        let signer = PrivateKeySigner::from_str(&private_key)
-            .map_err(|e| eyre::eyre!("Failed to parse private key: {}", e))?;
+    /// Get the EAS address the wrapped client's attestation extension was configured with
+    fn eas(&self) -> PyResult<Address> {
+        let client = self.inner.downcast_ref::<AlkahestClient>().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        Ok(client.extensions.attestation().addresses.eas)
+    }
+
+    /// Get the escrow contracts the wrapped client's extensions were configured with
+    fn escrow_contracts(&self) -> PyResult<escrow::EscrowContracts> {
+        let client = self.inner.downcast_ref::<AlkahestClient>().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Escrow extensions are not available in this client",
+            )
+        })?;
+        Ok(escrow::EscrowContracts {
+            eas: client.extensions.erc20().addresses.eas,
+            erc20: client.extensions.erc20().addresses.escrow_obligation,
+            erc721: client.extensions.erc721().addresses.escrow_obligation,
+            erc1155: client.extensions.erc1155().addresses.escrow_obligation,
+            token_bundle: client.extensions.token_bundle().addresses.escrow_obligation,
+        })
+    }
+
+    /// Resolve an optional address config, with the obligation contracts replaced by
+    /// the ones the wrapped client's extensions were configured with
+    fn client_addresses(
+        &self,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<alkahest_rs::DefaultExtensionConfig> {
+        let mut config = escrow::resolve_addresses(addresses)?;
+        if let Some(client) = self.inner.downcast_ref::<AlkahestClient>() {
+            config.erc20_addresses = client.extensions.erc20().addresses.clone();
+            config.erc721_addresses = client.extensions.erc721().addresses.clone();
+            config.erc1155_addresses = client.extensions.erc1155().addresses.clone();
+            config.token_bundle_addresses = client.extensions.token_bundle().addresses.clone();
+            config.string_obligation_addresses =
+                client.extensions.string_obligation().addresses.clone();
+        }
+        Ok(config)
+    }
+
+    /// Wait for the escrows in `buy_attestations` to be claimed, all of them or
+    /// just the first, each watched on the escrow contract that attested it
+    fn wait_for_claims(
+        &self,
+        buy_attestations: Vec<String>,
+        timeout: Option<f64>,
+        from_block: Option<u64>,
+        all: bool,
+    ) -> PyResult<impl std::future::Future<Output = PyResult<Vec<EscowClaimedLog>>> + Send + 'static>
+    {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let provider = self.read_provider()?;
+        if buy_attestations.is_empty() {
+            return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "buy_attestations must not be empty",
+            ));
+        }
+        let buy_attestations = buy_attestations
+            .iter()
+            .map(|uid| uid.parse::<FixedBytes<32>>().map_err(map_parse_to_pyerr))
+            .collect::<PyResult<Vec<_>>>()?;
+        let timeout = timeout.map(check_timeout).transpose()?;
+
+        Ok(async move {
+            let escrows = futures::future::try_join_all(buy_attestations.iter().map(|uid| {
+                let attestation_client = &attestation_client;
+                async move {
+                    let escrow = attestation_client.inner.get_attestation(*uid).await.map_err(
+                        |e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)),
+                    )?;
+                    if escrow.uid == FixedBytes::<32>::ZERO {
+                        return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                            format!("Escrow attestation {} not found", uid),
+                        ));
+                    }
+                    Ok((escrow.attester, *uid))
+                }
+            }))
+            .await?;
+
+            let wait = escrow::wait_for_escrow_claims(&provider, &escrows, from_block, all);
+            let claims = match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, wait).await.map_err(|_| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyTimeoutError, _>(format!(
+                        "Escrows not fulfilled within {} seconds",
+                        timeout.as_secs_f64()
+                    ))
+                })?,
+                None => wait.await,
+            }
+            .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+
+            Ok(claims.iter().map(EscowClaimedLog::from).collect())
+        })
+    }
 
+    /// Connect the alkahest-rs client for `signer` and wrap it, sequencing writes
+    /// through `nonce_manager`
+    ///
+    /// The wallet provider is built here rather than by alkahest-rs, so that its
+    /// transport carries the [`gas::GasMultiplierLayer`] every write goes through.
+    pub(crate) fn connect(
+        signer: PrivateKeySigner,
+        private_key: Option<String>,
+        nonce_manager: NonceManager,
+        rpc_url: String,
+        address_config: Option<alkahest_rs::DefaultExtensionConfig>,
+        gas_multiplier: f64,
+        read_rpc_url: Option<String>,
+    ) -> PyResult<Self> {
         // Create a shared runtime
         let runtime = std::sync::Arc::new(Runtime::new()?);
 
         // Since new is async, we must block_on it
         let client: alkahest_rs::DefaultAlkahestClient = runtime.clone().block_on(async {
-            alkahest_rs::AlkahestClient::with_base_extensions(signer.clone(), rpc_url.clone(), address_config).await
+            let transport = ClientBuilder::default()
+                .layer(gas::GasMultiplierLayer)
+                .connect(&rpc_url)
+                .await?;
+            let wallet_provider: WalletProvider = ProviderBuilder::new()
+                .wallet(EthereumWallet::from(signer.clone()))
+                .connect_client(transport);
+            alkahest_rs::AlkahestClient::with_wallet_provider(
+                signer.clone(),
+                wallet_provider,
+                address_config,
+            )
+            .await
         })?;
 
+        let read_provider = match read_rpc_url {
+            Some(read_rpc_url) if read_rpc_url != rpc_url => Some(
+                runtime
+                    .block_on(ProviderBuilder::new().connect(&read_rpc_url))
+                    .map_err(|e| eyre::eyre!("Failed to connect to {}: {}", read_rpc_url, e))?
+                    .erased(),
+            ),
+            _ => None,
+        };
+
+        // Permits are only signed for clients that have a real key
+        let erc20_signer = private_key.as_ref().map(|_| signer.clone());
+        let default_arbiter = DefaultArbiter::default();
+        let gas_multiplier = GasMultiplier::new(gas_multiplier);
+        let extension_read_provider = read_provider
+            .clone()
+            .unwrap_or_else(|| client.wallet_provider.clone().erased());
         let client = Self {
             inner: std::sync::Arc::new(client.clone()),
             runtime: Some(runtime.clone()),
-            private_key: Some(private_key.clone()),
+            private_key,
             rpc_url: Some(rpc_url.clone()),
-            erc20: Some(Erc20Client::new(client.extensions.erc20().clone())),
-            erc721: Some(Erc721Client::new(client.extensions.erc721().clone())),
-            erc1155: Some(Erc1155Client::new(client.extensions.erc1155().clone())),
+            erc20: Some(Erc20Client::new(
+                client.extensions.erc20().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                erc20_signer,
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
+            erc721: Some(Erc721Client::new(
+                client.extensions.erc721().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
+            erc1155: Some(Erc1155Client::new(
+                client.extensions.erc1155().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
+            )),
             token_bundle: Some(TokenBundleClient::new(
                 client.extensions.token_bundle().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
             )),
             attestation: Some(AttestationClient::new(
                 client.extensions.attestation().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+                default_arbiter.clone(),
+                gas_multiplier.clone(),
             )),
             string_obligation: Some(StringObligationClient::new(
                 client.extensions.string_obligation().clone(),
+                nonce_manager.clone(),
+                gas_multiplier.clone(),
             )),
-            oracle: Some(OracleClient::new(client.extensions.oracle().clone())),
+            oracle: Some(OracleClient::new(
+                client.extensions.oracle().clone(),
+                nonce_manager.clone(),
+                client.wallet_provider.clone(),
+                extension_read_provider.clone(),
+            )),
+            nonce_manager,
+            gas_multiplier,
+            read_provider,
+            default_arbiter,
         };
 
         Ok(client)
     }
 
+    /// Get the wallet provider of the wrapped client, whichever extension set it has
+    fn wallet_provider(&self) -> PyResult<WalletProvider> {
+        if let Some(client) = self.inner.downcast_ref::<AlkahestClient>() {
+            Ok(client.wallet_provider.clone())
+        } else if let Some(client) =
+            self.inner.downcast_ref::<alkahest_rs::AlkahestClient<NoExtension>>()
+        {
+            Ok(client.wallet_provider.clone())
+        } else {
+            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "Unknown client type",
+            ))
+        }
+    }
+}
+
+#[pymethods]
+impl PyAlkahestClient {
+    #[new]
+    #[pyo3(signature = (
+        private_key,
+        rpc_url=None,
+        address_config=None,
+        gas_multiplier=1.0,
+        *,
+        read_rpc_url=None,
+        write_rpc_url=None
+    ))]
+    pub fn __new__(
+        private_key: String,
+        rpc_url: Option<String>,
+        address_config: Option<DefaultExtensionConfig>,
+        gas_multiplier: f64,
+        read_rpc_url: Option<String>,
+        write_rpc_url: Option<String>,
+    ) -> PyResult<Self> {
+        let gas_multiplier = check_gas_multiplier(gas_multiplier)?;
+        let address_config = address_config.map(|x| x.try_into()).transpose()?;
+
+        // Transactions go through `write_rpc_url`; the binding's own reads and log
+        // queries go through `read_rpc_url`. Either defaults to `rpc_url`.
+        let rpc_url = write_rpc_url.or(rpc_url).ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Either rpc_url or write_rpc_url is required",
+            )
+        })?;
+
+        // Convert private_key String to LocalSigner
+        let signer = PrivateKeySigner::from_str(&private_key)
+            .map_err(|e| eyre::eyre!("Failed to parse private key: {}", e))?;
+        let nonce_manager = NonceManager::for_signer(&rpc_url, signer.address());
+
+        Self::connect(
+            signer,
+            Some(private_key),
+            nonce_manager,
+            rpc_url,
+            address_config,
+            gas_multiplier,
+            read_rpc_url,
+        )
+    }
+
+    /// Create a client that can read but not send transactions, for indexers and
+    /// dashboards that have no key
+    ///
+    /// Every read and query method works as usual. Methods that send transactions
+    /// raise `ReadOnlyClientError` before anything is sent, as do methods that default
+    /// to the signer's own address, such as `my_open_escrows`, since the client has
+    /// none.
+    #[staticmethod]
+    #[pyo3(signature = (rpc_url, address_config=None, *, read_rpc_url=None))]
+    pub fn read_only(
+        rpc_url: String,
+        address_config: Option<DefaultExtensionConfig>,
+        read_rpc_url: Option<String>,
+    ) -> PyResult<Self> {
+        let address_config = address_config.map(|x| x.try_into()).transpose()?;
+        // alkahest-rs always builds a wallet, so give it a throwaway key that
+        // never signs anything
+        let signer = PrivateKeySigner::random();
+        let nonce_manager = NonceManager::read_only(signer.address());
+
+        Self::connect(
+            signer,
+            None,
+            nonce_manager,
+            rpc_url,
+            address_config,
+            1.0,
+            read_rpc_url,
+        )
+    }
+
     /// List available extensions
     pub fn list_extensions(&self) -> Vec<String> {
         vec![
@@ -312,6 +687,550 @@ impl PyAlkahestClient {
         })
     }
 
+    /// Follow `ref_uid` links from an attestation, returning the chain starting with
+    /// the attestation itself
+    ///
+    /// The walk stops at an attestation with no reference, a reference that doesn't
+    /// exist, after `max_depth` attestations, or when a reference points back into
+    /// the chain.
+    #[pyo3(signature = (uid, max_depth=10))]
+    pub fn trace_obligation_chain<'py>(
+        &self,
+        py: Python<'py>,
+        uid: String,
+        max_depth: usize,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+
+        let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut chain: Vec<PyAttestation> = Vec::new();
+            let mut seen = std::collections::HashSet::new();
+            let mut next = uid;
+
+            while chain.len() < max_depth && next != FixedBytes::<32>::ZERO && seen.insert(next) {
+                let attestation = attestation_client
+                    .inner
+                    .get_attestation(next)
+                    .await
+                    .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+                if attestation.uid == FixedBytes::<32>::ZERO {
+                    if chain.is_empty() {
+                        return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                            format!("Attestation {} not found", next),
+                        ));
+                    }
+                    break;
+                }
+
+                next = attestation.refUID;
+                chain.push(attestation.into());
+            }
+
+            Ok(chain)
+        })
+    }
+
+    /// Fetch an attestation and describe it in a human-readable, multi-line string
+    ///
+    /// The obligation type is classified from the attester, its data decoded with the
+    /// matching obligation type, and known arbiters are named. Expiration is judged
+    /// against the latest block. `addresses` is used to name arbiters and decode
+    /// demands, and defaults to the alkahest-rs default deployment.
+    #[pyo3(signature = (uid, addresses=None))]
+    pub fn explain_attestation<'py>(
+        &self,
+        py: Python<'py>,
+        uid: String,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let addresses = self.client_addresses(addresses)?;
+        let provider = self.read_provider()?;
+        let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let attestation = attestation_client
+                .inner
+                .get_attestation(uid)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Attestation {} not found", uid),
+                ));
+            }
+            let now = escrow::latest_block_timestamp(&provider)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            Python::with_gil(|py| explain::explain(py, &attestation, &addresses, now))
+        })
+    }
+
+    /// Find the obligation contract that produced an attestation, by its schema
+    ///
+    /// Upgrading an obligation contract deploys it at a new address with a new
+    /// schema, so attestations made by an older deployment may not decode with the
+    /// current decoders. The returned address can be compared against the address
+    /// config to tell whether an attestation comes from the configured contracts.
+    /// Raises ValueError if no obligation contract uses the attestation's schema.
+    #[pyo3(signature = (uid, addresses=None))]
+    pub fn obligation_contract_of<'py>(
+        &self,
+        py: Python<'py>,
+        uid: String,
+        addresses: Option<DefaultExtensionConfig>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let addresses = self.client_addresses(addresses)?;
+        let provider = self.read_provider()?;
+        let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let attestation = attestation_client
+                .inner
+                .get_attestation(uid)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Attestation {} not found", uid),
+                ));
+            }
+            let contract = explain::obligation_contract_for_schema(
+                &provider,
+                attestation.schema,
+                attestation.attester,
+                &addresses,
+            )
+            .await
+            .map_err(error_handling::map_eyre_to_pyerr)?
+            .ok_or_else(|| {
+                pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "No obligation contract uses schema {} of attestation {}",
+                    attestation.schema, uid
+                ))
+            })?;
+            Ok(format!("{:?}", contract))
+        })
+    }
+
+    /// Check that a fulfillment attestation references the given escrow
+    ///
+    /// An escrow's arbiter decides whether a fulfillment is acceptable, but a
+    /// fulfillment can claim to be for any escrow, so check that its `refUID` is
+    /// `buy_attestation` before acting on it. Raises ValueError if the fulfillment
+    /// doesn't exist.
+    ///
+    /// Only meaningful for obligations that set a `refUID`, such as string
+    /// obligations made with `ref_uid`. Payment obligations leave it zero, so a
+    /// payment always returns False here; its link to an escrow is the
+    /// `EscrowClaimed` log emitted when the escrow is collected.
+    pub fn verify_fulfillment_references<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+        fulfillment: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let buy_attestation: FixedBytes<32> = buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+        let fulfillment: FixedBytes<32> = fulfillment.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let attestation = attestation_client
+                .inner
+                .get_attestation(fulfillment)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    format!("Fulfillment attestation {} not found", fulfillment),
+                ));
+            }
+            Ok(attestation.refUID == buy_attestation)
+        })
+    }
+
+    /// Get the address entitled to reclaim an escrow once it expires
+    ///
+    /// Escrow attestations are issued by the escrow obligation contract itself, so
+    /// their `attester` is always that contract. The depositor is the attestation's
+    /// `recipient`, and `reclaim_expired` always returns the escrowed tokens to it,
+    /// whoever submits the reclaim.
+    pub fn escrow_depositor<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let escrow = attestation_client
+                .inner
+                .get_attestation(buy_attestation)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if escrow.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Escrow attestation {} not found",
+                    buy_attestation
+                )));
+            }
+            Ok(format!("{:?}", escrow.recipient))
+        })
+    }
+
+    /// Seconds until an escrow expires, judged against the latest block's timestamp
+    ///
+    /// Negative once the escrow has expired and can be reclaimed with
+    /// `reclaim_expired`, and None if it never expires. Using the chain's clock
+    /// rather than the local one matches how the escrow contract judges expiry.
+    pub fn time_to_expiry<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let provider = self.read_provider()?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let escrow = attestation_client
+                .inner
+                .get_attestation(buy_attestation)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if escrow.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Escrow attestation {} not found",
+                    buy_attestation
+                )));
+            }
+            if escrow.expirationTime == 0 {
+                return Ok(None);
+            }
+            let now = escrow::latest_block_timestamp(&provider)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            Ok(Some(escrow.expirationTime as i64 - now as i64))
+        })
+    }
+
+    /// Check that an escrow's arbiter is one of `trusted_arbiters` before fulfilling it
+    ///
+    /// An escrow only releases to a fulfillment its arbiter accepts, so an escrow
+    /// naming an arbiter that never accepts anything locks the fulfiller's payment
+    /// for nothing. Raises `ValueError` if the attestation isn't an escrow from one
+    /// of the client's escrow contracts.
+    pub fn is_arbiter_trusted<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+        trusted_arbiters: Vec<String>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+        let trusted_arbiters = trusted_arbiters
+            .iter()
+            .map(|arbiter| arbiter.parse::<Address>().map_err(map_parse_to_pyerr))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let escrow = alkahest_rs::contracts::IEAS::new(contracts.eas, &provider)
+                .getAttestation(buy_attestation)
+                .call()
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            let obligation = contracts
+                .token_type(escrow.attester)
+                .and_then(|token_type| escrow::EscrowObligation::decode(token_type, &escrow.data))
+                .ok_or_else(|| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Attestation {} is not a known escrow",
+                        buy_attestation
+                    ))
+                })?;
+            let arbiter: Address = obligation.arbiter().parse().map_err(map_parse_to_pyerr)?;
+            Ok(trusted_arbiters.contains(&arbiter))
+        })
+    }
+
+    /// Summarize a completed barter for bookkeeping, from the escrow and the claim
+    /// returned by `wait_for_fulfillment`
+    ///
+    /// Returns a dict with the `buyer` who made the escrow and the `counterparty`
+    /// who fulfilled it, what the buyer `gave` (the decoded escrow, with
+    /// `gave_type` its token type) and what they `received` (the decoded
+    /// fulfillment, with `received_type` its kind), plus the claim's block and
+    /// transaction. A fulfillment from an obligation contract the binding has no
+    /// type for is given as raw bytes.
+    pub fn barter_result<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+        claim: EscowClaimedLog,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let addresses = self.client_addresses(None)?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+        let fulfillment: FixedBytes<32> = claim.fulfillment.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let eas = alkahest_rs::contracts::IEAS::new(contracts.eas, &provider);
+            let (escrow, fulfillment) = tokio::try_join!(
+                eas.getAttestation(buy_attestation).call(),
+                eas.getAttestation(fulfillment).call(),
+            )
+            .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if escrow.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Escrow attestation {} not found",
+                    buy_attestation
+                )));
+            }
+            accounting::barter_result(&escrow, &fulfillment, claim, &contracts, &addresses)
+        })
+    }
+
+    /// Describe an escrow as a JSON offer to share off-chain, such as in a chat or
+    /// an orderbook
+    ///
+    /// The offer holds the escrow UID and contract, the chain ID, the token type and
+    /// escrowed assets, the arbiter and raw demand with a `demand_summary` decoded
+    /// as `decode_demand` on escrow data does, and the expiration time (null if it
+    /// never expires). Read one back with `import_offer`.
+    pub fn export_offer<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let addresses = self.client_addresses(None)?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let (escrow, chain_id) = tokio::try_join!(
+                alkahest_rs::contracts::IEAS::new(contracts.eas, &provider)
+                    .getAttestation(buy_attestation)
+                    .call(),
+                provider.get_chain_id(),
+            )
+            .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            let token_type = contracts.token_type(escrow.attester);
+            let obligation = token_type
+                .and_then(|token_type| escrow::EscrowObligation::decode(token_type, &escrow.data))
+                .ok_or_else(|| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Attestation {} is not a known escrow",
+                        buy_attestation
+                    ))
+                })?;
+            Python::with_gil(|py| {
+                let demand = demand_decoders::decode_demand(
+                    py,
+                    obligation.arbiter(),
+                    obligation.demand(),
+                    &addresses,
+                )?;
+                let offer = offer::offer_json(
+                    py,
+                    chain_id,
+                    &escrow,
+                    token_type.unwrap_or_default(),
+                    &obligation,
+                    &demand,
+                )?;
+                Ok(offer.to_string())
+            })
+        })
+    }
+
+    /// Describe an escrow completely: its token type and escrowed assets, its
+    /// arbiter by address and name, its decoded demand, expiration, depositor and
+    /// status
+    ///
+    /// Demands are decoded as `decode_demand` on escrow data does, so arbiters with
+    /// a registered demand decoder get their decoder's result.
+    pub fn get_escrow_terms<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let addresses = self.client_addresses(None)?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            terms::escrow_terms(&provider, &contracts, &addresses, buy_attestation).await
+        })
+    }
+
+    /// Estimate what fulfilling an escrow by paying its demand would cost and earn
+    /// the signer
+    ///
+    /// Returns a dict with the escrow's `token_type`, the `offer` the signer would
+    /// receive, the `payment` it demands, and `gas_estimate`, `gas_price` and
+    /// `gas_cost_wei` for approving the payment, making it and collecting the
+    /// escrow, all simulated without sending anything. If the simulation reverts,
+    /// such as when the signer can't afford the payment, the gas fields are None
+    /// and `revert_reason` says why. Raises ValueError for escrows whose demand
+    /// isn't a payment.
+    pub fn fulfillment_economics<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestation: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let addresses = self.client_addresses(None)?;
+        let fulfiller = self.nonce_manager.own_address()?;
+        let buy_attestation: FixedBytes<32> =
+            buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let escrow = alkahest_rs::contracts::IEAS::new(contracts.eas, &provider)
+                .getAttestation(buy_attestation)
+                .call()
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            if escrow.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Escrow attestation {} not found",
+                    buy_attestation
+                )));
+            }
+            simulation::fulfillment_economics(&provider, fulfiller, &escrow, &contracts, &addresses)
+                .await
+        })
+    }
+
+    /// List the signer's escrows that are neither claimed, revoked nor expired
+    ///
+    /// Each entry holds the escrow attestation, its token type ("erc20", "erc721",
+    /// "erc1155" or "token_bundle"), the decoded escrow obligation where the binding
+    /// has a type for it, and the expiration time.
+    #[pyo3(signature = (from_block=None))]
+    pub fn my_open_escrows<'py>(
+        &self,
+        py: Python<'py>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let signer = self.nonce_manager.own_address()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            escrow::find_open_escrows(&provider, &contracts, signer, from_block)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))
+        })
+    }
+
+    /// List the most recently created open escrows from any depositor, newest first
+    ///
+    /// For marketplace listings: returns up to `limit` escrows that are neither
+    /// claimed, revoked nor expired, each with its token type, decoded obligation
+    /// and the block and transaction that created it. `schema` restricts the
+    /// listing to escrows attested under that obligation schema, and the scan
+    /// stops at `from_block`. Without `from_block` only the last million blocks
+    /// are scanned; pass it, such as the escrow contracts' deployment block, to
+    /// look further back.
+    #[pyo3(signature = (schema=None, limit=20, from_block=None))]
+    pub fn recent_escrows<'py>(
+        &self,
+        py: Python<'py>,
+        schema: Option<String>,
+        limit: usize,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let contracts = self.escrow_contracts()?;
+        let schema = schema
+            .map(|schema| schema.parse::<FixedBytes<32>>().map_err(map_parse_to_pyerr))
+            .transpose()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            escrow::find_recent_escrows(&provider, &contracts, schema, limit, from_block)
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))
+        })
+    }
+
+    /// Count the attestations an address has made, optionally only those under
+    /// `schema`
+    ///
+    /// Counts the EAS `Attested` events naming `attester` between `from_block`
+    /// and the latest block, without fetching or decoding the attestations
+    /// themselves. Revoked and expired attestations are still counted. The range
+    /// is queried in fixed windows so each request stays within RPC log limits,
+    /// but a long chain still means many requests; pass `from_block` (for
+    /// instance the block the attester's contract was deployed at) rather than
+    /// scanning from genesis, the default.
+    #[pyo3(signature = (attester, schema=None, from_block=None))]
+    pub fn attestation_count<'py>(
+        &self,
+        py: Python<'py>,
+        attester: String,
+        schema: Option<String>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let eas = self.eas()?;
+        let attester: Address = attester.parse().map_err(map_parse_to_pyerr)?;
+        let schema = schema
+            .map(|schema| schema.parse::<FixedBytes<32>>().map_err(map_parse_to_pyerr))
+            .transpose()?;
+
+        let mut filter = Filter::new()
+            .address(eas)
+            .event_signature(Attested::SIGNATURE_HASH)
+            .topic2(attester.into_word());
+        if let Some(schema) = schema {
+            filter = filter.topic3(schema);
+        }
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let logs = logs::get_logs_windowed(&provider, &filter, from_block.unwrap_or(0))
+                .await
+                .map_err(error_handling::map_eyre_to_pyerr)?;
+            Ok(logs.len())
+        })
+    }
+
     /// Extract demand data from an escrow attestation
     pub fn extract_demand_data(&self, escrow_attestation: &crate::clients::oracle::PyOracleAttestation) -> PyResult<crate::clients::oracle::PyTrustedOracleArbiterDemandData> {
         use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
@@ -385,15 +1304,264 @@ impl PyAlkahestClient {
         })
     }
 
-    #[pyo3(signature = (contract_address, buy_attestation, from_block=None))]
+    /// Dry-run a whole barter, sending nothing: the signer escrowing `price` for
+    /// `ask`, then `fulfiller` paying `ask` and collecting the escrow
+    ///
+    /// `price` and `ask` may each be an ERC20, ERC721 or ERC1155 token or a token
+    /// bundle, in the forms the extension clients take, and the escrow has the terms
+    /// the `buy_*_for_*` helpers use. `fulfiller` defaults to the signer itself,
+    /// which then needs to hold `ask` too. The report has each step's success, gas
+    /// and revert reason; the steps are simulated with `eth_simulateV1`, which the
+    /// node must support.
+    #[pyo3(signature = (price, ask, expiration, fulfiller=None))]
+    pub fn simulate_barter<'py>(
+        &self,
+        py: Python<'py>,
+        price: crate::types::TokenData,
+        ask: crate::types::TokenData,
+        expiration: u64,
+        fulfiller: Option<String>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        let signer = self.nonce_manager.own_address()?;
+        let fulfiller = match fulfiller {
+            Some(fulfiller) => fulfiller.parse().map_err(map_parse_to_pyerr)?,
+            None => signer,
+        };
+        let addresses = self.client_addresses(None)?;
+        let price: simulation::BarterAsset = price
+            .try_into()
+            .map_err(error_handling::map_eyre_to_pyerr)?;
+        let ask: simulation::BarterAsset =
+            ask.try_into().map_err(error_handling::map_eyre_to_pyerr)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            simulation::simulate_barter(
+                &provider,
+                signer,
+                fulfiller,
+                &addresses,
+                price,
+                ask,
+                expiration,
+            )
+            .await
+            .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))
+        })
+    }
+
+    /// Perform a raw `eth_call` against any contract and return the undecoded
+    /// return data, for view functions the binding doesn't wrap
+    #[pyo3(signature = (to, data, from_address=None))]
+    pub fn call<'py>(
+        &self,
+        py: Python<'py>,
+        to: String,
+        data: Vec<u8>,
+        from_address: Option<String>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.read_provider()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let to: Address = to.parse().map_err(map_parse_to_pyerr)?;
+            let mut tx = TransactionRequest::default()
+                .to(to)
+                .input(Bytes::from(data).into());
+            if let Some(from_address) = from_address {
+                let from_address: Address = from_address.parse().map_err(map_parse_to_pyerr)?;
+                tx = tx.from(from_address);
+            }
+
+            let result = provider.call(tx).await.map_err(|e| {
+                pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+            })?;
+            Ok(result.to_vec())
+        })
+    }
+
+    /// Address of the EAS contract the client's attestations are made on
+    pub fn eas_address(&self) -> PyResult<String> {
+        Ok(format!("{:?}", self.eas()?))
+    }
+
+    /// Read the EAS contract's semver `version()` string
+    pub fn eas_version<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let eas = self.eas()?;
+        let provider = self.read_provider()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            alloy::sol! {
+                #[sol(rpc)]
+                interface ISemver {
+                    function version() external view returns (string);
+                }
+            }
+
+            ISemver::new(eas, &provider)
+                .version()
+                .call()
+                .await
+                .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))
+        })
+    }
+
+    /// Multiplier applied to estimated gas limits for transactions the client sends
+    ///
+    /// Covers `send_transaction`, batches and the write methods of the extension
+    /// clients, each of which also takes a `gas_multiplier` for a single call. It's
+    /// applied beneath the wallet provider, so the extension clients' writes are
+    /// still alkahest-rs's own calls. The oracle's arbitration methods aren't
+    /// affected, and neither is a client wrapped with `from_client`, whose provider
+    /// alkahest-rs built.
+    #[getter]
+    pub fn gas_multiplier(&self) -> f64 {
+        self.gas_multiplier.get()
+    }
+
+    #[setter]
+    pub fn set_gas_multiplier(&self, gas_multiplier: f64) -> PyResult<()> {
+        self.gas_multiplier.set(check_gas_multiplier(gas_multiplier)?);
+        Ok(())
+    }
+
+    /// Set the arbiter and demand escrows use when they're given None as their
+    /// `item` (or `demand`, for attestation escrows)
+    ///
+    /// Applies to every extension client of this client, including ones fetched
+    /// before the call. An explicit `item` still takes precedence.
+    pub fn set_default_arbiter(&self, arbiter: String, demand: Vec<u8>) -> PyResult<()> {
+        self.default_arbiter.set(Some(alkahest_rs::types::ArbiterData {
+            arbiter: arbiter.parse().map_err(map_parse_to_pyerr)?,
+            demand: demand.into(),
+        }));
+        Ok(())
+    }
+
+    /// Remove the default arbiter, so escrows must be given their `item` again
+    pub fn clear_default_arbiter(&self) {
+        self.default_arbiter.set(None);
+    }
+
+    /// Send a raw transaction from the signer and wait for its receipt, returning
+    /// the transaction hash
+    ///
+    /// The gas limit is the node's estimate scaled by `gas_multiplier`, or by the
+    /// client's setting when it isn't given.
+    #[pyo3(signature = (to, data, value=0, gas_multiplier=None))]
+    pub fn send_transaction<'py>(
+        &self,
+        py: Python<'py>,
+        to: String,
+        data: Vec<u8>,
+        value: u128,
+        gas_multiplier: Option<f64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.wallet_provider()?;
+        let nonce_manager = self.nonce_manager.clone();
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        gas::future_into_py(py, gas_multiplier, async move {
+            let to: Address = to.parse().map_err(map_parse_to_pyerr)?;
+            let tx = TransactionRequest::default()
+                .to(to)
+                .value(U256::from(value))
+                .input(Bytes::from(data).into());
+
+            let _nonce_guard = nonce_manager.acquire().await?;
+            let receipt = provider
+                .send_transaction(tx)
+                .await
+                .map_err(|e| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+                })?
+                .get_receipt()
+                .await
+                .map_err(|e| {
+                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+                })?;
+            if !receipt.status() {
+                return Err(error_handling::TransactionRevertError::new_err(format!(
+                    "Transaction {} reverted",
+                    receipt.transaction_hash
+                )));
+            }
+            Ok(receipt.transaction_hash.to_string())
+        })
+    }
+
+    /// List the signer's transactions the node has accepted but not yet mined,
+    /// ordered by nonce
+    ///
+    /// Reads the node's transaction pool, so it needs a node serving the `txpool`
+    /// namespace. A transaction that has sat here for several blocks is usually
+    /// underpriced and can be resent with `speed_up`.
+    pub fn pending_transactions<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let provider = self.wallet_provider()?;
+        let signer = self.nonce_manager.own_address()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            pending::pending_transactions(&provider, signer).await
+        })
+    }
+
+    /// Replace the signer's pending transaction with `nonce` by the same
+    /// transaction paying `new_fee` wei per gas, returning the replacement's hash
+    ///
+    /// `new_fee` is the gas price of a legacy transaction or the max fee per gas of
+    /// an EIP-1559 one, and must be higher than the current one; most nodes want
+    /// at least a 10% increase. Whichever of the two transactions is mined, the
+    /// other is dropped, so anything awaiting the original's receipt won't resolve
+    /// if the replacement wins.
+    pub fn speed_up<'py>(
+        &self,
+        py: Python<'py>,
+        nonce: u64,
+        new_fee: u128,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        self.nonce_manager.ensure_writable()?;
+        let provider = self.wallet_provider()?;
+        let signer = self.nonce_manager.own_address()?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            pending::speed_up(&provider, signer, nonce, new_fee).await
+        })
+    }
+
+    /// Start a batch of transactions to send with consecutive nonces
+    ///
+    /// Use it as `async with client.batch() as batch:` and queue calls with
+    /// `batch.send_transaction`; they are sent when the block exits, and their
+    /// receipts are then in `batch.receipts`. `nonce` sets the first nonce instead
+    /// of the signer's pending transaction count, and `gas_multiplier` overrides the
+    /// client's setting for the whole batch.
+    #[pyo3(signature = (gas_multiplier=None, nonce=None))]
+    pub fn batch(
+        &self,
+        gas_multiplier: Option<f64>,
+        nonce: Option<u64>,
+    ) -> PyResult<batch::PyTransactionBatch> {
+        self.nonce_manager.ensure_writable()?;
+        let gas_multiplier = self.gas_multiplier.resolve(gas_multiplier)?;
+        Ok(batch::PyTransactionBatch::new(
+            self.wallet_provider()?,
+            self.nonce_manager.clone(),
+            gas_multiplier,
+            nonce,
+        ))
+    }
+
+    /// Wait for an escrow to be claimed, returning the claim along with the
+    /// block number and transaction hash it was claimed in
+    ///
+    /// With `confirmations` set, the claim only resolves once its block is that many
+    /// blocks deep (the claim block counting as one), and the claim is re-read at
+    /// that depth so a claim reorged out in the meantime is not returned.
+    #[pyo3(signature = (contract_address, buy_attestation, from_block=None, confirmations=0))]
     pub fn wait_for_fulfillment<'py>(
         &self,
         py: Python<'py>,
         contract_address: String,
         buy_attestation: String,
         from_block: Option<u64>,
+        confirmations: u64,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
         let inner = self.inner.clone();
+        let provider = self.read_provider()?;
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
             let contract_address: Address = contract_address.parse().map_err(|e| {
                 pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Parse error: {}", e))
@@ -425,10 +1593,147 @@ impl PyAlkahestClient {
                 ));
             };
 
-            let result: EscowClaimedLog = res.data.into();
+            // The claim resolves without log metadata, so look it up once it exists
+            let claim = if confirmations > 0 {
+                escrow::confirm_escrow_claim(
+                    &provider,
+                    contract_address,
+                    buy_attestation,
+                    from_block,
+                    confirmations,
+                )
+                .await
+                .map(Some)
+            } else {
+                escrow::find_escrow_claim(&provider, contract_address, buy_attestation, from_block)
+                    .await
+            }
+            .map_err(|e| {
+                pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+            })?;
+
+            let result: EscowClaimedLog = match claim {
+                Some(log) => EscowClaimedLog::from(&log),
+                None => res.data.into(),
+            };
             Ok(result)
         })
     }
+
+    /// Wait for the first of several escrows to be claimed, returning that claim
+    ///
+    /// Each escrow is watched on the escrow contract that attested it, so the set
+    /// can mix token types. Raises `TimeoutError` if none is claimed within
+    /// `timeout` seconds, or waits indefinitely if `timeout` is None.
+    #[pyo3(signature = (buy_attestations, timeout=None, from_block=None))]
+    pub fn wait_for_any_fulfillment<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestations: Vec<String>,
+        timeout: Option<f64>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let wait = self.wait_for_claims(buy_attestations, timeout, from_block, false)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut claims = wait.await?;
+            Ok(claims.remove(0))
+        })
+    }
+
+    /// Wait for every one of several escrows to be claimed, returning the claims
+    /// in the order the escrows were given
+    ///
+    /// Raises `TimeoutError` if they aren't all claimed within `timeout` seconds,
+    /// or waits indefinitely if `timeout` is None.
+    #[pyo3(signature = (buy_attestations, timeout=None, from_block=None))]
+    pub fn wait_for_all_fulfillment<'py>(
+        &self,
+        py: Python<'py>,
+        buy_attestations: Vec<String>,
+        timeout: Option<f64>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let wait = self.wait_for_claims(buy_attestations, timeout, from_block, true)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, wait)
+    }
+
+    /// Watch an attestation for revocation, calling `callback` with the `Revoked`
+    /// event if it is revoked within `timeout` seconds
+    ///
+    /// Resolves to the `Revoked` event once the callback has run, or to None if the
+    /// window passes without a revocation; with `timeout` None it watches
+    /// indefinitely. `callback` may be a plain function or a coroutine function.
+    /// The attestation's `revocationTime` is polled, and the event is looked up
+    /// once it is set; an attestation that is already revoked fires straight
+    /// away, with its event searched for from `from_block` (the genesis block by
+    /// default).
+    #[pyo3(signature = (uid, callback, timeout=None, from_block=None))]
+    pub fn watch_revocation<'py>(
+        &self,
+        py: Python<'py>,
+        uid: String,
+        callback: PyObject,
+        timeout: Option<f64>,
+        from_block: Option<u64>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let attestation_client = self.attestation.clone().ok_or_else(|| {
+            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+                "Attestation extension is not available in this client",
+            )
+        })?;
+        let provider = self.read_provider()?;
+        let eas = self.eas()?;
+        let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
+        let timeout = timeout.map(check_timeout).transpose()?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let attestation = attestation_client.inner.get_attestation(uid).await.map_err(|e| {
+                pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
+            })?;
+            if attestation.uid == FixedBytes::<32>::ZERO {
+                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Attestation {} not found",
+                    uid
+                )));
+            }
+
+            let wait = revocation::wait_for_revocation(&provider, eas, uid, from_block);
+            let revoked = match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, wait).await {
+                    Ok(revoked) => revoked,
+                    Err(_) => return Ok(None),
+                },
+                None => wait.await,
+            }
+            .map_err(|e| pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e)))?;
+            let revoked = PyRevoked::from(revoked);
+
+            // Coroutine callbacks hand back an awaitable, which is run to completion
+            let pending = Python::with_gil(|py| -> PyResult<_> {
+                let result = callback.call1(py, (revoked.clone(),))?.into_bound(py);
+                let inspect = py.import("inspect")?;
+                if inspect.getattr("isawaitable")?.call1((&result,))?.extract::<bool>()? {
+                    pyo3_async_runtimes::tokio::into_future(result).map(Some)
+                } else {
+                    Ok(None)
+                }
+            })?;
+            if let Some(pending) = pending {
+                pending.await?;
+            }
+
+            Ok(Some(revoked))
+        })
+    }
+}
+
+fn check_timeout(secs: f64) -> PyResult<std::time::Duration> {
+    std::time::Duration::try_from_secs_f64(secs).map_err(|_| {
+        pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "timeout must be a non-negative number of seconds, got {}",
+            secs
+        ))
+    })
 }
 
 pub fn get_attested_event(receipt: TransactionReceipt) -> eyre::Result<Log<Attested>> {
@@ -445,6 +1750,20 @@ pub fn get_attested_event(receipt: TransactionReceipt) -> eyre::Result<Log<Attes
     Ok(attested_event.inner)
 }
 
+/// Build the return value shared by write methods: the decoded `Attested`
+/// event, the transaction hash and, when requested, every log in the receipt
+pub fn attested_log_with_hash(
+    receipt: TransactionReceipt,
+    include_logs: bool,
+) -> eyre::Result<LogWithHash<AttestedLog>> {
+    let logs = include_logs.then(|| logs::decode_receipt_logs(&receipt));
+    Ok(LogWithHash {
+        log: get_attested_event(receipt.clone())?.data.into(),
+        transaction_hash: receipt.transaction_hash.to_string(),
+        logs,
+    })
+}
+
 #[pymodule]
 fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlkahestClient>()?;
@@ -462,6 +1781,7 @@ fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMockERC721>()?;
     m.add_class::<PyMockERC1155>()?;
     m.add_class::<PyERC20EscrowObligationData>()?;
+    m.add_class::<PyTokenBundleEscrowObligationData>()?;
     m.add_class::<PyERC20PaymentObligationData>()?;
     m.add_class::<PyERC721EscrowObligationData>()?;
     m.add_class::<PyERC721PaymentObligationData>()?;
@@ -469,6 +1789,35 @@ fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyERC1155PaymentObligationData>()?;
     m.add_class::<PyStringObligationData>()?;
     m.add_class::<PyErc20Data>()?;
+    m.add_function(wrap_pyfunction!(escrow::expiration_in, m)?)?;
+    m.add(
+        "TransactionRevertError",
+        m.py().get_type::<error_handling::TransactionRevertError>(),
+    )?;
+    m.add(
+        "InsufficientBalanceError",
+        m.py().get_type::<error_handling::InsufficientBalanceError>(),
+    )?;
+    m.add(
+        "ReadOnlyClientError",
+        m.py().get_type::<error_handling::ReadOnlyClientError>(),
+    )?;
+    m.add_function(wrap_pyfunction!(arbiters::encode_uid_arbiter_demand, m)?)?;
+    m.add_function(wrap_pyfunction!(arbiters::encode_recipient_arbiter_demand, m)?)?;
+    m.add_function(wrap_pyfunction!(arbiters::encode_intrinsics_arbiter_demand, m)?)?;
+    m.add_function(wrap_pyfunction!(arbiters::encode_intrinsics_arbiter_2_demand, m)?)?;
+    m.add_function(wrap_pyfunction!(arbiters::demand_erc20_payment, m)?)?;
+    m.add_function(wrap_pyfunction!(arbiters::demand_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(contract::normalize_schema_uid, m)?)?;
+    m.add_function(wrap_pyfunction!(contract::compute_schema_uid, m)?)?;
+    m.add_function(wrap_pyfunction!(logs::decode_attested_log, m)?)?;
+    m.add_function(wrap_pyfunction!(logs::decode_revoked_log, m)?)?;
+    m.add_function(wrap_pyfunction!(logs::decode_escrow_claimed_log, m)?)?;
+    m.add_function(wrap_pyfunction!(logs::decode_raw_log, m)?)?;
+    m.add_function(wrap_pyfunction!(offer::import_offer, m)?)?;
+    m.add_function(wrap_pyfunction!(demand_decoders::register_demand_decoder, m)?)?;
+    m.add_function(wrap_pyfunction!(demand_decoders::unregister_demand_decoder, m)?)?;
+    m.add_function(wrap_pyfunction!(demand_decoders::registered_demand_decoders, m)?)?;
 
     // Address Configuration Classes
     m.add_class::<crate::types::PyErc20Addresses>()?;
@@ -482,6 +1831,12 @@ fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // IEAS (Ethereum Attestation Service) Types from contract.rs
     m.add_class::<PyAttestation>()?;
     m.add_class::<PyAttestationRequest>()?;
+    m.add_class::<PyAttestationBuilder>()?;
+    m.add_class::<batch::PyTransactionBatch>()?;
+    m.add_class::<simulation::PySimulationReport>()?;
+    m.add_class::<simulation::PySimulationStep>()?;
+    m.add_class::<pending::PyPendingTx>()?;
+    m.add_class::<terms::PyEscrowTerms>()?;
     m.add_class::<PyAttestationRequestData>()?;
     m.add_class::<PyAttested>()?;
     m.add_class::<PyRevocationRequest>()?;
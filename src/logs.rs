@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use alkahest_rs::{
+    contracts::IEAS::{Attested, Revoked},
+    sol_types::EscrowClaimed,
+};
+use alloy::{
+    primitives::{Address, FixedBytes, LogData},
+    providers::Provider,
+    rpc::types::{Filter, Log, TransactionReceipt},
+    sol_types::SolEvent,
+};
+use pyo3::{exceptions::PyValueError, pyfunction, PyResult};
+
+use crate::{
+    error_handling::{map_parse_to_pyerr, map_sol_decode_to_pyerr},
+    types::{AttestedLog, DecodedLog, EscowClaimedLog, RevokedLog},
+};
+
+// Token events share signatures across standards but differ in which fields are
+// indexed, so ERC20 and ERC721 Transfer/Approval each get their own definition.
+mod erc20_events {
+    alloy::sol! {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+    }
+}
+
+mod erc721_events {
+    alloy::sol! {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+        event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+        event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+    }
+}
+
+mod erc1155_events {
+    alloy::sol! {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+}
+
+type EventArgs = Vec<(&'static str, String)>;
+
+/// Decode every log in a receipt, naming the events alkahest and the token
+/// standards emit and leaving unknown events as raw topics and data
+pub fn decode_receipt_logs(receipt: &TransactionReceipt) -> Vec<DecodedLog> {
+    receipt
+        .inner
+        .logs()
+        .iter()
+        .map(|log| decode_log(log.address(), log.data(), log.log_index))
+        .collect()
+}
+
+/// Decode a single log from its emitting address and topic/data payload
+pub fn decode_log(address: Address, data: &LogData, log_index: Option<u64>) -> DecodedLog {
+    let (event, args) = match decode_known_event(data) {
+        Some((name, args)) => (
+            Some(name.to_string()),
+            Some(
+                args.into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect::<HashMap<_, _>>(),
+            ),
+        ),
+        None => (None, None),
+    };
+
+    DecodedLog {
+        address: format!("{:?}", address),
+        topics: data.topics().iter().map(|topic| topic.to_string()).collect(),
+        data: data.data.to_vec(),
+        log_index,
+        event,
+        args,
+    }
+}
+
+/// Blocks covered by each request of a windowed log scan
+pub const LOG_WINDOW: u64 = 10_000;
+
+/// Fetch the logs matching `filter` from `from_block` to the latest block, oldest
+/// first
+///
+/// The range is queried in windows of [`LOG_WINDOW`] blocks, so each request stays
+/// within providers' block range limits however long the range is. Any block
+/// range already set on `filter` is replaced.
+pub async fn get_logs_windowed<P: Provider>(
+    provider: &P,
+    filter: &Filter,
+    from_block: u64,
+) -> eyre::Result<Vec<Log>> {
+    let latest = provider.get_block_number().await?;
+    get_logs_in_windows(provider, filter, from_block, latest).await
+}
+
+/// [`get_logs_windowed`] up to `to_block` rather than the latest block
+pub async fn get_logs_in_windows<P: Provider>(
+    provider: &P,
+    filter: &Filter,
+    from_block: u64,
+    to_block: u64,
+) -> eyre::Result<Vec<Log>> {
+    let mut logs = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+        let end = start.saturating_add(LOG_WINDOW - 1).min(to_block);
+        let window = filter.clone().from_block(start).to_block(end);
+        logs.extend(provider.get_logs(&window).await?);
+        start = end + 1;
+    }
+    Ok(logs)
+}
+
+/// Assemble a log's payload from the topic/data split raw log APIs return
+fn raw_log_data(topics: Vec<String>, data: Vec<u8>) -> PyResult<LogData> {
+    let topics = topics
+        .iter()
+        .map(|topic| topic.parse::<FixedBytes<32>>().map_err(map_parse_to_pyerr))
+        .collect::<PyResult<Vec<_>>>()?;
+    LogData::new(topics, data.into())
+        .ok_or_else(|| PyValueError::new_err("A log has at most 4 topics"))
+}
+
+/// Decode a raw log as event `E`, raising `ValueError` if it is another event
+fn decode_raw_event<E: SolEvent>(topics: Vec<String>, data: Vec<u8>) -> PyResult<E> {
+    let data = raw_log_data(topics, data)?;
+    if data.topics().first() != Some(&E::SIGNATURE_HASH) {
+        return Err(PyValueError::new_err(format!(
+            "Log is not a {} event",
+            E::SIGNATURE
+        )));
+    }
+    E::decode_log_data(&data).map_err(map_sol_decode_to_pyerr)
+}
+
+/// Decode an EAS `Attested` log obtained elsewhere, such as from an indexer
+#[pyfunction]
+pub fn decode_attested_log(topics: Vec<String>, data: Vec<u8>) -> PyResult<AttestedLog> {
+    decode_raw_event::<Attested>(topics, data).map(Into::into)
+}
+
+/// Decode an EAS `Revoked` log obtained elsewhere, such as from an indexer
+#[pyfunction]
+pub fn decode_revoked_log(topics: Vec<String>, data: Vec<u8>) -> PyResult<RevokedLog> {
+    decode_raw_event::<Revoked>(topics, data).map(Into::into)
+}
+
+/// Decode an escrow contract's `EscrowClaimed` log obtained elsewhere
+#[pyfunction]
+pub fn decode_escrow_claimed_log(
+    topics: Vec<String>,
+    data: Vec<u8>,
+) -> PyResult<EscowClaimedLog> {
+    decode_raw_event::<EscrowClaimed>(topics, data).map(Into::into)
+}
+
+/// Decode any log obtained elsewhere the way `include_logs` decodes receipt logs,
+/// naming the events alkahest and the token standards emit
+#[pyfunction]
+#[pyo3(signature = (address, topics, data, log_index=None))]
+pub fn decode_raw_log(
+    address: String,
+    topics: Vec<String>,
+    data: Vec<u8>,
+    log_index: Option<u64>,
+) -> PyResult<DecodedLog> {
+    let address: Address = address.parse().map_err(map_parse_to_pyerr)?;
+    Ok(decode_log(address, &raw_log_data(topics, data)?, log_index))
+}
+
+fn decode_known_event(data: &LogData) -> Option<(&'static str, EventArgs)> {
+    let topic0 = *data.topics().first()?;
+
+    if topic0 == Attested::SIGNATURE_HASH {
+        let event = Attested::decode_log_data(data).ok()?;
+        return Some((
+            "Attested",
+            vec![
+                ("recipient", format!("{:?}", event.recipient)),
+                ("attester", format!("{:?}", event.attester)),
+                ("uid", event.uid.to_string()),
+                ("schema_uid", event.schemaUID.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == Revoked::SIGNATURE_HASH {
+        let event = Revoked::decode_log_data(data).ok()?;
+        return Some((
+            "Revoked",
+            vec![
+                ("recipient", format!("{:?}", event.recipient)),
+                ("attester", format!("{:?}", event.attester)),
+                ("uid", event.uid.to_string()),
+                ("schema_uid", event.schemaUID.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == EscrowClaimed::SIGNATURE_HASH {
+        let event = EscrowClaimed::decode_log_data(data).ok()?;
+        return Some((
+            "EscrowClaimed",
+            vec![
+                ("payment", event.payment.to_string()),
+                ("fulfillment", event.fulfillment.to_string()),
+                ("fulfiller", format!("{:?}", event.fulfiller)),
+            ],
+        ));
+    }
+
+    if topic0 == erc20_events::Transfer::SIGNATURE_HASH {
+        if let Ok(event) = erc20_events::Transfer::decode_log_data(data) {
+            return Some((
+                "Transfer",
+                vec![
+                    ("from", format!("{:?}", event.from)),
+                    ("to", format!("{:?}", event.to)),
+                    ("value", event.value.to_string()),
+                ],
+            ));
+        }
+        let event = erc721_events::Transfer::decode_log_data(data).ok()?;
+        return Some((
+            "Transfer",
+            vec![
+                ("from", format!("{:?}", event.from)),
+                ("to", format!("{:?}", event.to)),
+                ("token_id", event.tokenId.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == erc20_events::Approval::SIGNATURE_HASH {
+        if let Ok(event) = erc20_events::Approval::decode_log_data(data) {
+            return Some((
+                "Approval",
+                vec![
+                    ("owner", format!("{:?}", event.owner)),
+                    ("spender", format!("{:?}", event.spender)),
+                    ("value", event.value.to_string()),
+                ],
+            ));
+        }
+        let event = erc721_events::Approval::decode_log_data(data).ok()?;
+        return Some((
+            "Approval",
+            vec![
+                ("owner", format!("{:?}", event.owner)),
+                ("approved", format!("{:?}", event.approved)),
+                ("token_id", event.tokenId.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == erc721_events::ApprovalForAll::SIGNATURE_HASH {
+        let event = erc721_events::ApprovalForAll::decode_log_data(data).ok()?;
+        return Some((
+            "ApprovalForAll",
+            vec![
+                ("owner", format!("{:?}", event.owner)),
+                ("operator", format!("{:?}", event.operator)),
+                ("approved", event.approved.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == erc1155_events::TransferSingle::SIGNATURE_HASH {
+        let event = erc1155_events::TransferSingle::decode_log_data(data).ok()?;
+        return Some((
+            "TransferSingle",
+            vec![
+                ("operator", format!("{:?}", event.operator)),
+                ("from", format!("{:?}", event.from)),
+                ("to", format!("{:?}", event.to)),
+                ("id", event.id.to_string()),
+                ("value", event.value.to_string()),
+            ],
+        ));
+    }
+
+    if topic0 == erc1155_events::TransferBatch::SIGNATURE_HASH {
+        let event = erc1155_events::TransferBatch::decode_log_data(data).ok()?;
+        let join = |values: &[alloy::primitives::U256]| {
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        return Some((
+            "TransferBatch",
+            vec![
+                ("operator", format!("{:?}", event.operator)),
+                ("from", format!("{:?}", event.from)),
+                ("to", format!("{:?}", event.to)),
+                ("ids", join(&event.ids)),
+                ("values", join(&event.values)),
+            ],
+        ));
+    }
+
+    None
+}
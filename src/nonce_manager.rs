@@ -0,0 +1,106 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use alloy::primitives::Address;
+use pyo3::PyResult;
+use tokio::sync::OwnedMutexGuard;
+
+static MANAGERS: OnceLock<Mutex<HashMap<(String, Address), NonceManager>>> = OnceLock::new();
+
+/// Per-signer transaction sequencing shared by every extension client
+///
+/// The alkahest-rs modules fill each transaction's nonce from the node's pending
+/// transaction count, so two extension clients sending for the same signer at once
+/// can both be handed the same nonce and one fails with "nonce too low". Write
+/// methods hold this manager's lock from submission until the receipt arrives,
+/// which keeps a signer's transactions strictly ordered. Managers are looked up by
+/// RPC URL and signer address, so clients built from the same key for the same
+/// node share one even when they come from different `PyAlkahestClient`
+/// instances, while the same key on another chain gets its own. The oracle's
+/// long-running listeners are not sequenced, since holding the lock for a whole
+/// subscription would block every other write from that signer.
+///
+/// A read-only client gets a manager that refuses every write instead, so the
+/// check lives at the one place every write path already goes through.
+#[derive(Clone)]
+pub struct NonceManager {
+    signer: Address,
+    lock: Arc<tokio::sync::Mutex<()>>,
+    read_only: bool,
+}
+
+impl NonceManager {
+    /// Get the shared manager for a signer sending through `rpc_url`, creating it
+    /// on first use
+    pub fn for_signer(rpc_url: &str, signer: Address) -> Self {
+        let managers = MANAGERS.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut managers = managers.lock().unwrap_or_else(|e| e.into_inner());
+        managers
+            .entry((rpc_url.to_string(), signer))
+            .or_insert_with(|| Self::unshared(signer))
+            .clone()
+    }
+
+    /// Get a manager of its own for a signer whose RPC URL isn't known, such as a
+    /// wrapped alkahest-rs client's
+    ///
+    /// It sequences the writes of the clients it's given to, but not those of
+    /// clients for the same signer created elsewhere.
+    pub fn unshared(signer: Address) -> Self {
+        Self {
+            signer,
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+            read_only: false,
+        }
+    }
+
+    /// Get a manager for a client that has no key, which refuses every write
+    ///
+    /// These are not shared, since there is nothing to sequence.
+    pub fn read_only(signer: Address) -> Self {
+        Self {
+            read_only: true,
+            ..Self::unshared(signer)
+        }
+    }
+
+    /// Address of the signer whose transactions this manager sequences
+    pub fn signer(&self) -> Address {
+        self.signer
+    }
+
+    /// Address of the signer, for a method that defaults to the client's own address
+    ///
+    /// Fails with `ReadOnlyClientError` for a read-only client, whose signer is a
+    /// throwaway key that would only ever show an empty account.
+    pub fn own_address(&self) -> PyResult<Address> {
+        if self.read_only {
+            return Err(crate::error_handling::ReadOnlyClientError::new_err(
+                "This is a read-only client, so it has no address of its own; pass the \
+                 address to look up instead",
+            ));
+        }
+        Ok(self.signer)
+    }
+
+    /// Fail with `ReadOnlyClientError` if this manager belongs to a read-only client
+    pub fn ensure_writable(&self) -> PyResult<()> {
+        if self.read_only {
+            return Err(crate::error_handling::ReadOnlyClientError::new_err(
+                "This is a read-only client; construct one with a private key to send transactions",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Wait for the signer's previous transaction to be mined, then hold the
+    /// slot until the returned guard is dropped
+    ///
+    /// Fails with `ReadOnlyClientError` for a read-only client.
+    pub async fn acquire(&self) -> PyResult<OwnedMutexGuard<()>> {
+        self.ensure_writable()?;
+        Ok(self.lock.clone().lock_owned().await)
+    }
+}
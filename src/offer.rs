@@ -0,0 +1,195 @@
+use alkahest_rs::contracts::IEAS;
+use alloy::{
+    hex,
+    primitives::{Address, FixedBytes},
+};
+use pyo3::{
+    exceptions::PyValueError, pyfunction, types::PyAnyMethods, Bound, PyAny, PyResult, Python,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    demand_decoders::Demand,
+    error_handling::map_serde_to_pyerr,
+    escrow::{EscrowObligation, PaymentDemand},
+};
+
+/// Format version written into exported offers; `import_offer` rejects others
+const OFFER_VERSION: u64 = 1;
+
+const TOKEN_TYPES: [&str; 4] = ["erc20", "erc721", "erc1155", "token_bundle"];
+
+/// Describe an escrow as an offer to share off-chain
+///
+/// Amounts and token IDs are decimal strings, since they can exceed the integers
+/// JSON consumers handle exactly. `demand_summary` is the demand as
+/// `decode_demand` decodes it: the payment the escrow asks for, the result of
+/// the arbiter's registered decoder (which must be JSON-serializable), or null
+/// when the arbiter has neither.
+pub fn offer_json(
+    py: Python<'_>,
+    chain_id: u64,
+    escrow: &IEAS::Attestation,
+    token_type: &str,
+    obligation: &EscrowObligation,
+    demand: &Demand,
+) -> PyResult<Value> {
+    let (offer, arbiter, demand_bytes) = match obligation {
+        EscrowObligation::Erc20(data) => (
+            json!({ "token": data.token, "amount": data.amount.to_string() }),
+            &data.arbiter,
+            &data.demand,
+        ),
+        EscrowObligation::Erc721(data) => (
+            json!({ "token": data.token, "token_id": data.token_id }),
+            &data.arbiter,
+            &data.demand,
+        ),
+        EscrowObligation::Erc1155(data) => (
+            json!({ "token": data.token, "token_id": data.token_id, "amount": data.amount }),
+            &data.arbiter,
+            &data.demand,
+        ),
+        EscrowObligation::TokenBundle(data) => (
+            json!({
+                "erc20s": data.erc20s.iter().map(|token| json!({
+                    "address": token.address,
+                    "value": token.value.to_string(),
+                })).collect::<Vec<_>>(),
+                "erc721s": data.erc721s.iter().map(|token| json!({
+                    "address": token.address,
+                    "id": token.id.to_string(),
+                })).collect::<Vec<_>>(),
+                "erc1155s": data.erc1155s.iter().map(|token| json!({
+                    "address": token.address,
+                    "id": token.id.to_string(),
+                    "value": token.value.to_string(),
+                })).collect::<Vec<_>>(),
+            }),
+            &data.arbiter,
+            &data.demand,
+        ),
+    };
+
+    let demand_summary = match demand {
+        Demand::Payment(payment) => payment_summary(payment),
+        Demand::Custom(decoded) => {
+            let encoded: String = py
+                .import("json")?
+                .getattr("dumps")?
+                .call1((decoded.bind(py),))?
+                .extract()?;
+            serde_json::from_str(&encoded).map_err(map_serde_to_pyerr)?
+        }
+        Demand::Raw(_) => Value::Null,
+    };
+
+    Ok(json!({
+        "version": OFFER_VERSION,
+        "chain_id": chain_id,
+        "escrow_uid": escrow.uid.to_string(),
+        "escrow_contract": format!("{:?}", escrow.attester),
+        "depositor": format!("{:?}", escrow.recipient),
+        "token_type": token_type,
+        "offer": offer,
+        "arbiter": arbiter,
+        "demand": hex::encode_prefixed(demand_bytes),
+        "demand_summary": demand_summary,
+        // Zero means the escrow never expires
+        "expiration_time": (escrow.expirationTime != 0).then_some(escrow.expirationTime),
+    }))
+}
+
+/// Summarize a payment demand for an offer
+fn payment_summary(payment: &PaymentDemand) -> Value {
+    match payment {
+        PaymentDemand::Erc20(data) => json!({
+            "token_type": "erc20",
+            "token": data.token,
+            "amount": data.amount.to_string(),
+            "payee": data.payee,
+        }),
+        PaymentDemand::Erc721(data) => json!({
+            "token_type": "erc721",
+            "token": data.token,
+            "token_id": data.token_id,
+            "payee": data.payee,
+        }),
+        PaymentDemand::Erc1155(data) => json!({
+            "token_type": "erc1155",
+            "token": data.token,
+            "token_id": data.token_id,
+            "amount": data.amount,
+            "payee": data.payee,
+        }),
+    }
+}
+
+/// Read a field of an offer, failing with the field's name if it's missing
+fn offer_field<'a>(offer: &'a Map<String, Value>, field: &str) -> PyResult<&'a Value> {
+    offer
+        .get(field)
+        .ok_or_else(|| PyValueError::new_err(format!("Invalid offer: missing {}", field)))
+}
+
+fn offer_str<'a>(offer: &'a Map<String, Value>, field: &str) -> PyResult<&'a str> {
+    offer_field(offer, field)?
+        .as_str()
+        .ok_or_else(|| PyValueError::new_err(format!("Invalid offer: {} must be a string", field)))
+}
+
+fn invalid_field<E: std::fmt::Display>(field: &str) -> impl Fn(E) -> pyo3::PyErr + '_ {
+    move |e| PyValueError::new_err(format!("Invalid offer: {}: {}", field, e))
+}
+
+/// Parse and check an offer made by `export_offer`, returning it as a dict
+///
+/// The fields are checked for shape only: that the escrow exists and matches the
+/// description is only known by reading it from the chain named by `chain_id`.
+#[pyfunction]
+pub fn import_offer<'py>(py: Python<'py>, offer: &str) -> PyResult<Bound<'py, PyAny>> {
+    let value: Value = serde_json::from_str(offer)
+        .map_err(|e| PyValueError::new_err(format!("Invalid offer: {}", e)))?;
+    let fields = value
+        .as_object()
+        .ok_or_else(|| PyValueError::new_err("Invalid offer: expected a JSON object"))?;
+
+    let version = offer_field(fields, "version")?.as_u64();
+    if version != Some(OFFER_VERSION) {
+        return Err(PyValueError::new_err(format!(
+            "Unsupported offer version {}, expected {}",
+            offer_field(fields, "version")?,
+            OFFER_VERSION
+        )));
+    }
+    offer_field(fields, "chain_id")?
+        .as_u64()
+        .ok_or_else(|| PyValueError::new_err("Invalid offer: chain_id must be an integer"))?;
+    offer_str(fields, "escrow_uid")?
+        .parse::<FixedBytes<32>>()
+        .map_err(invalid_field("escrow_uid"))?;
+    for field in ["escrow_contract", "depositor", "arbiter"] {
+        offer_str(fields, field)?
+            .parse::<Address>()
+            .map_err(invalid_field(field))?;
+    }
+    let token_type = offer_str(fields, "token_type")?;
+    if !TOKEN_TYPES.contains(&token_type) {
+        return Err(PyValueError::new_err(format!(
+            "Invalid offer: unknown token_type {}",
+            token_type
+        )));
+    }
+    if !offer_field(fields, "offer")?.is_object() {
+        return Err(PyValueError::new_err("Invalid offer: offer must be an object"));
+    }
+    hex::decode(offer_str(fields, "demand")?).map_err(invalid_field("demand"))?;
+    let expiration_time = offer_field(fields, "expiration_time")?;
+    if !expiration_time.is_null() && !expiration_time.is_u64() {
+        return Err(PyValueError::new_err(
+            "Invalid offer: expiration_time must be an integer or null",
+        ));
+    }
+
+    py.import("json")?.call_method1("loads", (offer,))
+}
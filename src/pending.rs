@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+
+use alkahest_rs::types::WalletProvider;
+use alloy::{
+    consensus::Transaction as _,
+    network::TransactionResponse as _,
+    primitives::{Address, U256},
+    providers::Provider,
+    rpc::types::Transaction,
+};
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, PyResult};
+
+use crate::error_handling::{map_eyre_to_pyerr, map_serde_to_pyerr};
+
+/// A transaction from the signer that the node holds but hasn't mined
+#[pyclass]
+#[derive(Clone)]
+pub struct PyPendingTx {
+    #[pyo3(get)]
+    pub nonce: u64,
+    #[pyo3(get)]
+    pub hash: String,
+    /// Gas price of a legacy transaction, or the max fee per gas of an EIP-1559
+    /// one, in wei
+    #[pyo3(get)]
+    pub gas_price: u128,
+    /// Tip of an EIP-1559 transaction in wei, None for legacy transactions
+    #[pyo3(get)]
+    pub max_priority_fee_per_gas: Option<u128>,
+    #[pyo3(get)]
+    pub to: Option<String>,
+    /// Whether the node is holding it back because an earlier nonce is missing,
+    /// rather than waiting for it to be mined
+    #[pyo3(get)]
+    pub queued: bool,
+}
+
+#[pymethods]
+impl PyPendingTx {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyPendingTx(nonce={}, hash='{}', gas_price={}, queued={})",
+            self.nonce, self.hash, self.gas_price, self.queued
+        )
+    }
+}
+
+/// Read the signer's transactions from the node's pool with `txpool_contentFrom`,
+/// ordered by nonce, each with whether it is queued behind a nonce gap
+async fn pool_transactions(
+    provider: &WalletProvider,
+    signer: Address,
+) -> PyResult<Vec<(Transaction, bool)>> {
+    let content: serde_json::Value = provider
+        .raw_request(Cow::Borrowed("txpool_contentFrom"), (signer,))
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+
+    let mut transactions = Vec::new();
+    for (section, queued) in [("pending", false), ("queued", true)] {
+        let Some(entries) = content.get(section).and_then(|entries| entries.as_object()) else {
+            continue;
+        };
+        for transaction in entries.values() {
+            let transaction: Transaction =
+                serde_json::from_value(transaction.clone()).map_err(map_serde_to_pyerr)?;
+            transactions.push((transaction, queued));
+        }
+    }
+    transactions.sort_by_key(|(transaction, _)| transaction.nonce());
+    Ok(transactions)
+}
+
+/// List the signer's transactions the node has accepted but not mined
+///
+/// Needs a node serving the `txpool` namespace, as geth, reth and anvil do.
+pub async fn pending_transactions(
+    provider: &WalletProvider,
+    signer: Address,
+) -> PyResult<Vec<PyPendingTx>> {
+    Ok(pool_transactions(provider, signer)
+        .await?
+        .into_iter()
+        .map(|(transaction, queued)| PyPendingTx {
+            nonce: transaction.nonce(),
+            hash: transaction.tx_hash().to_string(),
+            gas_price: transaction.max_fee_per_gas(),
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas(),
+            to: transaction.to().map(|to| format!("{:?}", to)),
+            queued,
+        })
+        .collect())
+}
+
+/// Resend the signer's pending transaction with `nonce` paying `new_fee` per gas,
+/// returning the replacement's hash
+///
+/// `new_fee` replaces the gas price of a legacy transaction or the max fee per gas
+/// of an EIP-1559 one. Nodes only accept a replacement whose tip rises too, so an
+/// EIP-1559 transaction's tip is raised in the same proportion as its fee cap.
+pub async fn speed_up(
+    provider: &WalletProvider,
+    signer: Address,
+    nonce: u64,
+    new_fee: u128,
+) -> PyResult<String> {
+    let transaction = pool_transactions(provider, signer)
+        .await?
+        .into_iter()
+        .map(|(transaction, _)| transaction)
+        .find(|transaction| transaction.nonce() == nonce)
+        .ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "No pending transaction from {:?} with nonce {}",
+                signer, nonce
+            ))
+        })?;
+
+    let fee = transaction.max_fee_per_gas();
+    if new_fee <= fee {
+        return Err(PyValueError::new_err(format!(
+            "new_fee {} must be higher than the pending transaction's fee of {}",
+            new_fee, fee
+        )));
+    }
+    let tip = transaction.max_priority_fee_per_gas();
+
+    let mut request = transaction.into_request();
+    match tip {
+        Some(tip) => {
+            let raised = U256::from(tip) * U256::from(new_fee) / U256::from(fee);
+            request.max_fee_per_gas = Some(new_fee);
+            request.max_priority_fee_per_gas = Some(raised.to::<u128>().min(new_fee));
+        }
+        None => request.gas_price = Some(new_fee),
+    }
+
+    let pending = provider
+        .send_transaction(request)
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+    Ok(pending.tx_hash().to_string())
+}
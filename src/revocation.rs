@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use alkahest_rs::contracts::IEAS::{self, Revoked};
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+    rpc::types::Filter,
+    sol_types::SolEvent,
+};
+
+const REVOCATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Look up the EAS `Revoked` event for a revoked attestation
+///
+/// `Revoked` doesn't index the UID, so the logs are narrowed by the
+/// attestation's recipient, attester and schema, which are indexed, and
+/// searched from `from_block` on.
+pub async fn find_revocation<P: Provider>(
+    provider: &P,
+    eas: Address,
+    attestation: &IEAS::Attestation,
+    from_block: u64,
+) -> eyre::Result<Option<Revoked>> {
+    let filter = Filter::new()
+        .address(eas)
+        .event_signature(Revoked::SIGNATURE_HASH)
+        .topic1(attestation.recipient.into_word())
+        .topic2(attestation.attester.into_word())
+        .topic3(attestation.schema)
+        .from_block(from_block);
+
+    for log in provider.get_logs(&filter).await? {
+        let revoked = log.log_decode::<Revoked>()?.inner.data;
+        if revoked.uid == attestation.uid {
+            return Ok(Some(revoked));
+        }
+    }
+    Ok(None)
+}
+
+/// Wait until an attestation is revoked, returning its `Revoked` event
+///
+/// Polls the attestation's `revocationTime` and only searches the logs once it
+/// is set. If the attestation wasn't yet revoked when polling began, the search
+/// starts from the block polling began at; otherwise it starts from
+/// `from_block`, the genesis block by default.
+pub async fn wait_for_revocation<P: Provider>(
+    provider: &P,
+    eas: Address,
+    uid: FixedBytes<32>,
+    from_block: Option<u64>,
+) -> eyre::Result<Revoked> {
+    let eas_contract = IEAS::new(eas, provider);
+    let mut search_from = from_block.unwrap_or(0);
+    loop {
+        let polled_at = provider.get_block_number().await?;
+        let attestation = eas_contract.getAttestation(uid).call().await?;
+        if attestation.revocationTime != 0 {
+            if let Some(revoked) = find_revocation(provider, eas, &attestation, search_from).await? {
+                return Ok(revoked);
+            }
+            return Err(eyre::eyre!(
+                "Attestation {} is revoked but its Revoked event wasn't found from block {}",
+                uid,
+                search_from
+            ));
+        }
+        // Not revoked as of `polled_at`, so the event can't be in an earlier block
+        search_from = search_from.max(polled_at);
+        tokio::time::sleep(REVOCATION_POLL_INTERVAL).await;
+    }
+}
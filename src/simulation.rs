@@ -0,0 +1,578 @@
+use alkahest_rs::{
+    contracts::{
+        ERC1155PaymentObligation, ERC20EscrowObligation, ERC20PaymentObligation,
+        ERC721PaymentObligation,
+        IEAS::{self, Attested},
+    },
+    types::{ArbiterData, Erc1155Data, Erc20Data, Erc721Data, TokenBundleData},
+};
+use alloy::{
+    primitives::{Address, FixedBytes, U256},
+    providers::{DynProvider, Provider},
+    rpc::types::{
+        simulate::{SimBlock, SimCallResult, SimulatePayload},
+        TransactionRequest,
+    },
+    signers::local::PrivateKeySigner,
+    sol_types::{SolEvent, SolValue},
+};
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, IntoPyObject, PyResult, Python};
+
+use crate::{
+    demand_decoders::{decode_demand, Demand},
+    error_handling::map_eyre_to_pyerr,
+    escrow::{EscrowContracts, EscrowObligation, PaymentDemand},
+    tokens::{sign_eip2612_permit, IERC1155, IERC20, IERC721},
+    transactions::{
+        bundle_approvals, bundle_escrow, bundle_payment, bundle_payment_data, collect_escrow,
+        erc1155_escrow, erc1155_operator_approval, erc1155_payment, erc1155_payment_data,
+        erc20_approval, erc20_escrow, erc20_payment, erc20_payment_data, erc721_approval,
+        erc721_escrow, erc721_payment, erc721_payment_data, payment_demand,
+    },
+    types::TokenData,
+};
+
+/// Outcome of one simulated step of a barter
+#[pyclass]
+#[derive(Clone)]
+pub struct PySimulationStep {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub success: bool,
+    #[pyo3(get)]
+    pub gas_used: u64,
+    /// The node's description of why the step reverted, if it did
+    #[pyo3(get)]
+    pub revert_reason: Option<String>,
+}
+
+#[pymethods]
+impl PySimulationStep {
+    fn __repr__(&self) -> String {
+        match &self.revert_reason {
+            Some(reason) => format!(
+                "PySimulationStep(name='{}', success={}, revert_reason='{}')",
+                self.name, self.success, reason
+            ),
+            None => format!(
+                "PySimulationStep(name='{}', success={}, gas_used={})",
+                self.name, self.success, self.gas_used
+            ),
+        }
+    }
+}
+
+/// Per-step outcome of a simulated barter, in the order the steps would be sent
+#[pyclass]
+#[derive(Clone)]
+pub struct PySimulationReport {
+    #[pyo3(get)]
+    pub steps: Vec<PySimulationStep>,
+    /// UID the escrow attestation would have, if the escrow step succeeded
+    ///
+    /// The UID depends on the block the escrow is mined in, so the real one will
+    /// differ; this only confirms an attestation would be made.
+    #[pyo3(get)]
+    pub escrow_uid: Option<String>,
+    /// UID the payment attestation would have, if the payment step succeeded,
+    /// with the same caveat as `escrow_uid`
+    #[pyo3(get)]
+    pub fulfillment_uid: Option<String>,
+}
+
+#[pymethods]
+impl PySimulationReport {
+    /// Whether every step succeeded
+    #[getter]
+    pub fn success(&self) -> bool {
+        self.steps.iter().all(|step| step.success)
+    }
+
+    /// The first step that reverted, if any
+    #[getter]
+    pub fn failed_step(&self) -> Option<PySimulationStep> {
+        self.steps.iter().find(|step| !step.success).cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        let steps: Vec<String> = self.steps.iter().map(PySimulationStep::__repr__).collect();
+        format!(
+            "PySimulationReport(success={}, steps=[{}])",
+            self.success(),
+            steps.join(", ")
+        )
+    }
+}
+
+/// An asset a simulated barter escrows or asks for
+pub enum BarterAsset {
+    Erc20(Erc20Data),
+    Erc721(Erc721Data),
+    Erc1155(Erc1155Data),
+    TokenBundle(TokenBundleData),
+}
+
+impl TryFrom<TokenData> for BarterAsset {
+    type Error = eyre::Error;
+
+    fn try_from(value: TokenData) -> eyre::Result<Self> {
+        Ok(match value {
+            TokenData::Erc20(token) => Self::Erc20(token.try_into()?),
+            TokenData::Erc721(token) => Self::Erc721(token.try_into()?),
+            TokenData::Erc1155(token) => Self::Erc1155(token.try_into()?),
+            TokenData::TokenBundle(bundle) => Self::TokenBundle(bundle.try_into()?),
+        })
+    }
+}
+
+impl BarterAsset {
+    /// Approvals letting `spender` take the asset, several for a bundle
+    fn approvals<P: Provider>(&self, provider: &P, spender: Address) -> Vec<TransactionRequest> {
+        match self {
+            Self::Erc20(token) => vec![erc20_approval(provider, token, spender)],
+            Self::Erc721(token) => vec![erc721_approval(provider, token, spender)],
+            Self::Erc1155(token) => vec![erc1155_operator_approval(
+                provider,
+                token.address,
+                spender,
+                true,
+            )],
+            Self::TokenBundle(bundle) => bundle_approvals(provider, bundle, spender),
+        }
+    }
+
+    fn escrow_obligation(&self, addresses: &alkahest_rs::DefaultExtensionConfig) -> Address {
+        match self {
+            Self::Erc20(_) => addresses.erc20_addresses.escrow_obligation,
+            Self::Erc721(_) => addresses.erc721_addresses.escrow_obligation,
+            Self::Erc1155(_) => addresses.erc1155_addresses.escrow_obligation,
+            Self::TokenBundle(_) => addresses.token_bundle_addresses.escrow_obligation,
+        }
+    }
+
+    fn payment_obligation(&self, addresses: &alkahest_rs::DefaultExtensionConfig) -> Address {
+        match self {
+            Self::Erc20(_) => addresses.erc20_addresses.payment_obligation,
+            Self::Erc721(_) => addresses.erc721_addresses.payment_obligation,
+            Self::Erc1155(_) => addresses.erc1155_addresses.payment_obligation,
+            Self::TokenBundle(_) => addresses.token_bundle_addresses.payment_obligation,
+        }
+    }
+
+    /// Escrow of the asset until `item` is fulfilled
+    fn escrow<P: Provider>(
+        &self,
+        provider: &P,
+        escrow_obligation: Address,
+        item: ArbiterData,
+        expiration: u64,
+    ) -> TransactionRequest {
+        match self {
+            Self::Erc20(token) => {
+                erc20_escrow(provider, escrow_obligation, token, item, expiration)
+            }
+            Self::Erc721(token) => {
+                erc721_escrow(provider, escrow_obligation, token, item, expiration)
+            }
+            Self::Erc1155(token) => {
+                erc1155_escrow(provider, escrow_obligation, token, item, expiration)
+            }
+            Self::TokenBundle(bundle) => {
+                bundle_escrow(provider, escrow_obligation, bundle, item, expiration)
+            }
+        }
+    }
+
+    /// The demand an escrow makes for the asset paid to `payee`, along with the
+    /// payment fulfilling it
+    fn payment<P: Provider>(
+        &self,
+        provider: &P,
+        payment_obligation: Address,
+        payee: Address,
+    ) -> (ArbiterData, TransactionRequest) {
+        match self {
+            Self::Erc20(token) => {
+                let data = erc20_payment_data(token, payee);
+                (
+                    payment_demand(payment_obligation, &data),
+                    erc20_payment(provider, payment_obligation, data),
+                )
+            }
+            Self::Erc721(token) => {
+                let data = erc721_payment_data(token, payee);
+                (
+                    payment_demand(payment_obligation, &data),
+                    erc721_payment(provider, payment_obligation, data),
+                )
+            }
+            Self::Erc1155(token) => {
+                let data = erc1155_payment_data(token, payee);
+                (
+                    payment_demand(payment_obligation, &data),
+                    erc1155_payment(provider, payment_obligation, data),
+                )
+            }
+            Self::TokenBundle(bundle) => {
+                let data = bundle_payment_data(bundle, payee);
+                (
+                    payment_demand(payment_obligation, &data),
+                    bundle_payment(provider, payment_obligation, data),
+                )
+            }
+        }
+    }
+}
+
+/// Simulate a whole barter without sending anything: `signer` escrowing `price`
+/// for `ask`, and `fulfiller` paying `ask` and collecting the escrow
+///
+/// The steps are `signer`'s approvals and escrow, then `fulfiller`'s approvals,
+/// payment and collection, with one approval per token of a bundle. They run in
+/// order in one `eth_simulateV1` block, so each sees the state the earlier ones
+/// leave behind. The collection needs the escrow's and the payment's UIDs, so the
+/// steps before it are simulated first to learn them, then all of them together;
+/// if the escrow or the payment reverts, the collection isn't simulated. The
+/// escrow demands `ask` paid to `signer` through its payment obligation, the same
+/// terms the `buy_*_for_*` helpers create.
+pub async fn simulate_barter(
+    provider: &DynProvider,
+    signer: Address,
+    fulfiller: Address,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+    price: BarterAsset,
+    ask: BarterAsset,
+    expiration: u64,
+) -> eyre::Result<PySimulationReport> {
+    let escrow_obligation = price.escrow_obligation(addresses);
+    let payment_obligation = ask.payment_obligation(addresses);
+    let (demand, pay) = ask.payment(provider, payment_obligation, signer);
+
+    let mut names = Vec::new();
+    let mut calls = Vec::new();
+    for approval in price.approvals(provider, escrow_obligation) {
+        names.push("approve");
+        calls.push(approval.from(signer));
+    }
+    let escrow_step = calls.len();
+    names.push("escrow");
+    calls.push(
+        price
+            .escrow(provider, escrow_obligation, demand, expiration)
+            .from(signer),
+    );
+    for approval in ask.approvals(provider, payment_obligation) {
+        names.push("approve_payment");
+        calls.push(approval.from(fulfiller));
+    }
+    let pay_step = calls.len();
+    names.push("pay");
+    calls.push(pay.from(fulfiller));
+
+    let mut results = simulate_calls(provider, calls.clone()).await?;
+    let escrow_uid = attested_uid(&results[escrow_step]);
+    let fulfillment_uid = attested_uid(&results[pay_step]);
+    if let (Some(escrow_uid), Some(fulfillment_uid)) = (escrow_uid, fulfillment_uid) {
+        names.push("collect");
+        calls.push(
+            collect_escrow(provider, escrow_obligation, escrow_uid, fulfillment_uid)
+                .from(fulfiller),
+        );
+        results = simulate_calls(provider, calls).await?;
+    }
+
+    let steps = names
+        .into_iter()
+        .zip(results)
+        .map(|(name, result)| PySimulationStep {
+            name: name.to_string(),
+            success: result.status,
+            gas_used: result.gas_used,
+            revert_reason: result.error.map(|error| error.message),
+        })
+        .collect();
+
+    Ok(PySimulationReport {
+        steps,
+        escrow_uid: escrow_uid.map(|uid| uid.to_string()),
+        fulfillment_uid: fulfillment_uid.map(|uid| uid.to_string()),
+    })
+}
+
+/// Gas every transaction pays before executing, which a contract-internal call
+/// doesn't
+const INTRINSIC_GAS: u64 = 21_000;
+
+/// Estimated gas for the two ways of funding an ERC20 escrow
+#[derive(IntoPyObject)]
+pub struct PermitCostComparison {
+    /// A separate `approve` transaction, then the escrow
+    pub approve_path: u64,
+    /// The escrow with a permit, or None if the token doesn't support EIP-2612
+    pub permit_path: Option<u64>,
+    /// "approve" or "permit", whichever is estimated to cost less gas overall
+    pub cheaper: String,
+}
+
+/// Estimate the gas of escrowing `price` after an approval versus with a permit
+///
+/// Both paths are simulated with `eth_simulateV1`. The permit path is simulated
+/// as the token's `permit` followed by the escrow; since a permit escrow makes
+/// that call inside one transaction, one transaction's intrinsic gas is taken
+/// off, making it an approximation. The escrow has an empty demand, so a real
+/// demand adds its calldata cost to both paths equally.
+pub async fn compare_permit_cost<P: Provider>(
+    provider: &P,
+    signer: &PrivateKeySigner,
+    escrow_obligation: Address,
+    price: Erc20Data,
+) -> eyre::Result<PermitCostComparison> {
+    let owner = signer.address();
+    let escrow = ERC20EscrowObligation::new(escrow_obligation, provider)
+        .doObligation(
+            ERC20EscrowObligation::ObligationData {
+                token: price.address,
+                amount: price.value,
+                arbiter: Address::ZERO,
+                demand: Default::default(),
+            },
+            0,
+        )
+        .from(owner)
+        .into_transaction_request();
+    let approve = IERC20::new(price.address, provider)
+        .approve(escrow_obligation, price.value)
+        .from(owner)
+        .into_transaction_request();
+
+    let results = simulate_calls(provider, vec![approve, escrow.clone()]).await?;
+    if let Some(failed) = results.iter().find(|result| !result.status) {
+        eyre::bail!(
+            "Escrowing {} of token {:?} would revert: {}",
+            price.value,
+            price.address,
+            failed
+                .error
+                .as_ref()
+                .map_or("no reason given", |error| error.message.as_str())
+        );
+    }
+    let approve_path = results.iter().map(|result| result.gas_used).sum::<u64>();
+
+    let permit_path = match sign_eip2612_permit(
+        provider,
+        signer,
+        price.address,
+        escrow_obligation,
+        price.value,
+        U256::MAX,
+    )
+    .await
+    {
+        Ok(permit) => {
+            let results = simulate_calls(provider, vec![permit, escrow]).await?;
+            results.iter().all(|result| result.status).then(|| {
+                results[0].gas_used.saturating_sub(INTRINSIC_GAS) + results[1].gas_used
+            })
+        }
+        // No `nonces`/`DOMAIN_SEPARATOR`, so no EIP-2612 permit
+        Err(_) => None,
+    };
+
+    let cheaper = match permit_path {
+        Some(permit_path) if permit_path < approve_path => "permit",
+        _ => "approve",
+    };
+    Ok(PermitCostComparison {
+        approve_path,
+        permit_path,
+        cheaper: cheaper.to_string(),
+    })
+}
+
+/// What fulfilling an escrow would cost and earn the fulfiller
+#[derive(IntoPyObject)]
+pub struct FulfillmentEconomics {
+    /// Token type of the escrow: "erc20", "erc721", "erc1155" or "token_bundle"
+    pub token_type: String,
+    /// The escrowed assets the fulfiller receives
+    pub offer: EscrowObligation,
+    /// The payment the escrow demands
+    pub payment: PaymentDemand,
+    /// Gas to approve the payment, make it and collect the escrow, or None if the
+    /// simulation reverted
+    pub gas_estimate: Option<u64>,
+    /// The node's current gas price in wei
+    pub gas_price: u128,
+    /// `gas_estimate` times `gas_price` in wei, as a decimal string
+    pub gas_cost_wei: Option<String>,
+    /// Why the simulation reverted, such as a balance too low for the payment
+    pub revert_reason: Option<String>,
+}
+
+/// Approval and payment transactions fulfilling an escrow's payment demand
+fn payment_calls<P: Provider>(
+    provider: &P,
+    fulfiller: Address,
+    payment: &PaymentDemand,
+    demand: &[u8],
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> eyre::Result<[TransactionRequest; 2]> {
+    let calls = match payment {
+        PaymentDemand::Erc20(_) => {
+            let obligation = addresses.erc20_addresses.payment_obligation;
+            let data = ERC20PaymentObligation::ObligationData::abi_decode(demand)?;
+            [
+                IERC20::new(data.token, provider)
+                    .approve(obligation, data.amount)
+                    .into_transaction_request(),
+                ERC20PaymentObligation::new(obligation, provider)
+                    .doObligation(data)
+                    .into_transaction_request(),
+            ]
+        }
+        PaymentDemand::Erc721(_) => {
+            let obligation = addresses.erc721_addresses.payment_obligation;
+            let data = ERC721PaymentObligation::ObligationData::abi_decode(demand)?;
+            [
+                IERC721::new(data.token, provider)
+                    .approve(obligation, data.tokenId)
+                    .into_transaction_request(),
+                ERC721PaymentObligation::new(obligation, provider)
+                    .doObligation(data)
+                    .into_transaction_request(),
+            ]
+        }
+        PaymentDemand::Erc1155(_) => {
+            let obligation = addresses.erc1155_addresses.payment_obligation;
+            let data = ERC1155PaymentObligation::ObligationData::abi_decode(demand)?;
+            [
+                IERC1155::new(data.token, provider)
+                    .setApprovalForAll(obligation, true)
+                    .into_transaction_request(),
+                ERC1155PaymentObligation::new(obligation, provider)
+                    .doObligation(data)
+                    .into_transaction_request(),
+            ]
+        }
+    };
+    Ok(calls.map(|call| call.from(fulfiller)))
+}
+
+/// Estimate what fulfilling an escrow by paying its demand would cost `fulfiller`
+///
+/// The approval, payment and collection are simulated with `eth_simulateV1`. The
+/// collection needs the payment's UID, so the approval and payment are simulated
+/// first to learn it, then all three together. The approval is always included,
+/// so a fulfiller who has already approved the payment spends a little less.
+/// The demand is decoded as `decode_demand` decodes it, and only escrows whose
+/// demand decodes to a payment can be estimated, since only a payment says what
+/// to pay.
+pub async fn fulfillment_economics<P: Provider>(
+    provider: &P,
+    fulfiller: Address,
+    escrow: &IEAS::Attestation,
+    contracts: &EscrowContracts,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+) -> PyResult<FulfillmentEconomics> {
+    let token_type = contracts.token_type(escrow.attester);
+    let offer = token_type
+        .and_then(|token_type| EscrowObligation::decode(token_type, &escrow.data))
+        .ok_or_else(|| {
+            PyValueError::new_err(format!("Attestation {} is not a known escrow", escrow.uid))
+        })?;
+    let payment = match Python::with_gil(|py| {
+        decode_demand(py, offer.arbiter(), offer.demand(), addresses)
+    })? {
+        Demand::Payment(payment) => payment,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Escrow {} demands something other than a payment",
+                escrow.uid
+            )))
+        }
+    };
+    let calls = payment_calls(provider, fulfiller, &payment, offer.demand(), addresses)
+        .map_err(map_eyre_to_pyerr)?;
+
+    let (results, gas_price) =
+        tokio::try_join!(simulate_calls(provider, calls.to_vec()), async {
+            provider.get_gas_price().await.map_err(eyre::Report::from)
+        })
+        .map_err(map_eyre_to_pyerr)?;
+    let results = match attested_uid(&results[1]) {
+        Some(fulfillment) => {
+            let collect = collect_escrow(provider, escrow.attester, escrow.uid, fulfillment)
+                .from(fulfiller);
+            let [approve, pay] = calls;
+            simulate_calls(provider, vec![approve, pay, collect])
+                .await
+                .map_err(map_eyre_to_pyerr)?
+        }
+        None => results,
+    };
+
+    let revert_reason = results.iter().find(|result| !result.status).map(|failed| {
+        failed
+            .error
+            .as_ref()
+            .map_or_else(|| "no reason given".to_string(), |error| error.message.clone())
+    });
+    let gas_estimate = revert_reason
+        .is_none()
+        .then(|| results.iter().map(|result| result.gas_used).sum::<u64>());
+
+    Ok(FulfillmentEconomics {
+        token_type: token_type.unwrap_or_default().to_string(),
+        offer,
+        payment,
+        gas_estimate,
+        gas_price,
+        gas_cost_wei: gas_estimate
+            .map(|gas| (U256::from(gas) * U256::from(gas_price)).to_string()),
+        revert_reason,
+    })
+}
+
+/// Run calls in order in one simulated block, returning each call's result
+async fn simulate_calls<P: Provider>(
+    provider: &P,
+    calls: Vec<TransactionRequest>,
+) -> eyre::Result<Vec<SimCallResult>> {
+    let expected = calls.len();
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimBlock {
+            calls,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let block = provider
+        .simulate(&payload)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| eyre::eyre!("Node returned no simulated block"))?;
+    if block.calls.len() != expected {
+        eyre::bail!(
+            "Node simulated {} calls, expected {}",
+            block.calls.len(),
+            expected
+        );
+    }
+    Ok(block.calls)
+}
+
+/// UID from the `Attested` event a simulated call emitted, if it succeeded
+fn attested_uid(result: &SimCallResult) -> Option<FixedBytes<32>> {
+    if !result.status {
+        return None;
+    }
+    result
+        .logs
+        .iter()
+        .filter(|log| log.topic0() == Some(&Attested::SIGNATURE_HASH))
+        .find_map(|log| log.log_decode::<Attested>().ok())
+        .map(|log| log.inner.uid)
+}
@@ -0,0 +1,120 @@
+use alkahest_rs::contracts::IEAS;
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+};
+use pyo3::{
+    exceptions::PyValueError, pyclass, pymethods, IntoPyObjectExt, PyObject, PyResult, Python,
+};
+
+use crate::{
+    demand_decoders::decode_demand,
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
+    escrow::{find_escrow_claim, latest_block_timestamp, EscrowContracts, EscrowObligation},
+    explain::arbiter_name,
+};
+
+/// Everything an escrow offers and asks for, with where it stands
+#[pyclass]
+pub struct PyEscrowTerms {
+    #[pyo3(get)]
+    pub uid: String,
+    /// "erc20", "erc721", "erc1155" or "token_bundle"
+    #[pyo3(get)]
+    pub token_type: String,
+    /// The escrowed assets, as the escrow obligation data of the token type
+    #[pyo3(get)]
+    pub offer: PyObject,
+    #[pyo3(get)]
+    pub arbiter: String,
+    /// Name of the arbiter in the address config, or None if it isn't a known one
+    #[pyo3(get)]
+    pub arbiter_name: Option<String>,
+    /// The demand as `decode_demand` on the escrow data returns it
+    #[pyo3(get)]
+    pub demand: PyObject,
+    /// None if the escrow never expires
+    #[pyo3(get)]
+    pub expiration_time: Option<u64>,
+    #[pyo3(get)]
+    pub depositor: String,
+    /// "open", "expired", "claimed", or "revoked" for an escrow revoked without
+    /// being claimed, such as one reclaimed after expiring
+    #[pyo3(get)]
+    pub status: String,
+}
+
+#[pymethods]
+impl PyEscrowTerms {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyEscrowTerms(uid='{}', token_type='{}', arbiter='{}', depositor='{}', status='{}')",
+            self.uid, self.token_type, self.arbiter, self.depositor, self.status
+        )
+    }
+}
+
+/// Read an escrow and describe its terms and status
+///
+/// The status is judged against the latest block's timestamp, and the claim is
+/// looked up in the escrow contract's `EscrowClaimed` logs from genesis.
+pub async fn escrow_terms<P: Provider>(
+    provider: &P,
+    contracts: &EscrowContracts,
+    addresses: &alkahest_rs::DefaultExtensionConfig,
+    buy_attestation: FixedBytes<32>,
+) -> PyResult<PyEscrowTerms> {
+    let (escrow, now) = tokio::try_join!(
+        async {
+            IEAS::new(contracts.eas, provider)
+                .getAttestation(buy_attestation)
+                .call()
+                .await
+                .map_err(eyre::Report::from)
+        },
+        latest_block_timestamp(provider),
+    )
+    .map_err(map_eyre_to_pyerr)?;
+    if escrow.uid == FixedBytes::<32>::ZERO {
+        return Err(PyValueError::new_err(format!(
+            "Escrow attestation {} not found",
+            buy_attestation
+        )));
+    }
+    let token_type = contracts.token_type(escrow.attester);
+    let obligation = token_type
+        .and_then(|token_type| EscrowObligation::decode(token_type, &escrow.data))
+        .ok_or_else(|| {
+            PyValueError::new_err(format!("Attestation {} is not a known escrow", buy_attestation))
+        })?;
+
+    let claim = find_escrow_claim(provider, escrow.attester, buy_attestation, None)
+        .await
+        .map_err(map_eyre_to_pyerr)?;
+    let status = if claim.is_some() {
+        "claimed"
+    } else if escrow.revocationTime != 0 {
+        "revoked"
+    } else if escrow.expirationTime != 0 && escrow.expirationTime <= now {
+        "expired"
+    } else {
+        "open"
+    };
+
+    let arbiter: Address = obligation.arbiter().parse().map_err(map_parse_to_pyerr)?;
+    Python::with_gil(|py| {
+        let demand = decode_demand(py, obligation.arbiter(), obligation.demand(), addresses)?;
+        Ok(PyEscrowTerms {
+            uid: escrow.uid.to_string(),
+            token_type: token_type.unwrap_or_default().to_string(),
+            arbiter: format!("{:?}", arbiter),
+            arbiter_name: arbiter_name(arbiter, addresses).map(str::to_string),
+            demand: demand.into_py_any(py)?,
+            offer: obligation.into_py_any(py)?,
+            // Zero means the escrow never expires
+            expiration_time: (escrow.expirationTime != 0).then_some(escrow.expirationTime),
+            depositor: format!("{:?}", escrow.recipient),
+            status: status.to_string(),
+        })
+    })
+}
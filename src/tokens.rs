@@ -0,0 +1,300 @@
+use alkahest_rs::types::{Erc20Data, WalletProvider};
+use alloy::{
+    primitives::{keccak256, Address, FixedBytes, U256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+    signers::{local::PrivateKeySigner, Signer},
+    sol,
+    sol_types::{SolCall, SolValue},
+};
+use pyo3::{exceptions::PyValueError, types::PyAnyMethods, Bound, FromPyObject, PyAny, PyResult};
+
+use crate::error_handling::{
+    map_eyre_to_pyerr, map_parse_to_pyerr, InsufficientBalanceError, TransactionRevertError,
+};
+
+// Minimal token interfaces for the reads the alkahest-rs modules don't expose
+sol! {
+    #[sol(rpc)]
+    interface IERC20 {
+        function allowance(address owner, address spender) external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function approve(address spender, uint256 value) external returns (bool);
+    }
+
+    #[sol(rpc)]
+    interface IERC721 {
+        function isApprovedForAll(address owner, address operator) external view returns (bool);
+        function approve(address to, uint256 tokenId) external;
+    }
+
+    #[sol(rpc)]
+    interface IERC1155 {
+        function isApprovedForAll(address account, address operator) external view returns (bool);
+        function setApprovalForAll(address operator, bool approved) external;
+    }
+
+    #[sol(rpc)]
+    interface IERC20Permit {
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function nonces(address owner) external view returns (uint256);
+        function permit(
+            address owner,
+            address spender,
+            uint256 value,
+            uint256 deadline,
+            uint8 v,
+            bytes32 r,
+            bytes32 s
+        ) external;
+    }
+
+    #[sol(rpc)]
+    interface IDaiPermit {
+        function PERMIT_TYPEHASH() external view returns (bytes32);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function nonces(address holder) external view returns (uint256);
+        function permit(
+            address holder,
+            address spender,
+            uint256 nonce,
+            uint256 expiry,
+            bool allowed,
+            uint8 v,
+            bytes32 r,
+            bytes32 s
+        ) external;
+    }
+}
+
+/// The permit format a token's `permit` function follows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermitType {
+    /// EIP-2612: `permit(owner, spender, value, deadline, v, r, s)`
+    Eip2612,
+    /// DAI's original permit: `permit(holder, spender, nonce, expiry, allowed, v, r, s)`,
+    /// which has no amount and grants an unlimited allowance
+    Dai,
+}
+
+impl<'py> FromPyObject<'py> for PermitType {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "eip2612" => Ok(Self::Eip2612),
+            "dai" => Ok(Self::Dai),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown permit_type '{}', expected 'eip2612' or 'dai'",
+                other
+            ))),
+        }
+    }
+}
+
+fn dai_permit_typehash() -> FixedBytes<32> {
+    keccak256("Permit(address holder,address spender,uint256 nonce,uint256 expiry,bool allowed)")
+}
+
+/// Detect a token's permit format from its `PERMIT_TYPEHASH`
+///
+/// Tokens exposing DAI's typehash are DAI-style; anything else, including tokens
+/// whose `PERMIT_TYPEHASH` getter reverts or doesn't exist, is assumed to follow
+/// EIP-2612. Failures to reach the node are returned rather than guessed past.
+pub async fn detect_permit_type<P: Provider>(
+    provider: &P,
+    token: Address,
+) -> eyre::Result<PermitType> {
+    match IDaiPermit::new(token, provider).PERMIT_TYPEHASH().call().await {
+        Ok(typehash) if typehash == dai_permit_typehash() => Ok(PermitType::Dai),
+        Ok(_) => Ok(PermitType::Eip2612),
+        Err(e) if is_missing_getter(&e) => Ok(PermitType::Eip2612),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether a view call failed because the contract rejected it, as opposed to
+/// the node being unreachable or erroring
+///
+/// A missing getter either reverts, possibly without data, or returns nothing
+/// decodable.
+pub(crate) fn is_missing_getter(e: &alloy::contract::Error) -> bool {
+    match e {
+        alloy::contract::Error::ZeroData(..) | alloy::contract::Error::AbiError(_) => true,
+        alloy::contract::Error::TransportError(e) => e.as_error_resp().is_some_and(|resp| {
+            resp.as_revert_data().is_some() || resp.message.contains("revert")
+        }),
+        _ => false,
+    }
+}
+
+/// EIP-712 digest of a permit, from the token's domain separator and the hash of
+/// the permit struct
+fn permit_digest(domain_separator: FixedBytes<32>, struct_hash: FixedBytes<32>) -> FixedBytes<32> {
+    keccak256([&[0x19, 0x01][..], domain_separator.as_slice(), struct_hash.as_slice()].concat())
+}
+
+/// Sign an EIP-2612 permit for `spender` to take `value` of `token`, returning
+/// the `permit` call as a transaction from the signer
+pub async fn sign_eip2612_permit<P: Provider>(
+    provider: &P,
+    signer: &PrivateKeySigner,
+    token: Address,
+    spender: Address,
+    value: U256,
+    deadline: U256,
+) -> eyre::Result<TransactionRequest> {
+    let owner = signer.address();
+    let erc20 = IERC20Permit::new(token, provider);
+    let nonce = erc20.nonces(owner).call().await?;
+    let domain_separator = erc20.DOMAIN_SEPARATOR().call().await?;
+
+    let typehash = keccak256(
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+    );
+    let struct_hash =
+        keccak256((typehash, owner, spender, value, nonce, deadline).abi_encode_params());
+    let signature = signer
+        .sign_hash(&permit_digest(domain_separator, struct_hash))
+        .await?;
+
+    Ok(erc20
+        .permit(
+            owner,
+            spender,
+            value,
+            deadline,
+            27 + signature.v() as u8,
+            signature.r().into(),
+            signature.s().into(),
+        )
+        .from(owner)
+        .into_transaction_request())
+}
+
+/// Sign and send a DAI-style permit giving `spender` an unlimited allowance
+///
+/// The permit is submitted as its own transaction, since the obligation contracts'
+/// permit entry points only accept EIP-2612 signatures. It lapses at `expiry`, a
+/// unix timestamp, and DAI treats an expiry of zero as never expiring.
+pub async fn send_dai_permit(
+    provider: &WalletProvider,
+    signer: &PrivateKeySigner,
+    token: Address,
+    spender: Address,
+    expiry: u64,
+) -> eyre::Result<()> {
+    let holder = signer.address();
+    let dai = IDaiPermit::new(token, provider);
+    let nonce = dai.nonces(holder).call().await?;
+    let domain_separator = dai.DOMAIN_SEPARATOR().call().await?;
+    let expiry = U256::from(expiry);
+
+    let struct_hash = keccak256(
+        (dai_permit_typehash(), holder, spender, nonce, expiry, true).abi_encode_params(),
+    );
+    let signature = signer
+        .sign_hash(&permit_digest(domain_separator, struct_hash))
+        .await?;
+
+    let receipt = dai
+        .permit(
+            holder,
+            spender,
+            nonce,
+            expiry,
+            true,
+            27 + signature.v() as u8,
+            signature.r().into(),
+            signature.s().into(),
+        )
+        .send()
+        .await?
+        .get_receipt()
+        .await?;
+    if !receipt.status() {
+        eyre::bail!(
+            "DAI-style permit on token {:?} for spender {:?} reverted in {}",
+            token,
+            spender,
+            receipt.transaction_hash
+        );
+    }
+    Ok(())
+}
+
+/// Simulate an ERC20 `approve` and raise if the token reports failure
+///
+/// Non-compliant tokens can return `false` from `approve` instead of reverting, and
+/// the transaction still mines. Tokens that return no data at all are treated as
+/// succeeding, and a reverting simulation is left for the real transaction to
+/// surface.
+pub async fn check_erc20_approve<P: Provider>(
+    provider: &P,
+    token: &Erc20Data,
+    owner: Address,
+    spender: Address,
+) -> PyResult<()> {
+    let call = IERC20::approveCall {
+        spender,
+        value: token.value,
+    };
+    let tx = TransactionRequest::default()
+        .from(owner)
+        .to(token.address)
+        .input(call.abi_encode().into());
+
+    let Ok(output) = provider.call(tx).await else {
+        return Ok(());
+    };
+    if !output.is_empty() && output.iter().all(|byte| *byte == 0) {
+        return Err(TransactionRevertError::new_err(format!(
+            "Token {:?} returned false from approve({:?}, {})",
+            token.address, spender, token.value
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a mined ERC20 approval actually left the requested allowance
+pub async fn confirm_erc20_allowance(
+    provider: &WalletProvider,
+    token: &Erc20Data,
+    owner: Address,
+    spender: Address,
+    transaction_hash: FixedBytes<32>,
+) -> PyResult<()> {
+    let allowance: U256 = IERC20::new(token.address, provider)
+        .allowance(owner, spender)
+        .call()
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+    if allowance < token.value {
+        return Err(TransactionRevertError::new_err(format!(
+            "Approval transaction {} mined but token {:?} reports an allowance of {} instead of {}",
+            transaction_hash, token.address, allowance, token.value
+        )));
+    }
+    Ok(())
+}
+
+/// Raise `InsufficientBalanceError` if `owner` holds less of a token than an
+/// escrow is about to take
+pub async fn check_erc20_balance<P: Provider>(
+    provider: &P,
+    token: &crate::types::Erc20Data,
+    owner: Address,
+) -> PyResult<()> {
+    let address: Address = token.address.parse().map_err(map_parse_to_pyerr)?;
+    let required = U256::from(token.value);
+    let available = IERC20::new(address, provider)
+        .balanceOf(owner)
+        .call()
+        .await
+        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+    if available < required {
+        return Err(InsufficientBalanceError::new_err(format!(
+            "Insufficient balance of token {:?} for {:?}: required {}, available {}",
+            address, owner, required, available
+        )));
+    }
+    Ok(())
+}
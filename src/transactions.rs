@@ -0,0 +1,268 @@
+use alkahest_rs::{
+    contracts::{
+        ERC1155EscrowObligation, ERC1155PaymentObligation, ERC20EscrowObligation,
+        ERC20PaymentObligation, ERC721EscrowObligation, ERC721PaymentObligation,
+        TokenBundleEscrowObligation, TokenBundlePaymentObligation,
+    },
+    types::{ArbiterData, Erc1155Data, Erc20Data, Erc721Data, TokenBundleData},
+};
+use alloy::{
+    primitives::{Address, FixedBytes},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+    sol_types::SolValue,
+};
+
+use crate::tokens::{IERC1155, IERC20, IERC721};
+
+pub fn erc20_approval<P: Provider>(
+    provider: &P,
+    token: &Erc20Data,
+    spender: Address,
+) -> TransactionRequest {
+    IERC20::new(token.address, provider)
+        .approve(spender, token.value)
+        .into_transaction_request()
+}
+
+pub fn erc721_approval<P: Provider>(
+    provider: &P,
+    token: &Erc721Data,
+    spender: Address,
+) -> TransactionRequest {
+    IERC721::new(token.address, provider)
+        .approve(spender, token.id)
+        .into_transaction_request()
+}
+
+pub fn erc1155_operator_approval<P: Provider>(
+    provider: &P,
+    token: Address,
+    operator: Address,
+    approved: bool,
+) -> TransactionRequest {
+    IERC1155::new(token, provider)
+        .setApprovalForAll(operator, approved)
+        .into_transaction_request()
+}
+
+/// Approvals letting `spender` take every asset of a bundle, one transaction per
+/// ERC20 and ERC721 token and one per ERC1155 contract
+pub fn bundle_approvals<P: Provider>(
+    provider: &P,
+    bundle: &TokenBundleData,
+    spender: Address,
+) -> Vec<TransactionRequest> {
+    let mut approvals: Vec<TransactionRequest> = bundle
+        .erc20s
+        .iter()
+        .map(|token| erc20_approval(provider, token, spender))
+        .chain(
+            bundle
+                .erc721s
+                .iter()
+                .map(|token| erc721_approval(provider, token, spender)),
+        )
+        .collect();
+    let mut erc1155s: Vec<Address> = bundle.erc1155s.iter().map(|token| token.address).collect();
+    erc1155s.sort();
+    erc1155s.dedup();
+    approvals.extend(
+        erc1155s
+            .into_iter()
+            .map(|token| erc1155_operator_approval(provider, token, spender, true)),
+    );
+    approvals
+}
+
+/// Collect an escrow held by any of the escrow obligation contracts
+///
+/// They all inherit `collectEscrow` from the same base contract, so the ERC20
+/// binding encodes the call for each of them.
+pub fn collect_escrow<P: Provider>(
+    provider: &P,
+    escrow_contract: Address,
+    buy_attestation: FixedBytes<32>,
+    fulfillment: FixedBytes<32>,
+) -> TransactionRequest {
+    ERC20EscrowObligation::new(escrow_contract, provider)
+        .collectEscrow(buy_attestation, fulfillment)
+        .into_transaction_request()
+}
+
+pub fn erc20_escrow<P: Provider>(
+    provider: &P,
+    escrow_obligation: Address,
+    price: &Erc20Data,
+    item: ArbiterData,
+    expiration: u64,
+) -> TransactionRequest {
+    let data = ERC20EscrowObligation::ObligationData {
+        token: price.address,
+        amount: price.value,
+        arbiter: item.arbiter,
+        demand: item.demand,
+    };
+    ERC20EscrowObligation::new(escrow_obligation, provider)
+        .doObligation(data, expiration)
+        .into_transaction_request()
+}
+
+pub fn erc721_escrow<P: Provider>(
+    provider: &P,
+    escrow_obligation: Address,
+    price: &Erc721Data,
+    item: ArbiterData,
+    expiration: u64,
+) -> TransactionRequest {
+    let data = ERC721EscrowObligation::ObligationData {
+        token: price.address,
+        tokenId: price.id,
+        arbiter: item.arbiter,
+        demand: item.demand,
+    };
+    ERC721EscrowObligation::new(escrow_obligation, provider)
+        .doObligation(data, expiration)
+        .into_transaction_request()
+}
+
+pub fn erc1155_escrow<P: Provider>(
+    provider: &P,
+    escrow_obligation: Address,
+    price: &Erc1155Data,
+    item: ArbiterData,
+    expiration: u64,
+) -> TransactionRequest {
+    let data = ERC1155EscrowObligation::ObligationData {
+        token: price.address,
+        tokenId: price.id,
+        amount: price.value,
+        arbiter: item.arbiter,
+        demand: item.demand,
+    };
+    ERC1155EscrowObligation::new(escrow_obligation, provider)
+        .doObligation(data, expiration)
+        .into_transaction_request()
+}
+
+pub fn bundle_escrow<P: Provider>(
+    provider: &P,
+    escrow_obligation: Address,
+    price: &TokenBundleData,
+    item: ArbiterData,
+    expiration: u64,
+) -> TransactionRequest {
+    let data = TokenBundleEscrowObligation::ObligationData {
+        arbiter: item.arbiter,
+        demand: item.demand,
+        erc20Tokens: price.erc20s.iter().map(|token| token.address).collect(),
+        erc20Amounts: price.erc20s.iter().map(|token| token.value).collect(),
+        erc721Tokens: price.erc721s.iter().map(|token| token.address).collect(),
+        erc721TokenIds: price.erc721s.iter().map(|token| token.id).collect(),
+        erc1155Tokens: price.erc1155s.iter().map(|token| token.address).collect(),
+        erc1155TokenIds: price.erc1155s.iter().map(|token| token.id).collect(),
+        erc1155Amounts: price.erc1155s.iter().map(|token| token.value).collect(),
+    };
+    TokenBundleEscrowObligation::new(escrow_obligation, provider)
+        .doObligation(data, expiration)
+        .into_transaction_request()
+}
+
+pub fn erc20_payment_data(
+    price: &Erc20Data,
+    payee: Address,
+) -> ERC20PaymentObligation::ObligationData {
+    ERC20PaymentObligation::ObligationData {
+        token: price.address,
+        amount: price.value,
+        payee,
+    }
+}
+
+pub fn erc721_payment_data(
+    price: &Erc721Data,
+    payee: Address,
+) -> ERC721PaymentObligation::ObligationData {
+    ERC721PaymentObligation::ObligationData {
+        token: price.address,
+        tokenId: price.id,
+        payee,
+    }
+}
+
+pub fn erc1155_payment_data(
+    price: &Erc1155Data,
+    payee: Address,
+) -> ERC1155PaymentObligation::ObligationData {
+    ERC1155PaymentObligation::ObligationData {
+        token: price.address,
+        tokenId: price.id,
+        amount: price.value,
+        payee,
+    }
+}
+
+pub fn bundle_payment_data(
+    price: &TokenBundleData,
+    payee: Address,
+) -> TokenBundlePaymentObligation::ObligationData {
+    TokenBundlePaymentObligation::ObligationData {
+        erc20Tokens: price.erc20s.iter().map(|token| token.address).collect(),
+        erc20Amounts: price.erc20s.iter().map(|token| token.value).collect(),
+        erc721Tokens: price.erc721s.iter().map(|token| token.address).collect(),
+        erc721TokenIds: price.erc721s.iter().map(|token| token.id).collect(),
+        erc1155Tokens: price.erc1155s.iter().map(|token| token.address).collect(),
+        erc1155TokenIds: price.erc1155s.iter().map(|token| token.id).collect(),
+        erc1155Amounts: price.erc1155s.iter().map(|token| token.value).collect(),
+        payee,
+    }
+}
+
+/// The arbiter and demand of an escrow asking for a payment through
+/// `payment_obligation`, the terms the `buy_*_for_*` helpers escrow for
+pub fn payment_demand<T: SolValue>(payment_obligation: Address, data: &T) -> ArbiterData {
+    ArbiterData {
+        arbiter: payment_obligation,
+        demand: data.abi_encode().into(),
+    }
+}
+
+pub fn erc20_payment<P: Provider>(
+    provider: &P,
+    payment_obligation: Address,
+    data: ERC20PaymentObligation::ObligationData,
+) -> TransactionRequest {
+    ERC20PaymentObligation::new(payment_obligation, provider)
+        .doObligation(data)
+        .into_transaction_request()
+}
+
+pub fn erc721_payment<P: Provider>(
+    provider: &P,
+    payment_obligation: Address,
+    data: ERC721PaymentObligation::ObligationData,
+) -> TransactionRequest {
+    ERC721PaymentObligation::new(payment_obligation, provider)
+        .doObligation(data)
+        .into_transaction_request()
+}
+
+pub fn erc1155_payment<P: Provider>(
+    provider: &P,
+    payment_obligation: Address,
+    data: ERC1155PaymentObligation::ObligationData,
+) -> TransactionRequest {
+    ERC1155PaymentObligation::new(payment_obligation, provider)
+        .doObligation(data)
+        .into_transaction_request()
+}
+
+pub fn bundle_payment<P: Provider>(
+    provider: &P,
+    payment_obligation: Address,
+    data: TokenBundlePaymentObligation::ObligationData,
+) -> TransactionRequest {
+    TokenBundlePaymentObligation::new(payment_obligation, provider)
+        .doObligation(data)
+        .into_transaction_request()
+}
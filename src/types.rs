@@ -1,7 +1,14 @@
-use alkahest_rs::{contracts::IEAS::Attested, sol_types::EscrowClaimed};
+use alkahest_rs::{
+    contracts::IEAS::{Attested, Revoked},
+    sol_types::EscrowClaimed,
+};
+use std::sync::{Arc, Mutex};
+
 use alloy::primitives::{FixedBytes, U256};
 use pyo3::{exceptions::PyValueError, pyclass, FromPyObject, IntoPyObject, PyErr, PyResult};
 
+use crate::error_handling::map_eyre_to_pyerr;
+
 macro_rules! client_address_config {
     ($name:ident) => {
         #[derive(FromPyObject)]
@@ -321,6 +328,78 @@ impl TryFrom<ArbiterData> for alkahest_rs::types::ArbiterData {
     }
 }
 
+/// The arbiter and demand escrows use when they're given no `item`, shared by a
+/// client's extension clients so setting it once covers them all
+#[derive(Clone, Default)]
+pub struct DefaultArbiter(Arc<Mutex<Option<alkahest_rs::types::ArbiterData>>>);
+
+impl DefaultArbiter {
+    pub fn set(&self, arbiter: Option<alkahest_rs::types::ArbiterData>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = arbiter;
+    }
+
+    /// Convert an escrow's `item`, or take the default when it's None
+    pub fn resolve(&self, item: Option<ArbiterData>) -> PyResult<alkahest_rs::types::ArbiterData> {
+        match item {
+            Some(item) => item.try_into().map_err(map_eyre_to_pyerr),
+            None => self
+                .0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone()
+                .ok_or_else(|| {
+                    PyValueError::new_err(
+                        "No item given and no default arbiter set; call set_default_arbiter first",
+                    )
+                }),
+        }
+    }
+}
+
+/// Multiplier applied to estimated gas limits, shared by a client and its
+/// extension clients so changing the client's setting covers them all
+#[derive(Clone)]
+pub struct GasMultiplier(Arc<Mutex<f64>>);
+
+impl GasMultiplier {
+    pub fn new(gas_multiplier: f64) -> Self {
+        Self(Arc::new(Mutex::new(gas_multiplier)))
+    }
+
+    pub fn get(&self) -> f64 {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, gas_multiplier: f64) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = gas_multiplier;
+    }
+
+    /// Check a per-call override, or take the shared setting when it's None
+    pub fn resolve(&self, gas_multiplier: Option<f64>) -> PyResult<f64> {
+        match gas_multiplier {
+            Some(gas_multiplier) => check_gas_multiplier(gas_multiplier),
+            None => Ok(self.get()),
+        }
+    }
+}
+
+impl Default for GasMultiplier {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+pub fn check_gas_multiplier(gas_multiplier: f64) -> PyResult<f64> {
+    if gas_multiplier.is_finite() && gas_multiplier >= 1.0 {
+        Ok(gas_multiplier)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "gas_multiplier must be at least 1.0, got {}",
+            gas_multiplier
+        )))
+    }
+}
+
 #[derive(FromPyObject)]
 #[pyo3(from_item_all)]
 pub struct Erc20Data {
@@ -349,13 +428,134 @@ pub struct PyErc20Data {
 
     #[pyo3(get)]
     pub value: u64,
+
+    /// Token decimals, only used for display
+    #[pyo3(get)]
+    pub decimals: Option<u8>,
+
+    /// Token symbol, only used for display
+    #[pyo3(get)]
+    pub symbol: Option<String>,
 }
 
 #[pymethods]
 impl PyErc20Data {
     #[new]
     pub fn new(address: String, value: u64) -> Self {
-        Self { address, value }
+        Self {
+            address,
+            value,
+            decimals: None,
+            symbol: None,
+        }
+    }
+
+    /// Copy with the token's decimals and symbol attached, so the repr shows a
+    /// human-readable amount such as `100.0 USDC` instead of base units
+    #[pyo3(signature = (decimals, symbol=None))]
+    pub fn with_metadata(&self, decimals: u8, symbol: Option<String>) -> Self {
+        Self {
+            decimals: Some(decimals),
+            symbol,
+            ..self.clone()
+        }
+    }
+
+    fn __add__(&self, other: &Self) -> PyResult<Self> {
+        self.check_same_token(other)?;
+        let value = self.value.checked_add(other.value).ok_or_else(|| {
+            pyo3::exceptions::PyOverflowError::new_err("ERC20 amount overflow")
+        })?;
+        Ok(Self {
+            value,
+            ..self.clone()
+        })
+    }
+
+    fn __sub__(&self, other: &Self) -> PyResult<Self> {
+        self.check_same_token(other)?;
+        let value = self.value.checked_sub(other.value).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Cannot subtract {} from {}: ERC20 amounts can't be negative",
+                other.value, self.value
+            ))
+        })?;
+        Ok(Self {
+            value,
+            ..self.clone()
+        })
+    }
+
+    fn __lt__(&self, other: &Self) -> PyResult<bool> {
+        self.check_same_token(other)?;
+        Ok(self.value < other.value)
+    }
+
+    fn __eq__(&self, other: &Self) -> bool {
+        self.same_token(other) && self.value == other.value
+    }
+
+    fn __repr__(&self) -> String {
+        let value = match self.decimals {
+            Some(decimals) => {
+                let amount = trim_fraction(&format_units(U256::from(self.value), decimals));
+                match &self.symbol {
+                    Some(symbol) => format!("{} {}", amount, symbol),
+                    None => amount,
+                }
+            }
+            None => self.value.to_string(),
+        };
+        format!("PyErc20Data(address={}, value={})", self.address, value)
+    }
+}
+
+/// Render a base-unit token amount as a fixed-point decimal string with exactly
+/// `decimals` fractional digits
+pub fn format_units(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    format!("{}.{}", whole, fraction)
+}
+
+/// Drop trailing fractional zeros, keeping at least one digit after the point
+fn trim_fraction(amount: &str) -> String {
+    match amount.split_once('.') {
+        Some((whole, fraction)) => {
+            let fraction = fraction.trim_end_matches('0');
+            format!("{}.{}", whole, if fraction.is_empty() { "0" } else { fraction })
+        }
+        None => format!("{}.0", amount),
+    }
+}
+
+impl PyErc20Data {
+    /// Token addresses are compared as addresses, so checksummed and lowercase
+    /// forms of the same token match
+    fn same_token(&self, other: &Self) -> bool {
+        match (
+            self.address.parse::<alloy::primitives::Address>(),
+            other.address.parse::<alloy::primitives::Address>(),
+        ) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => self.address.eq_ignore_ascii_case(&other.address),
+        }
+    }
+
+    fn check_same_token(&self, other: &Self) -> PyResult<()> {
+        if self.same_token(other) {
+            Ok(())
+        } else {
+            Err(PyValueError::new_err(format!(
+                "Token mismatch: {} and {}",
+                self.address, other.address
+            )))
+        }
     }
 }
 
@@ -444,11 +644,37 @@ impl TryFrom<TokenBundleData> for alkahest_rs::types::TokenBundleData {
     }
 }
 
+/// Any of the assets above, told apart by its keys: `erc20s`, `erc721s` and
+/// `erc1155s` for a bundle, `id` and `value` for ERC1155, `id` alone for ERC721
+/// and `value` alone for ERC20
+#[derive(FromPyObject)]
+pub enum TokenData {
+    TokenBundle(TokenBundleData),
+    Erc1155(Erc1155Data),
+    Erc721(Erc721Data),
+    Erc20(Erc20Data),
+}
+
 #[derive(IntoPyObject)]
 pub struct EscowClaimedLog {
     pub payment: String,
     pub fulfillment: String,
     pub fulfiller: String,
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+}
+
+// Claims are handed to Python as dicts, so they are read back from the same keys
+impl<'py> FromPyObject<'py> for EscowClaimedLog {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            payment: ob.get_item("payment")?.extract()?,
+            fulfillment: ob.get_item("fulfillment")?.extract()?,
+            fulfiller: ob.get_item("fulfiller")?.extract()?,
+            block_number: ob.get_item("block_number")?.extract()?,
+            transaction_hash: ob.get_item("transaction_hash")?.extract()?,
+        })
+    }
 }
 
 impl From<EscrowClaimed> for EscowClaimedLog {
@@ -457,6 +683,18 @@ impl From<EscrowClaimed> for EscowClaimedLog {
             payment: value.payment.to_string(),
             fulfillment: value.fulfillment.to_string(),
             fulfiller: value.fulfiller.to_string(),
+            block_number: None,
+            transaction_hash: None,
+        }
+    }
+}
+
+impl From<&alloy::rpc::types::Log<EscrowClaimed>> for EscowClaimedLog {
+    fn from(value: &alloy::rpc::types::Log<EscrowClaimed>) -> Self {
+        Self {
+            block_number: value.block_number,
+            transaction_hash: value.transaction_hash.map(|hash| hash.to_string()),
+            ..EscowClaimedLog::from(value.inner.data.clone())
         }
     }
 }
@@ -480,6 +718,25 @@ impl From<Attested> for AttestedLog {
     }
 }
 
+#[derive(IntoPyObject)]
+pub struct RevokedLog {
+    pub recipient: String,
+    pub attester: String,
+    pub uid: String,
+    pub schema_uid: String,
+}
+
+impl From<Revoked> for RevokedLog {
+    fn from(value: Revoked) -> Self {
+        Self {
+            recipient: value.recipient.to_string(),
+            attester: value.attester.to_string(),
+            uid: value.uid.to_string(),
+            schema_uid: value.schemaUID.to_string(),
+        }
+    }
+}
+
 #[derive(FromPyObject)]
 pub struct AttestationRequestData {
     pub recipient: String,
@@ -523,10 +780,35 @@ impl TryFrom<AttestationRequest> for alkahest_rs::contracts::IEAS::AttestationRe
     }
 }
 
+/// ERC20 allowances granted to the escrow and payment obligation contracts
+#[derive(IntoPyObject)]
+pub struct Erc20ApprovalStatus {
+    pub escrow: String,
+    pub payment: String,
+}
+
+/// Whether the escrow and payment obligation contracts are approved operators
+#[derive(IntoPyObject)]
+pub struct OperatorApprovalStatus {
+    pub escrow: bool,
+    pub payment: bool,
+}
+
+#[derive(IntoPyObject)]
+pub struct DecodedLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: Vec<u8>,
+    pub log_index: Option<u64>,
+    pub event: Option<String>,
+    pub args: Option<std::collections::HashMap<String, String>>,
+}
+
 #[derive(IntoPyObject)]
 pub struct LogWithHash<T> {
     pub log: T,
     pub transaction_hash: String,
+    pub logs: Option<Vec<DecodedLog>>,
 }
 
 #[pyclass]
@@ -1,4 +1,4 @@
-use crate::{types::PyDefaultExtensionConfig, PyAlkahestClient};
+use crate::{nonce_manager::NonceManager, types::PyDefaultExtensionConfig, PyAlkahestClient};
 use alkahest_rs::{
     types::WalletProvider,
     utils::{setup_test_environment, MockAddresses, TestContext},
@@ -31,6 +31,75 @@ impl PyWalletProvider {
             Ok(())
         })
     }
+
+    /// Turn anvil's mining of every transaction as it arrives on or off, to leave
+    /// transactions pending in tests
+    pub fn anvil_set_auto_mine<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        enabled: bool,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use alloy::providers::ext::AnvilApi;
+        use pyo3_async_runtimes::tokio::future_into_py;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            provider
+                .anvil_set_auto_mine(enabled)
+                .await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            Ok(())
+        })
+    }
+
+    /// Deploy contract creation code from the provider's signer, returning the new
+    /// contract's address
+    pub fn deploy<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        code: Vec<u8>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use alloy::{
+            network::TransactionBuilder, providers::Provider, rpc::types::TransactionRequest,
+        };
+        use pyo3_async_runtimes::tokio::future_into_py;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            let receipt = provider
+                .send_transaction(TransactionRequest::default().with_deploy_code(code))
+                .await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?
+                .get_receipt()
+                .await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            let address = receipt.contract_address.ok_or_else(|| {
+                pyo3::exceptions::PyRuntimeError::new_err("Deployment created no contract")
+            })?;
+            Ok(format!("{:?}", address))
+        })
+    }
+
+    pub fn anvil_mine<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        blocks: u64,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use alloy::providers::ext::AnvilApi;
+        use pyo3_async_runtimes::tokio::future_into_py;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            provider
+                .anvil_mine(Some(blocks), None)
+                .await
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+            Ok(())
+        })
+    }
 }
 
 #[pyclass]
@@ -97,16 +166,33 @@ impl EnvTestManager {
             .block_on(setup_test_environment())
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
 
+        let rpc_url = ctx.anvil.ws_endpoint_url().to_string();
+        // Connected like any other client, so the gas multiplier reaches their
+        // writes, but without a private key to sign DAI-style permits with
+        let connect = |signer: &alloy::signers::local::PrivateKeySigner| {
+            PyAlkahestClient::connect(
+                signer.clone(),
+                None,
+                NonceManager::for_signer(&rpc_url, signer.address()),
+                rpc_url.clone(),
+                Some(ctx.addresses.clone()),
+                1.0,
+                None,
+            )
+        };
+        let alice_client = connect(&ctx.alice)?;
+        let bob_client = connect(&ctx.bob)?;
+
         Ok(Self {
             runtime: rt,
-            rpc_url: ctx.anvil.ws_endpoint_url().to_string(),
+            rpc_url,
             god: ctx.god.address().to_string(),
             alice: ctx.alice.address().to_string(),
             bob: ctx.bob.address().to_string(),
             addresses: PyDefaultExtensionConfig::from(&ctx.addresses),
             mock_addresses: PyMockAddresses::from(&ctx.mock_addresses),
-            alice_client: PyAlkahestClient::from_client(ctx.alice_client.clone()),
-            bob_client: PyAlkahestClient::from_client(ctx.bob_client.clone()),
+            alice_client,
+            bob_client,
             god_wallet_provider: PyWalletProvider {
                 inner: ctx.god_provider.clone(),
             },